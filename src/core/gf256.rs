@@ -3,12 +3,15 @@
 // polynomial x^8 + x^4 + x^3 + x + 1 (0x11B). All operations are constant-time and
 // branch-free (mask-based conditionals) to resist timing side channels. No lookup tables
 // by default; feature `gf256-table` can enable tables for non-paranoid benchmarks.
+// Bulk slice kernels (`mul_slice`, `mul_add_slice`, `mul_pointwise`) dispatch at runtime to
+// AVX2/SSSE3/NEON paths using split-nibble register shuffles (no secret-indexed memory) with a
+// constant-time scalar fallback, mirroring `core::xor`.
 // Whitepaper sections 1.1/1.2 align with this implementation.
 
-#![forbid(unsafe_code)]
+// #![forbid(unsafe_code)] // Removed for SIMD/Optimization
 
 use core::ops::{Add, AddAssign, Mul, MulAssign};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
 /// The finite field element type, wrapping a u8.
 ///
@@ -368,6 +371,327 @@ pub fn poly_eval(coeffs: &[GF256], x: GF256) -> GF256 {
     result
 }
 
+// Split-nibble product tables for a fixed coefficient: c*x = LO[x & 0xF] ^ HI[x >> 4].
+// Looked up only through in-register byte shuffles (pshufb / tbl), never through memory.
+// Coefficients can be secret (MAC keys, Lagrange weights), so the tables are wiped on drop.
+#[inline(always)]
+fn nibble_tables(coeff: GF256) -> (Zeroizing<[u8; 16]>, Zeroizing<[u8; 16]>) {
+    let mut lo = Zeroizing::new([0u8; 16]);
+    let mut hi = Zeroizing::new([0u8; 16]);
+    for i in 0..16u8 {
+        lo[i as usize] = (coeff * GF256(i)).0;
+        hi[i as usize] = (coeff * GF256(i << 4)).0;
+    }
+    (lo, hi)
+}
+
+/// Multiplies every byte of `dst` by `coeff` in place: `dst[i] = dst[i] * coeff`.
+///
+/// # Safety Guarantees
+/// - Constant-time: Fixed iteration counts; SIMD paths use register shuffles only; the scalar
+///   tail uses the bit-serial `Mul`.
+/// - Side-channel resistant: No data-dependent branches or memory accesses.
+///
+/// # Performance
+/// - AVX2: 32 bytes/iteration; SSSE3/NEON: 16 bytes/iteration; scalar fallback otherwise.
+///
+/// # Whitepaper Compliance
+/// - Section 1.1/1.2: Bulk Horner steps for share evaluation and refresh.
+#[inline]
+pub fn mul_slice(dst: &mut [u8], coeff: GF256) {
+    let ptr = dst.as_mut_ptr();
+    // SAFETY: src and dst alias the same valid slice; kernels read each byte before writing it.
+    unsafe { mul_kernel(ptr as *const u8, ptr, dst.len(), coeff, false) }
+}
+
+/// Multiply-accumulate: `dst[i] = dst[i] + src[i] * coeff` over GF(2^8).
+///
+/// # Panics
+/// Panics if `dst.len() != src.len()` (programming error).
+///
+/// # Safety Guarantees
+/// - As in `mul_slice`.
+///
+/// # Whitepaper Compliance
+/// - Section 2.5: Lagrange recombination `S = sum lambda_j * y_j` over whole shares.
+#[inline]
+pub fn mul_add_slice(dst: &mut [u8], src: &[u8], coeff: GF256) {
+    assert_eq!(dst.len(), src.len(), "mul_add_slice: length mismatch");
+    // SAFETY: both pointers are valid for `dst.len()` bytes (asserted above).
+    unsafe { mul_kernel(src.as_ptr(), dst.as_mut_ptr(), dst.len(), coeff, true) }
+}
+
+/// Element-wise product: `dst[i] = dst[i] * src[i]` over GF(2^8).
+///
+/// Used where every lane has its own multiplier (e.g. the 64 SIP-64 evaluation points).
+///
+/// # Panics
+/// Panics if `dst.len() != src.len()` (programming error).
+///
+/// # Safety Guarantees
+/// - Constant-time: 8 fixed shift/mask rounds per vector; no branches on data.
+#[inline]
+pub fn mul_pointwise(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "mul_pointwise: length mismatch");
+    // SAFETY: both pointers are valid for `dst.len()` bytes (asserted above).
+    unsafe { pointwise_inner(dst.as_mut_ptr(), src.as_ptr(), dst.len()) }
+}
+
+/// Dispatch for `mul_slice` / `mul_add_slice`.
+///
+/// # Safety
+/// - `src` and `dst` must be valid for `len` bytes; they may alias exactly.
+#[inline(always)]
+unsafe fn mul_kernel(src: *const u8, dst: *mut u8, len: usize, coeff: GF256, add: bool) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if len >= 16 {
+            if is_x86_feature_detected!("avx2") {
+                let (lo, hi) = nibble_tables(coeff);
+                return mul_avx2(src, dst, len, &lo, &hi, coeff, add);
+            }
+            if is_x86_feature_detected!("ssse3") {
+                let (lo, hi) = nibble_tables(coeff);
+                return mul_ssse3(src, dst, len, &lo, &hi, coeff, add);
+            }
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if len >= 16 && core::arch::is_aarch64_feature_detected!("neon") {
+            let (lo, hi) = nibble_tables(coeff);
+            return mul_neon(src, dst, len, &lo, &hi, coeff, add);
+        }
+    }
+
+    mul_scalar(src, dst, len, coeff, add);
+}
+
+/// Scalar fallback (bit-serial constant-time multiply per byte).
+///
+/// # Safety
+/// - Pointers must be valid for `len` bytes.
+#[inline(always)]
+unsafe fn mul_scalar(src: *const u8, dst: *mut u8, len: usize, coeff: GF256, add: bool) {
+    let acc_mask = 0u8.wrapping_sub(add as u8);
+    for i in 0..len {
+        let p = (GF256(*src.add(i)) * coeff).0;
+        *dst.add(i) = p ^ (*dst.add(i) & acc_mask);
+    }
+}
+
+/// AVX2 split-nibble multiply (32 bytes per iteration).
+///
+/// # Safety
+/// - Requires AVX2 (checked at runtime); unaligned loads/stores; loop bound keeps accesses in range.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn mul_avx2(src: *const u8, dst: *mut u8, len: usize, lo: &[u8; 16], hi: &[u8; 16], coeff: GF256, add: bool) {
+    use core::arch::x86_64::*;
+    let lo_t = _mm256_broadcastsi128_si256(_mm_loadu_si128(lo.as_ptr() as *const __m128i));
+    let hi_t = _mm256_broadcastsi128_si256(_mm_loadu_si128(hi.as_ptr() as *const __m128i));
+    let mask = _mm256_set1_epi8(0x0F);
+    let mut i = 0;
+
+    while i + 32 <= len {
+        let v = _mm256_loadu_si256(src.add(i) as *const __m256i);
+        let l = _mm256_and_si256(v, mask);
+        let h = _mm256_and_si256(_mm256_srli_epi64(v, 4), mask);
+        let mut p = _mm256_xor_si256(_mm256_shuffle_epi8(lo_t, l), _mm256_shuffle_epi8(hi_t, h));
+        if add {
+            p = _mm256_xor_si256(p, _mm256_loadu_si256(dst.add(i) as *const __m256i));
+        }
+        _mm256_storeu_si256(dst.add(i) as *mut __m256i, p);
+        i += 32;
+    }
+
+    // Tail: scalar fallback for remaining bytes (0-31)
+    mul_scalar(src.add(i), dst.add(i), len - i, coeff, add);
+}
+
+/// SSSE3 split-nibble multiply (16 bytes per iteration).
+///
+/// # Safety
+/// - Requires SSSE3 (checked at runtime); unaligned loads/stores.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "ssse3")]
+unsafe fn mul_ssse3(src: *const u8, dst: *mut u8, len: usize, lo: &[u8; 16], hi: &[u8; 16], coeff: GF256, add: bool) {
+    use core::arch::x86_64::*;
+    let lo_t = _mm_loadu_si128(lo.as_ptr() as *const __m128i);
+    let hi_t = _mm_loadu_si128(hi.as_ptr() as *const __m128i);
+    let mask = _mm_set1_epi8(0x0F);
+    let mut i = 0;
+
+    while i + 16 <= len {
+        let v = _mm_loadu_si128(src.add(i) as *const __m128i);
+        let l = _mm_and_si128(v, mask);
+        let h = _mm_and_si128(_mm_srli_epi64(v, 4), mask);
+        let mut p = _mm_xor_si128(_mm_shuffle_epi8(lo_t, l), _mm_shuffle_epi8(hi_t, h));
+        if add {
+            p = _mm_xor_si128(p, _mm_loadu_si128(dst.add(i) as *const __m128i));
+        }
+        _mm_storeu_si128(dst.add(i) as *mut __m128i, p);
+        i += 16;
+    }
+
+    mul_scalar(src.add(i), dst.add(i), len - i, coeff, add);
+}
+
+/// NEON split-nibble multiply via `tbl` (16 bytes per iteration).
+///
+/// # Safety
+/// - Requires NEON (checked at runtime on aarch64); vld1q/vst1q accept any alignment.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn mul_neon(src: *const u8, dst: *mut u8, len: usize, lo: &[u8; 16], hi: &[u8; 16], coeff: GF256, add: bool) {
+    use core::arch::aarch64::*;
+    let lo_t = vld1q_u8(lo.as_ptr());
+    let hi_t = vld1q_u8(hi.as_ptr());
+    let mask = vdupq_n_u8(0x0F);
+    let mut i = 0;
+
+    while i + 16 <= len {
+        let v = vld1q_u8(src.add(i));
+        let l = vandq_u8(v, mask);
+        let h = vshrq_n_u8::<4>(v);
+        let mut p = veorq_u8(vqtbl1q_u8(lo_t, l), vqtbl1q_u8(hi_t, h));
+        if add {
+            p = veorq_u8(p, vld1q_u8(dst.add(i)));
+        }
+        vst1q_u8(dst.add(i), p);
+        i += 16;
+    }
+
+    mul_scalar(src.add(i), dst.add(i), len - i, coeff, add);
+}
+
+/// Dispatch for `mul_pointwise`.
+///
+/// # Safety
+/// - Pointers must be valid for `len` bytes.
+#[inline(always)]
+unsafe fn pointwise_inner(dst: *mut u8, src: *const u8, len: usize) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return pointwise_avx2(dst, src, len);
+        }
+        // SSE2 is part of the x86_64 baseline.
+        return pointwise_sse2(dst, src, len);
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if core::arch::is_aarch64_feature_detected!("neon") {
+            return pointwise_neon(dst, src, len);
+        }
+    }
+
+    #[allow(unreachable_code)]
+    pointwise_scalar(dst, src, len);
+}
+
+/// Scalar element-wise product.
+///
+/// # Safety
+/// - Pointers must be valid for `len` bytes.
+#[inline(always)]
+unsafe fn pointwise_scalar(dst: *mut u8, src: *const u8, len: usize) {
+    for i in 0..len {
+        *dst.add(i) = (GF256(*dst.add(i)) * GF256(*src.add(i))).0;
+    }
+}
+
+/// AVX2 element-wise product: MSB-first shift-and-add with masked reduction (32 lanes).
+///
+/// # Safety
+/// - Requires AVX2 (checked at runtime); unaligned loads/stores.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn pointwise_avx2(dst: *mut u8, src: *const u8, len: usize) {
+    use core::arch::x86_64::*;
+    let zero = _mm256_setzero_si256();
+    let poly = _mm256_set1_epi8(0x1B);
+    let mut i = 0;
+
+    while i + 32 <= len {
+        let a = _mm256_loadu_si256(dst.add(i) as *const __m256i);
+        let mut b = _mm256_loadu_si256(src.add(i) as *const __m256i);
+        let mut p = zero;
+        for _ in 0..8 {
+            // p = p * x (mod 0x11B); then add `a` where the current top bit of b is set.
+            let carry = _mm256_cmpgt_epi8(zero, p);
+            p = _mm256_xor_si256(_mm256_add_epi8(p, p), _mm256_and_si256(carry, poly));
+            let bit = _mm256_cmpgt_epi8(zero, b);
+            p = _mm256_xor_si256(p, _mm256_and_si256(a, bit));
+            b = _mm256_add_epi8(b, b);
+        }
+        _mm256_storeu_si256(dst.add(i) as *mut __m256i, p);
+        i += 32;
+    }
+
+    pointwise_scalar(dst.add(i), src.add(i), len - i);
+}
+
+/// SSE2 element-wise product (16 lanes); same schedule as the AVX2 path.
+///
+/// # Safety
+/// - SSE2 is always available on x86_64; unaligned loads/stores.
+#[cfg(target_arch = "x86_64")]
+unsafe fn pointwise_sse2(dst: *mut u8, src: *const u8, len: usize) {
+    use core::arch::x86_64::*;
+    let zero = _mm_setzero_si128();
+    let poly = _mm_set1_epi8(0x1B);
+    let mut i = 0;
+
+    while i + 16 <= len {
+        let a = _mm_loadu_si128(dst.add(i) as *const __m128i);
+        let mut b = _mm_loadu_si128(src.add(i) as *const __m128i);
+        let mut p = zero;
+        for _ in 0..8 {
+            let carry = _mm_cmpgt_epi8(zero, p);
+            p = _mm_xor_si128(_mm_add_epi8(p, p), _mm_and_si128(carry, poly));
+            let bit = _mm_cmpgt_epi8(zero, b);
+            p = _mm_xor_si128(p, _mm_and_si128(a, bit));
+            b = _mm_add_epi8(b, b);
+        }
+        _mm_storeu_si128(dst.add(i) as *mut __m128i, p);
+        i += 16;
+    }
+
+    pointwise_scalar(dst.add(i), src.add(i), len - i);
+}
+
+/// NEON element-wise product (16 lanes); same schedule as the AVX2 path.
+///
+/// # Safety
+/// - Requires NEON (checked at runtime on aarch64).
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn pointwise_neon(dst: *mut u8, src: *const u8, len: usize) {
+    use core::arch::aarch64::*;
+    let poly = vdupq_n_u8(0x1B);
+    let mut i = 0;
+
+    while i + 16 <= len {
+        let a = vld1q_u8(dst.add(i));
+        let mut b = vld1q_u8(src.add(i));
+        let mut p = vdupq_n_u8(0);
+        for _ in 0..8 {
+            let carry = vreinterpretq_u8_s8(vshrq_n_s8::<7>(vreinterpretq_s8_u8(p)));
+            p = veorq_u8(vshlq_n_u8::<1>(p), vandq_u8(carry, poly));
+            let bit = vreinterpretq_u8_s8(vshrq_n_s8::<7>(vreinterpretq_s8_u8(b)));
+            p = veorq_u8(p, vandq_u8(a, bit));
+            b = vshlq_n_u8::<1>(b);
+        }
+        vst1q_u8(dst.add(i), p);
+        i += 16;
+    }
+
+    pointwise_scalar(dst.add(i), src.add(i), len - i);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -440,4 +764,137 @@ mod tests {
         // Assuming tables correctly precomputed; test consistency with bit-serial.
         assert_eq!(GF256(0x02).mul_table(GF256(0x03)), GF256(0x06));
     }
+
+    fn sample(len: usize, seed: u8) -> Vec<u8> {
+        (0..len).map(|i| (i as u8).wrapping_mul(37).wrapping_add(seed).rotate_left(3)).collect()
+    }
+
+    #[test]
+    fn test_mul_slice_matches_scalar() {
+        for len in [0usize, 1, 15, 16, 17, 31, 32, 33, 100, 257] {
+            for c in 0u8..=255 {
+                let src = sample(len, c);
+                let mut dst = src.clone();
+                mul_slice(&mut dst, GF256(c));
+                for (d, s) in dst.iter().zip(src.iter()) {
+                    assert_eq!(*d, (GF256(*s) * GF256(c)).0, "len {} coeff {:02x}", len, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_add_slice_matches_scalar() {
+        for len in [0usize, 7, 16, 32, 48, 65, 1000] {
+            for c in [0u8, 1, 2, 0x53, 0x8E, 0xFF] {
+                let src = sample(len, 0x11);
+                let acc = sample(len, 0xA5);
+                let mut dst = acc.clone();
+                mul_add_slice(&mut dst, &src, GF256(c));
+                for i in 0..len {
+                    assert_eq!(dst[i], (GF256(acc[i]) + GF256(src[i]) * GF256(c)).0);
+                }
+            }
+        }
+    }
+
+    // Calls every kernel this host can run directly, not just the one dispatch picks.
+    #[test]
+    fn test_mul_kernels_agree() {
+        for len in [0usize, 1, 15, 16, 17, 31, 32, 33, 100, 257] {
+            for c in [0u8, 1, 2, 0x53, 0x8E, 0xFF] {
+                let coeff = GF256(c);
+                let src = sample(len, c);
+                let acc = sample(len, 0xA5);
+                #[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(unused_variables))]
+                let (lo, hi) = nibble_tables(coeff);
+                for add in [false, true] {
+                    let expect: Vec<u8> = (0..len)
+                        .map(|i| (GF256(src[i]) * coeff).0 ^ if add { acc[i] } else { 0 })
+                        .collect();
+                    let mut out = acc.clone();
+                    unsafe { mul_scalar(src.as_ptr(), out.as_mut_ptr(), len, coeff, add) };
+                    assert_eq!(out, expect, "scalar len {} coeff {:02x}", len, c);
+
+                    #[cfg(target_arch = "x86_64")]
+                    {
+                        if is_x86_feature_detected!("avx2") {
+                            let mut out = acc.clone();
+                            unsafe { mul_avx2(src.as_ptr(), out.as_mut_ptr(), len, &lo, &hi, coeff, add) };
+                            assert_eq!(out, expect, "avx2 len {} coeff {:02x}", len, c);
+                        }
+                        if is_x86_feature_detected!("ssse3") {
+                            let mut out = acc.clone();
+                            unsafe { mul_ssse3(src.as_ptr(), out.as_mut_ptr(), len, &lo, &hi, coeff, add) };
+                            assert_eq!(out, expect, "ssse3 len {} coeff {:02x}", len, c);
+                        }
+                    }
+
+                    #[cfg(target_arch = "aarch64")]
+                    {
+                        if core::arch::is_aarch64_feature_detected!("neon") {
+                            let mut out = acc.clone();
+                            unsafe { mul_neon(src.as_ptr(), out.as_mut_ptr(), len, &lo, &hi, coeff, add) };
+                            assert_eq!(out, expect, "neon len {} coeff {:02x}", len, c);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pointwise_kernels_agree() {
+        for len in [0usize, 5, 16, 31, 32, 33, 64, 99, 256] {
+            let a = sample(len, 0x3C);
+            let b = sample(len, 0xC3);
+            let expect: Vec<u8> = (0..len).map(|i| (GF256(a[i]) * GF256(b[i])).0).collect();
+            let mut out = a.clone();
+            unsafe { pointwise_scalar(out.as_mut_ptr(), b.as_ptr(), len) };
+            assert_eq!(out, expect, "scalar len {}", len);
+
+            #[cfg(target_arch = "x86_64")]
+            {
+                if is_x86_feature_detected!("avx2") {
+                    let mut out = a.clone();
+                    unsafe { pointwise_avx2(out.as_mut_ptr(), b.as_ptr(), len) };
+                    assert_eq!(out, expect, "avx2 len {}", len);
+                }
+                let mut out = a.clone();
+                unsafe { pointwise_sse2(out.as_mut_ptr(), b.as_ptr(), len) };
+                assert_eq!(out, expect, "sse2 len {}", len);
+            }
+
+            #[cfg(target_arch = "aarch64")]
+            {
+                if core::arch::is_aarch64_feature_detected!("neon") {
+                    let mut out = a.clone();
+                    unsafe { pointwise_neon(out.as_mut_ptr(), b.as_ptr(), len) };
+                    assert_eq!(out, expect, "neon len {}", len);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_pointwise_matches_scalar() {
+        for len in [0usize, 5, 16, 31, 32, 64, 99] {
+            let a = sample(len, 0x3C);
+            let b = sample(len, 0xC3);
+            let mut dst = a.clone();
+            mul_pointwise(&mut dst, &b);
+            for i in 0..len {
+                assert_eq!(dst[i], (GF256(a[i]) * GF256(b[i])).0);
+            }
+        }
+        // Exhaustive over one 256x256 grid row at a time.
+        let all: Vec<u8> = (0u8..=255).collect();
+        for c in 0u8..=255 {
+            let mut dst = all.clone();
+            mul_pointwise(&mut dst, &[c; 256]);
+            for (x, d) in dst.iter().enumerate() {
+                assert_eq!(*d, (GF256(x as u8) * GF256(c)).0);
+            }
+        }
+    }
 }
@@ -0,0 +1,139 @@
+#![forbid(unsafe_code)]
+// GF(2^16) arithmetic module.
+// Finite field arithmetic over GF(2^16) with the irreducible polynomial
+// x^16 + x^12 + x^3 + x + 1 (0x1100B). Used for Shamir sharing in groups of more than
+// 255 members (`mpc::wide`). All operations are constant-time and branch-free
+// (mask-based conditionals); no lookup tables.
+
+use core::ops::{Add, AddAssign, Mul, MulAssign};
+use zeroize::Zeroize;
+
+/// Low 16 bits of the reduction polynomial (x^16 = x^12 + x^3 + x + 1).
+const POLY_LOW: u16 = 0x100B;
+
+/// The finite field element type, wrapping a u16.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Zeroize)]
+#[repr(transparent)]
+pub struct GF65536(pub u16);
+
+impl From<u16> for GF65536 {
+    #[inline(always)]
+    fn from(value: u16) -> Self {
+        GF65536(value)
+    }
+}
+
+impl From<GF65536> for u16 {
+    #[inline(always)]
+    fn from(gf: GF65536) -> u16 {
+        gf.0
+    }
+}
+
+/// GF(2^16) addition: XOR.
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for GF65536 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        GF65536(self.0 ^ rhs.0)
+    }
+}
+
+impl AddAssign for GF65536 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// GF(2^16) multiplication: bit-serial with reduction modulo 0x1100B.
+///
+/// # Safety Guarantees
+/// - Constant-time: Fixed 16 iterations; mask-based conditionals (no branches).
+impl Mul for GF65536 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let mut a = self.0;
+        let b = rhs.0;
+        let mut p = 0u16;
+        for i in 0..16 {
+            p ^= a & 0u16.wrapping_sub((b >> i) & 1);
+            a = (a << 1) ^ (POLY_LOW & 0u16.wrapping_sub(a >> 15));
+        }
+        GF65536(p)
+    }
+}
+
+impl MulAssign for GF65536 {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl GF65536 {
+    /// Multiplicative inverse `a^(2^16 - 2)`; returns 0 for a=0 (same convention as `GF256::inv`).
+    ///
+    /// # Safety Guarantees
+    /// - Constant-time: Fixed Itoh–Tsujii chain (15 squarings, 6 multiplications).
+    #[inline(always)]
+    pub fn inv(self) -> Self {
+        // b_k = a^(2^k - 1); b_{i+j} = b_i^(2^j) * b_j
+        let b1 = self;
+        let b2 = b1.sqr_n(1) * b1;
+        let b4 = b2.sqr_n(2) * b2;
+        let b8 = b4.sqr_n(4) * b4;
+        let b12 = b8.sqr_n(4) * b4;
+        let b14 = b12.sqr_n(2) * b2;
+        let b15 = b14.sqr_n(1) * b1;
+        // a^(2^16 - 2) = (a^(2^15 - 1))^2; a=0 maps to 0 without a branch.
+        b15.sqr_n(1)
+    }
+
+    /// Computes self / rhs, returning None on division by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            None
+        } else {
+            Some(self * rhs.inv())
+        }
+    }
+
+    #[inline(always)]
+    fn sqr_n(self, n: usize) -> Self {
+        let mut x = self;
+        for _ in 0..n {
+            x = x * x;
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_reduction() {
+        assert_eq!(GF65536(0x8000) * GF65536(2), GF65536(POLY_LOW));
+        assert_eq!(GF65536(0x1234) * GF65536(1), GF65536(0x1234));
+        assert_eq!(GF65536(0) * GF65536(0xFFFF), GF65536(0));
+        // Commutativity and distributivity on a few points.
+        let (a, b, c) = (GF65536(0xBEEF), GF65536(0x1D2C), GF65536(0x0F0F));
+        assert_eq!(a * b, b * a);
+        assert_eq!(a * (b + c), a * b + a * c);
+    }
+
+    #[test]
+    fn test_inv_exhaustive() {
+        assert_eq!(GF65536(0).inv(), GF65536(0));
+        for a in 1u16..=u16::MAX {
+            assert_eq!(GF65536(a) * GF65536(a).inv(), GF65536(1), "inv({:04x})", a);
+        }
+        assert_eq!(GF65536(3).checked_div(GF65536(0)), None);
+    }
+}
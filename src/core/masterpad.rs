@@ -1,25 +1,77 @@
-#![forbid(unsafe_code)]
+// #![forbid(unsafe_code)] // Removed for memory-mapped pads (file mapping is unsafe)
 
 use crate::core::sip64::MAC_LEN;
-use zeroize::Zeroize;
+use crate::pad::burn::{burn_slice, BurnGuard};
+use zeroize::{Zeroize, Zeroizing};
+#[cfg(feature = "std")]
+use memmap2::{MmapMut, MmapOptions};
 
 // MasterPad provides constant-time, monotonic block allocation over a backing pad.
-// Blocks are laid out as `keystream || mac_key(64B)` and never reused.
-// No unsafe; strict bounds checking; suitable for OTP keystream provisioning.
+// Blocks are laid out as `keystream || mac_key(64B)` and never reused; each handed-out
+// block is burned in the backing pad as soon as the caller is done with it.
+// Strict bounds checking; suitable for OTP keystream provisioning.
+// Large pads can be memory-mapped from a file (`open_mapped`): pages are mlocked where
+// the rlimit allows and excluded from core dumps. On drop the consumed range (everything
+// below the position, including skipped gaps) is burned in the file; reopening resumes past
+// that burned prefix, so later sessions keep the unused keystream and never reuse any.
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum PadError {
     Insufficient,
+    // Opening or mapping a pad file failed.
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
 }
 
 pub enum PadBuf {
     Heap(Vec<u8>),
+    // File mapping whose pages could not be locked (rlimit / privileges).
+    #[cfg(feature = "std")]
+    Mmap(MmapMut),
+    // File mapping with pages locked in RAM.
+    #[cfg(feature = "std")]
+    Locked(MmapMut),
+}
+
+impl PadBuf {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            PadBuf::Heap(v) => v,
+            #[cfg(feature = "std")]
+            PadBuf::Mmap(m) | PadBuf::Locked(m) => m,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self {
+            PadBuf::Heap(v) => v,
+            #[cfg(feature = "std")]
+            PadBuf::Mmap(m) | PadBuf::Locked(m) => m,
+        }
+    }
+
+    // Whether pages are locked against swapping.
+    pub fn is_locked(&self) -> bool {
+        match self {
+            PadBuf::Heap(_) => false,
+            #[cfg(feature = "std")]
+            PadBuf::Mmap(_) => false,
+            #[cfg(feature = "std")]
+            PadBuf::Locked(_) => true,
+        }
+    }
 }
 
 impl Drop for PadBuf {
     fn drop(&mut self) {
         match self {
             PadBuf::Heap(v) => v.zeroize(),
+            #[cfg(feature = "std")]
+            PadBuf::Mmap(m) | PadBuf::Locked(m) => {
+                // MasterPad burned the consumed range; push it to the file before
+                // munmap (and munlock) in MmapMut's drop.
+                let _ = m.flush();
+            }
         }
     }
 }
@@ -29,48 +81,105 @@ pub struct MasterPad {
     pos: usize,
 }
 
+impl Drop for MasterPad {
+    fn drop(&mut self) {
+        // Heap pads are zeroized whole by PadBuf; mapped pads keep their unused tail.
+        if !matches!(self.buf, PadBuf::Heap(_)) {
+            let pos = self.pos;
+            burn_slice(&mut self.buf.as_mut_slice()[..pos]);
+        }
+    }
+}
+
 impl MasterPad {
     // Create from heap buffer; position starts at 0.
     pub fn from_bytes(bytes: Vec<u8>) -> Self {
         MasterPad { buf: PadBuf::Heap(bytes), pos: 0 }
     }
 
+    // Map a pad file read/write; position starts after the burned (all-zero) prefix left by
+    // earlier sessions. Unused zero bytes at the boundary are skipped, never reused.
+    // Pages are locked (`PadBuf::Locked`) when the memlock rlimit allows, otherwise the
+    // mapping stays swappable (`PadBuf::Mmap`). On Linux the mapping is marked MADV_DONTDUMP.
+    #[cfg(feature = "std")]
+    pub fn open_mapped<P: AsRef<std::path::Path>>(path: P) -> Result<Self, PadError> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| PadError::Io(e.kind()))?;
+        // SAFETY: the file is mapped shared read/write; concurrent modification by another
+        // process would only corrupt keystream, never memory safety of the slice accessors.
+        let map = unsafe { MmapOptions::new().map_mut(&file) }.map_err(|e| PadError::Io(e.kind()))?;
+
+        #[cfg(target_os = "linux")]
+        let _ = map.advise(memmap2::Advice::DontDump);
+
+        #[cfg(unix)]
+        let buf = if map.lock().is_ok() { PadBuf::Locked(map) } else { PadBuf::Mmap(map) };
+        #[cfg(not(unix))]
+        let buf = PadBuf::Mmap(map);
+
+        let pos = buf.as_slice().iter().position(|&b| b != 0).unwrap_or(buf.as_slice().len());
+        Ok(MasterPad { buf, pos })
+    }
+
+    // Whether the backing pages are locked in RAM.
+    pub fn is_locked(&self) -> bool { self.buf.is_locked() }
+
     // Remaining bytes available for allocation.
     pub fn available(&self) -> usize {
-        match &self.buf { PadBuf::Heap(v) => v.len().saturating_sub(self.pos) }
+        self.buf.as_slice().len().saturating_sub(self.pos)
     }
 
+    // Offset of the next block to be allocated.
+    pub fn position(&self) -> usize { self.pos }
+
     // Borrow the next block `payload_len + MAC_LEN` without advancing.
     pub fn peek_block(&self, payload_len: usize) -> Result<&[u8], PadError> {
-        let need = payload_len + MAC_LEN;
-        if self.available() < need { return Err(PadError::Insufficient); }
-        match &self.buf {
-            PadBuf::Heap(v) => Ok(&v[self.pos..self.pos + need]),
-        }
+        self.peek_at(self.pos, payload_len)
     }
 
-    // Consume and return the next block by borrowing; advances position.
-    pub fn consume_block(&mut self, payload_len: usize) -> Result<&[u8], PadError> {
-        let need = payload_len + MAC_LEN;
-        if self.available() < need { return Err(PadError::Insufficient); }
-        let start = self.pos;
-        let end = start + need;
-        self.pos = end;
-        match &self.buf {
-            PadBuf::Heap(v) => Ok(&v[start..end]),
-        }
+    // Borrow the block `payload_len + MAC_LEN` starting at `offset` without advancing.
+    // Callers reading below `position()` must track for themselves which regions are still unused.
+    pub fn peek_at(&self, offset: usize, payload_len: usize) -> Result<&[u8], PadError> {
+        let end = offset.checked_add(payload_len + MAC_LEN).ok_or(PadError::Insufficient)?;
+        let v = self.buf.as_slice();
+        if end > v.len() { return Err(PadError::Insufficient); }
+        Ok(&v[offset..end])
     }
 
-    // Consume and return the next block by value; advances position.
-    pub fn take_block(&mut self, payload_len: usize) -> Result<Vec<u8>, PadError> {
+    // Move the allocation position forward to `offset`; the skipped bytes are left untouched.
+    pub fn advance_to(&mut self, offset: usize) -> Result<(), PadError> {
+        if offset < self.pos || offset > self.buf.as_slice().len() { return Err(PadError::Insufficient); }
+        self.pos = offset;
+        Ok(())
+    }
+
+    // Irreversibly overwrite `start..end` of the backing pad.
+    pub fn burn_range(&mut self, start: usize, end: usize) -> Result<(), PadError> {
+        let v = self.buf.as_mut_slice();
+        if start > end || end > v.len() { return Err(PadError::Insufficient); }
+        burn_slice(&mut v[start..end]);
+        Ok(())
+    }
+
+    // Consume the next block by borrowing; advances position.
+    // The block is burned in the pad when the returned guard is dropped.
+    pub fn consume_block(&mut self, payload_len: usize) -> Result<BurnGuard<'_>, PadError> {
         let need = payload_len + MAC_LEN;
         if self.available() < need { return Err(PadError::Insufficient); }
         let start = self.pos;
         let end = start + need;
         self.pos = end;
-        match &self.buf {
-            PadBuf::Heap(v) => Ok(v[start..end].to_vec()),
-        }
+        Ok(BurnGuard::new(&mut self.buf.as_mut_slice()[start..end]))
+    }
+
+    // Consume the next block by value; advances position.
+    // The block is burned in the pad immediately; the returned copy zeroizes on drop.
+    pub fn take_block(&mut self, payload_len: usize) -> Result<Zeroizing<Vec<u8>>, PadError> {
+        let block = self.consume_block(payload_len)?;
+        Ok(Zeroizing::new(block.to_vec()))
     }
 }
 
@@ -99,4 +208,55 @@ mod tests {
         assert_eq!(pad.available(), 0);
         assert!(pad.consume_block(payload_len).is_err());
     }
+
+    #[test]
+    fn test_masterpad_blocks_burned_after_use() {
+        let payload_len = 4;
+        let mut pad = MasterPad::from_bytes(vec![0x77u8; 2 * (payload_len + MAC_LEN)]);
+        {
+            let block = pad.consume_block(payload_len).unwrap();
+            assert!(block.iter().all(|&b| b == 0x77));
+        }
+        assert!(pad.peek_at(0, payload_len).unwrap().iter().all(|&b| b == 0));
+
+        let copy = pad.take_block(payload_len).unwrap();
+        assert!(copy.iter().all(|&b| b == 0x77));
+        assert!(pad.peek_at(payload_len + MAC_LEN, payload_len).unwrap().iter().all(|&b| b == 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_masterpad_mapped_burned_on_drop() {
+        let name = format!("togm_test_mapped_pad_{}_{:?}.bin", std::process::id(), std::thread::current().id());
+        let path = std::env::temp_dir().join(name);
+        let block = 8 + MAC_LEN;
+        let mut backing = vec![0x5Au8; 3 * block];
+        backing[..8].copy_from_slice(b"KEYBYTES");
+        std::fs::write(&path, &backing).unwrap();
+
+        {
+            let mut pad = MasterPad::open_mapped(&path).unwrap();
+            assert_eq!(pad.available(), backing.len());
+            let b1 = pad.take_block(8).unwrap();
+            assert_eq!(&b1[..8], b"KEYBYTES");
+            // Skipped over without a block handed out: still burned on drop.
+            pad.advance_to(2 * block).unwrap();
+        }
+
+        // Only the consumed range is burned; the next session resumes after it.
+        let after = std::fs::read(&path).unwrap();
+        assert_eq!(after.len(), backing.len());
+        assert!(after[..2 * block].iter().all(|&b| b == 0));
+        assert_eq!(&after[2 * block..], &backing[2 * block..]);
+        {
+            let mut pad = MasterPad::open_mapped(&path).unwrap();
+            assert_eq!(pad.position(), 2 * block);
+            assert!(pad.consume_block(8).is_ok());
+            assert!(pad.consume_block(8).is_err());
+        }
+        assert!(std::fs::read(&path).unwrap().iter().all(|&b| b == 0));
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(MasterPad::open_mapped(&path), Err(PadError::Io(_))));
+    }
 }
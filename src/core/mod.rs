@@ -1,5 +1,8 @@
 pub mod gf256;
+pub mod gf65536;
 pub mod otp_engine;
+pub mod padding;
+pub mod poly_mac;
 pub mod masterpad;
 pub mod ratchet;
 pub mod sip64;
@@ -6,7 +6,8 @@ use alloc::vec::Vec;
 use core::convert::TryInto;
 
 use crate::core::xor::xor;
-use crate::core::sip64::{sip64_tag, verify as verify_sip64, MAC_LEN};
+use crate::core::sip64::{sip64_tag, verify as verify_sip64, Sip64Hasher, MAC_LEN};
+use crate::core::poly_mac::{poly_tag, poly_verify, MacMode};
 
 
 #[derive(Debug, PartialEq, Eq)]
@@ -14,6 +15,8 @@ pub enum OtpError {
     KeystreamTooShort,
     BlockTooSmall,
     TagMismatch,
+    InvalidMacMode,
+    LengthMismatch,
 }
 
 /// Constant-time OTP encryption using the shared `xor()` path.
@@ -95,6 +98,54 @@ pub fn decrypt_and_verify(ciphertext: &[u8], metadata: &[u8], block: &[u8], expe
 }
 
 
+/// Mode-aware Encrypt-then-MAC. The `block` layout is `keystream || mac_material`, with
+/// `mode.pad_cost()` bytes of MAC material: the SIP-64 key, `hash_key || mask` for
+/// `OneTime`, or just the mask for `WegmanCarter` (whose long-lived key is `hash_key`).
+/// `hash_key` is ignored by the other modes.
+pub fn encrypt_and_tag_mode(mode: MacMode, plaintext: &[u8], metadata: &[u8], block: &[u8], hash_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), OtpError> {
+    if let MacMode::Sip64 = mode {
+        return encrypt_and_tag(plaintext, metadata, block).map(|(ct, tag)| (ct, tag.to_vec()));
+    }
+    let (keystream, key, mask) = split_block_mode(mode, block, plaintext.len(), hash_key)?;
+    let ct = encrypt(plaintext, keystream)?;
+    let tag = poly_tag(&ct, metadata, key, mask).map_err(|_| OtpError::InvalidMacMode)?;
+    Ok((ct, tag))
+}
+
+/// Mode-aware counterpart of `decrypt_and_verify`; the tag is checked before decrypting.
+pub fn decrypt_and_verify_mode(mode: MacMode, ciphertext: &[u8], metadata: &[u8], block: &[u8], hash_key: &[u8], expected_tag: &[u8]) -> Result<Vec<u8>, OtpError> {
+    if let MacMode::Sip64 = mode {
+        let tag: &[u8; MAC_LEN] = expected_tag.try_into().map_err(|_| OtpError::TagMismatch)?;
+        return decrypt_and_verify(ciphertext, metadata, block, tag);
+    }
+    let (keystream, key, mask) = split_block_mode(mode, block, ciphertext.len(), hash_key)?;
+    if !poly_verify(ciphertext, metadata, key, mask, expected_tag) {
+        return Err(OtpError::TagMismatch);
+    }
+    decrypt(ciphertext, keystream)
+}
+
+// (keystream, hash_key, mask) for the polynomial modes.
+type PolyParts<'a> = (&'a [u8], &'a [u8], &'a [u8]);
+
+fn split_block_mode<'a>(mode: MacMode, block: &'a [u8], payload_len: usize, hash_key: &'a [u8]) -> Result<PolyParts<'a>, OtpError> {
+    mode.validate().map_err(|_| OtpError::InvalidMacMode)?;
+    let tag_len = mode.tag_len();
+    if block.len() < payload_len + mode.pad_cost() {
+        return Err(OtpError::BlockTooSmall);
+    }
+    let (keystream, material) = block.split_at(payload_len);
+    match mode {
+        MacMode::WegmanCarter { .. } => {
+            if hash_key.len() != mode.hash_key_len() {
+                return Err(OtpError::InvalidMacMode);
+            }
+            Ok((keystream, hash_key, &material[..tag_len]))
+        }
+        _ => Ok((keystream, &material[..tag_len], &material[tag_len..2 * tag_len])),
+    }
+}
+
 /// Split a `block` into `(&keystream, &mac_key)` with strict bounds checking.
 #[inline(always)]
 pub fn split_block(block: &[u8], payload_len: usize) -> Result<(&[u8], &[u8; MAC_LEN]), OtpError> {
@@ -105,6 +156,80 @@ pub fn split_block(block: &[u8], payload_len: usize) -> Result<(&[u8], &[u8; MAC
     let mac_key: &[u8; MAC_LEN] = mac_key_slice[..MAC_LEN].try_into().map_err(|_| OtpError::BlockTooSmall)?;
     Ok((keystream, mac_key))
 }
+/// Streaming Encrypt-then-MAC over a `keystream || mac_key(64B)` block.
+///
+/// Produces the same ciphertext and tag as `encrypt_and_tag` without holding the whole
+/// message. Senders call `seal_chunk` for each piece and `finalize` with the metadata;
+/// receivers `absorb` every ciphertext piece, `verify`, and only then decrypt
+/// (e.g. chunk-wise via `decrypt_into` over the keystream from `split_block`).
+pub struct OtpStream<'a> {
+    keystream: &'a [u8],
+    pos: usize,
+    mac: Sip64Hasher,
+}
+
+impl<'a> OtpStream<'a> {
+    /// Bind a block for a payload of `payload_len` bytes.
+    pub fn new(block: &'a [u8], payload_len: usize) -> Result<Self, OtpError> {
+        let (keystream, mac_key) = split_block(block, payload_len)?;
+        Ok(OtpStream { keystream, pos: 0, mac: Sip64Hasher::new(mac_key) })
+    }
+
+    /// Encrypt the next plaintext piece into `out` and absorb the ciphertext.
+    ///
+    /// `out` must be exactly `plaintext.len()` bytes, else `LengthMismatch`.
+    pub fn seal_chunk(&mut self, plaintext: &[u8], out: &mut [u8]) -> Result<(), OtpError> {
+        if out.len() != plaintext.len() {
+            return Err(OtpError::LengthMismatch);
+        }
+        let ks = self.next_keystream(plaintext.len())?;
+        encrypt_into(plaintext, ks, out)?;
+        self.mac.update(out);
+        Ok(())
+    }
+
+    /// Absorb the next ciphertext piece without decrypting it.
+    pub fn absorb(&mut self, ciphertext: &[u8]) -> Result<(), OtpError> {
+        self.next_keystream(ciphertext.len())?;
+        self.mac.update(ciphertext);
+        Ok(())
+    }
+
+    /// Finish with the metadata and return the tag.
+    ///
+    /// Fails with `LengthMismatch` unless exactly `payload_len` bytes were sealed.
+    pub fn finalize(mut self, metadata: &[u8]) -> Result<[u8; MAC_LEN], OtpError> {
+        self.check_complete()?;
+        self.mac.update(metadata);
+        Ok(self.mac.finalize())
+    }
+
+    /// Finish with the metadata and check `expected_tag` in constant time.
+    ///
+    /// Fails with `LengthMismatch` unless exactly `payload_len` bytes were absorbed.
+    pub fn verify(mut self, metadata: &[u8], expected_tag: &[u8; MAC_LEN]) -> Result<(), OtpError> {
+        self.check_complete()?;
+        self.mac.update(metadata);
+        if self.mac.verify(expected_tag) { Ok(()) } else { Err(OtpError::TagMismatch) }
+    }
+
+    fn check_complete(&self) -> Result<(), OtpError> {
+        if self.pos != self.keystream.len() {
+            return Err(OtpError::LengthMismatch);
+        }
+        Ok(())
+    }
+
+    fn next_keystream(&mut self, len: usize) -> Result<&'a [u8], OtpError> {
+        let end = self.pos.checked_add(len).ok_or(OtpError::KeystreamTooShort)?;
+        if end > self.keystream.len() {
+            return Err(OtpError::KeystreamTooShort);
+        }
+        let ks = &self.keystream[self.pos..end];
+        self.pos = end;
+        Ok(ks)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -146,4 +271,54 @@ mod tests {
         let pt = decrypt_and_verify(&ct, b"m", &block, &tag).unwrap();
         assert_eq!(pt, p);
     }
+
+    #[test]
+    fn test_mac_modes_roundtrip() {
+        let p = b"ok";
+        let block: Vec<u8> = (0..2 + MAC_LEN).map(|i| (i as u8).wrapping_mul(13).wrapping_add(7)).collect();
+        let hk = [0x42u8; 16];
+        for mode in [MacMode::Sip64, MacMode::OneTime { tag_len: 16 }, MacMode::OneTime { tag_len: 32 }, MacMode::WegmanCarter { tag_len: 16 }] {
+            let used = &block[..p.len() + mode.pad_cost()];
+            let (ct, tag) = encrypt_and_tag_mode(mode, p, b"md", used, &hk).unwrap();
+            assert_eq!(tag.len(), mode.tag_len());
+            assert_eq!(decrypt_and_verify_mode(mode, &ct, b"md", used, &hk, &tag).unwrap(), p);
+            let mut bad = tag.clone();
+            bad[0] ^= 1;
+            assert_eq!(decrypt_and_verify_mode(mode, &ct, b"md", used, &hk, &bad), Err(OtpError::TagMismatch));
+        }
+        let wc = MacMode::WegmanCarter { tag_len: 16 };
+        assert_eq!(encrypt_and_tag_mode(wc, p, b"", &block, &hk[..8]), Err(OtpError::InvalidMacMode));
+        assert_eq!(encrypt_and_tag_mode(MacMode::OneTime { tag_len: 32 }, p, b"", &block[..40], &[]), Err(OtpError::BlockTooSmall));
+    }
+
+    #[test]
+    fn test_stream_matches_block_api() {
+        let p: Vec<u8> = (0..300u32).map(|i| (i * 7) as u8).collect();
+        let mut block: Vec<u8> = (0..300u32).map(|i| (i * 11 + 3) as u8).collect();
+        block.extend((0..MAC_LEN).map(|i| (i as u8).wrapping_mul(5)));
+        let (ct, tag) = encrypt_and_tag(&p, b"meta", &block).unwrap();
+
+        let mut sealer = OtpStream::new(&block, p.len()).unwrap();
+        let mut ct2 = vec![0u8; p.len()];
+        for (src, dst) in p.chunks(97).zip(ct2.chunks_mut(97)) {
+            sealer.seal_chunk(src, dst).unwrap();
+        }
+        assert_eq!(ct2, ct);
+        assert_eq!(sealer.seal_chunk(&[0], &mut []), Err(OtpError::LengthMismatch));
+        assert_eq!(sealer.finalize(b"meta"), Ok(tag));
+
+        let mut opener = OtpStream::new(&block, ct.len()).unwrap();
+        for piece in ct.chunks(64) {
+            opener.absorb(piece).unwrap();
+        }
+        assert_eq!(opener.absorb(&[0]), Err(OtpError::KeystreamTooShort));
+        assert!(opener.verify(b"meta", &tag).is_ok());
+
+        let mut short = OtpStream::new(&block, ct.len()).unwrap();
+        short.absorb(&ct[..ct.len() - 1]).unwrap();
+        assert_eq!(short.verify(b"meta", &tag), Err(OtpError::LengthMismatch));
+        let mut partial = OtpStream::new(&block, p.len()).unwrap();
+        partial.seal_chunk(&p[..10], &mut ct2[..10]).unwrap();
+        assert_eq!(partial.finalize(b"meta"), Err(OtpError::LengthMismatch));
+    }
 }
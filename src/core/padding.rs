@@ -0,0 +1,183 @@
+#![forbid(unsafe_code)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+
+// Length-hiding padding for OTP payloads, applied before `otp_engine::encrypt_and_tag`
+// (see `protocol::envelope::OtpEnvelope::seal_padded`; the pad block for the padded length
+// comes from `pad::lifecycle::MasterPad::get_padded_block`).
+// Padding is ISO/IEC 7816-4 style (`0x80` then zeros), so unpadding is unambiguous for any
+// plaintext. Unpadding scans the whole buffer with masks; timing depends only on the padded length.
+// Policies trade pad budget for length privacy:
+// - `None`: no padding, exact length visible.
+// - `Fixed`: multiples of `block` bytes (whitepaper default 4096).
+// - `PowerOfTwo`: next power of two, at least `min`; leaks O(log log n) bits.
+// - `Padme`: Padmé buckets; at most ~12% overhead, also O(log log n) bits leaked.
+//   Buckets only start to merge lengths above a few bytes, so very short messages stay
+//   distinguishable; prefer `Fixed` for short chat traffic.
+
+// Whitepaper block size for fixed padding.
+pub const DEFAULT_PAD_BLOCK: usize = 4096;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PaddingError {
+    // Policy parameter is unusable (zero block / minimum).
+    InvalidPolicy,
+    // Padded length does not fit in usize.
+    TooLong,
+    // No `0x80` marker found before the trailing zeros.
+    InvalidPadding,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingPolicy {
+    None,
+    Fixed { block: usize },
+    PowerOfTwo { min: usize },
+    Padme,
+}
+
+impl Default for PaddingPolicy {
+    fn default() -> Self {
+        PaddingPolicy::Fixed { block: DEFAULT_PAD_BLOCK }
+    }
+}
+
+impl PaddingPolicy {
+    // Length on the wire for a `len`-byte plaintext (including the padding marker).
+    pub fn padded_len(&self, len: usize) -> Result<usize, PaddingError> {
+        if let PaddingPolicy::None = self {
+            return Ok(len);
+        }
+        let l = len.checked_add(1).ok_or(PaddingError::TooLong)?;
+        match *self {
+            PaddingPolicy::None => Ok(len),
+            PaddingPolicy::Fixed { block } => {
+                if block == 0 { return Err(PaddingError::InvalidPolicy); }
+                l.checked_next_multiple_of(block).ok_or(PaddingError::TooLong)
+            }
+            PaddingPolicy::PowerOfTwo { min } => {
+                if min == 0 { return Err(PaddingError::InvalidPolicy); }
+                let p = l.checked_next_power_of_two().ok_or(PaddingError::TooLong)?;
+                Ok(core::cmp::max(p, min))
+            }
+            PaddingPolicy::Padme => Ok(padme(l as u64).try_into().map_err(|_| PaddingError::TooLong)?),
+        }
+    }
+
+    // Pad `plaintext` to `padded_len(plaintext.len())` bytes.
+    pub fn pad(&self, plaintext: &[u8]) -> Result<Zeroizing<Vec<u8>>, PaddingError> {
+        let total = self.padded_len(plaintext.len())?;
+        let mut out = Zeroizing::new(Vec::with_capacity(total));
+        out.extend_from_slice(plaintext);
+        if !matches!(self, PaddingPolicy::None) {
+            out.push(0x80);
+            out.resize(total, 0);
+        }
+        Ok(out)
+    }
+
+    // Strip padding; returns the original plaintext as a sub-slice.
+    // The input must be exactly `padded_len` of the recovered plaintext length, so buffers
+    // this policy could not have produced (e.g. not a multiple of the block) are rejected.
+    pub fn unpad<'a>(&self, padded: &'a [u8]) -> Result<&'a [u8], PaddingError> {
+        if let PaddingPolicy::None = self {
+            return Ok(padded);
+        }
+        // Constant-time scan for the last non-zero byte: `marker` ends as its index,
+        // `seen` records whether any was found.
+        let mut marker: usize = 0;
+        let mut marker_byte: u8 = 0;
+        let mut seen: u8 = 0;
+        for (i, &b) in padded.iter().enumerate() {
+            let nz = ((b as u16 | (0u16.wrapping_sub(b as u16))) >> 15) as u8 & 1;
+            let mask = 0u8.wrapping_sub(nz);
+            let wmask = 0usize.wrapping_sub(nz as usize);
+            marker = (i & wmask) | (marker & !wmask);
+            marker_byte = (b & mask) | (marker_byte & !mask);
+            seen |= nz;
+        }
+        if seen == 0 || marker_byte != 0x80 {
+            return Err(PaddingError::InvalidPadding);
+        }
+        if self.padded_len(marker)? != padded.len() {
+            return Err(PaddingError::InvalidPadding);
+        }
+        Ok(&padded[..marker])
+    }
+}
+
+// Padmé: round `l` up so that only the top ~log2(log2(l)) + 1 bits may be non-zero.
+fn padme(l: u64) -> u64 {
+    if l < 2 {
+        return l;
+    }
+    let e = 63 - l.leading_zeros() as u64; // floor(log2 l)
+    let s = 64 - e.leading_zeros() as u64; // floor(log2 e) + 1
+    let last_bits = e - s;
+    let mask = (1u64 << last_bits) - 1;
+    (l + mask) & !mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padding_lengths() {
+        let fixed = PaddingPolicy::default();
+        assert_eq!(fixed.padded_len(0), Ok(4096));
+        assert_eq!(fixed.padded_len(4095), Ok(4096));
+        assert_eq!(fixed.padded_len(4096), Ok(8192));
+        let p2 = PaddingPolicy::PowerOfTwo { min: 64 };
+        assert_eq!(p2.padded_len(3), Ok(64));
+        assert_eq!(p2.padded_len(100), Ok(128));
+        assert_eq!(p2.padded_len(127), Ok(128));
+        assert_eq!(p2.padded_len(128), Ok(256));
+        // "yes" and "no" are indistinguishable under the bucket policies.
+        for policy in [fixed, p2] {
+            assert_eq!(policy.padded_len(2), policy.padded_len(3));
+        }
+        assert_eq!(PaddingPolicy::Padme.padded_len(1000), PaddingPolicy::Padme.padded_len(1010));
+        assert_eq!(PaddingPolicy::None.padded_len(3), Ok(3));
+        assert_eq!(PaddingPolicy::Fixed { block: 0 }.padded_len(3), Err(PaddingError::InvalidPolicy));
+    }
+
+    #[test]
+    fn test_padme_overhead_bound() {
+        for len in [1usize, 9, 100, 1000, 12_345, 1 << 20] {
+            let padded = PaddingPolicy::Padme.padded_len(len).unwrap();
+            assert!(padded > len);
+            assert!((padded - len - 1) * 100 <= (len + 1) * 12, "len {}", len);
+        }
+        assert_eq!(padme(1000), 1024);
+    }
+
+    #[test]
+    fn test_pad_unpad_roundtrip() {
+        let msgs: [&[u8]; 4] = [b"", b"yes", &[0x80, 0, 0], &[0u8; 70]];
+        for policy in [PaddingPolicy::default(), PaddingPolicy::PowerOfTwo { min: 16 }, PaddingPolicy::Padme, PaddingPolicy::None] {
+            for m in msgs {
+                let padded = policy.pad(m).unwrap();
+                assert_eq!(padded.len(), policy.padded_len(m.len()).unwrap());
+                assert_eq!(policy.unpad(&padded).unwrap(), m);
+            }
+        }
+        assert_eq!(PaddingPolicy::Padme.unpad(&[1, 2, 0, 0]), Err(PaddingError::InvalidPadding));
+        assert_eq!(PaddingPolicy::Padme.unpad(&[0, 0]), Err(PaddingError::InvalidPadding));
+    }
+
+    #[test]
+    fn test_unpad_rejects_foreign_lengths() {
+        let fixed = PaddingPolicy::Fixed { block: 16 };
+        let mut padded = fixed.pad(b"abc").unwrap().to_vec();
+        // One block too many, or a length that is not a multiple of the block.
+        padded.extend_from_slice(&[0u8; 16]);
+        assert_eq!(fixed.unpad(&padded), Err(PaddingError::InvalidPadding));
+        assert_eq!(fixed.unpad(&padded[..17]), Err(PaddingError::InvalidPadding));
+        assert_eq!(fixed.unpad(&padded[..16]).unwrap(), b"abc");
+        // A well-formed marker at a length outside the power-of-two buckets.
+        assert_eq!(PaddingPolicy::PowerOfTwo { min: 4 }.unpad(&[7, 0x80, 0, 0, 0]), Err(PaddingError::InvalidPadding));
+    }
+}
@@ -0,0 +1,279 @@
+#![forbid(unsafe_code)]
+// Pad-economical information-theoretic MAC modes.
+// - `Sip64`: legacy SIP-64 tag, 64 fresh key bytes and a 64-byte tag per message.
+// - `OneTime`: polynomial MAC over GF(2^64) with a fresh hash key and mask per message;
+//   a 16 or 32-byte tag costs twice its length in pad.
+// - `WegmanCarter`: the same polynomial hash under a long-lived group hash key, with each
+//   tag encrypted by a fresh one-time mask; only the tag length is drawn per message.
+//
+// Every 8-byte tag lane is `H_k(m) + s` where `H_k` is Horner evaluation at `k` over the
+// zero-padded LE words of `ciphertext`, then `metadata`, then both lengths. Two distinct
+// messages of at most `n` words give a non-zero difference polynomial of degree <= n, so a
+// forgery succeeds with probability at most `(n / 2^64)^lanes`. For Wegman–Carter the bound
+// holds per verification attempt as long as no mask is ever reused under the same hash key.
+//
+// The legacy SIP-64 tag has no such bound: the first ciphertext byte is the constant term
+// of every lane, so adding `d` to it and to every tag byte yields a valid forgery.
+// `forgery_bound_bits` reports 0 for it.
+//
+// Multiplication uses `universal_hash::clmul64` (PCLMULQDQ / PMULL, constant-time software
+// fallback) with a branch-free reduction; timing depends only on the public lengths.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use alloc::vec;
+use core::convert::TryInto;
+use crate::core::sip64::MAC_LEN;
+use crate::core::universal_hash::clmul64;
+use zeroize::Zeroize;
+
+/// Bytes per GF(2^64) tag lane.
+pub const LANE_LEN: usize = 8;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MacError {
+    // Tag length other than 16 or 32 bytes.
+    InvalidMode,
+    // Hash key or mask shorter than the mode requires.
+    KeyTooShort,
+}
+
+/// Authentication mode of a group's OTP messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MacMode {
+    /// SIP-64 with a fresh 64-byte key per message.
+    #[default]
+    Sip64,
+    /// Polynomial MAC with a fresh `tag_len`-byte hash key and mask per message.
+    OneTime { tag_len: usize },
+    /// Polynomial MAC under a long-lived `tag_len`-byte hash key, one-time-pad encrypted tags.
+    WegmanCarter { tag_len: usize },
+}
+
+impl MacMode {
+    /// Checks the tag length (16 or 32 bytes for the polynomial modes).
+    pub fn validate(&self) -> Result<(), MacError> {
+        match *self {
+            MacMode::Sip64 => Ok(()),
+            MacMode::OneTime { tag_len } | MacMode::WegmanCarter { tag_len } => {
+                if tag_len == 16 || tag_len == 32 { Ok(()) } else { Err(MacError::InvalidMode) }
+            }
+        }
+    }
+
+    /// Tag length on the wire.
+    pub fn tag_len(&self) -> usize {
+        match *self {
+            MacMode::Sip64 => MAC_LEN,
+            MacMode::OneTime { tag_len } | MacMode::WegmanCarter { tag_len } => tag_len,
+        }
+    }
+
+    /// Pad bytes drawn per message for authentication (after the keystream).
+    pub fn pad_cost(&self) -> usize {
+        match *self {
+            MacMode::Sip64 => MAC_LEN,
+            MacMode::OneTime { tag_len } => 2 * tag_len,
+            MacMode::WegmanCarter { tag_len } => tag_len,
+        }
+    }
+
+    /// Length of the long-lived hash key (Wegman–Carter only, otherwise 0).
+    pub fn hash_key_len(&self) -> usize {
+        match *self {
+            MacMode::WegmanCarter { tag_len } => tag_len,
+            _ => 0,
+        }
+    }
+
+    /// `b` such that a single forgery attempt on a `ct_len`/`md_len` message succeeds with
+    /// probability at most `2^-b`. Always 0 for `Sip64` (see module notes).
+    pub fn forgery_bound_bits(&self, ct_len: usize, md_len: usize) -> u32 {
+        match *self {
+            MacMode::Sip64 => 0,
+            MacMode::OneTime { tag_len } | MacMode::WegmanCarter { tag_len } => {
+                let n = message_words(ct_len, md_len);
+                let lanes = (tag_len / LANE_LEN) as u32;
+                // ceil(log2 n) bits lost per lane.
+                let lost = 64 - (n - 1).leading_zeros();
+                lanes * (64 - lost)
+            }
+        }
+    }
+
+    /// Wire identifier (envelope `mac_mode` byte).
+    pub fn to_wire(&self) -> u8 {
+        match *self {
+            MacMode::Sip64 => 0,
+            MacMode::OneTime { tag_len } => 0x10 | (tag_len / LANE_LEN) as u8,
+            MacMode::WegmanCarter { tag_len } => 0x20 | (tag_len / LANE_LEN) as u8,
+        }
+    }
+
+    /// Parses a wire identifier; `None` for unknown or invalid modes.
+    pub fn from_wire(id: u8) -> Option<Self> {
+        let tag_len = (id & 0x0F) as usize * LANE_LEN;
+        let mode = match id >> 4 {
+            0 if id == 0 => MacMode::Sip64,
+            1 => MacMode::OneTime { tag_len },
+            2 => MacMode::WegmanCarter { tag_len },
+            _ => return None,
+        };
+        mode.validate().ok().map(|_| mode)
+    }
+}
+
+/// Polynomial tag over `ciphertext || metadata` with `mask.len() / 8` lanes.
+///
+/// # Errors
+/// * `MacError::InvalidMode` if `mask` is not 16 or 32 bytes.
+/// * `MacError::KeyTooShort` if `hash_key` is shorter than `mask`.
+pub fn poly_tag(ciphertext: &[u8], metadata: &[u8], hash_key: &[u8], mask: &[u8]) -> Result<Vec<u8>, MacError> {
+    MacMode::OneTime { tag_len: mask.len() }.validate()?;
+    if hash_key.len() < mask.len() {
+        return Err(MacError::KeyTooShort);
+    }
+    let mut tag = vec![0u8; mask.len()];
+    for ((out, k), s) in tag.chunks_exact_mut(LANE_LEN).zip(hash_key.chunks_exact(LANE_LEN)).zip(mask.chunks_exact(LANE_LEN)) {
+        let mut k = u64::from_le_bytes(k.try_into().map_err(|_| MacError::KeyTooShort)?);
+        let s = u64::from_le_bytes(s.try_into().map_err(|_| MacError::KeyTooShort)?);
+        let mut acc = horner(0, ciphertext, k);
+        acc = horner(acc, metadata, k);
+        acc = gf64_mul(acc ^ ciphertext.len() as u64, k);
+        acc = gf64_mul(acc ^ metadata.len() as u64, k);
+        out.copy_from_slice(&(acc ^ s).to_le_bytes());
+        k.zeroize();
+        acc.zeroize();
+    }
+    Ok(tag)
+}
+
+/// Recomputes the tag and compares in constant time.
+pub fn poly_verify(ciphertext: &[u8], metadata: &[u8], hash_key: &[u8], mask: &[u8], tag: &[u8]) -> bool {
+    match poly_tag(ciphertext, metadata, hash_key, mask) {
+        Ok(mut expected) => {
+            let ok = expected.len() == tag.len()
+                && expected.iter().zip(tag.iter()).fold(0u8, |d, (a, b)| d | (a ^ b)) == 0;
+            expected.zeroize();
+            ok
+        }
+        Err(_) => false,
+    }
+}
+
+// Words hashed for a message: zero-padded data words plus the two length words.
+fn message_words(ct_len: usize, md_len: usize) -> u64 {
+    (ct_len as u64).div_ceil(8) + (md_len as u64).div_ceil(8) + 2
+}
+
+// acc = (acc + w) * k over the zero-padded LE words of `data`.
+#[inline(always)]
+fn horner(mut acc: u64, data: &[u8], k: u64) -> u64 {
+    let mut chunks = data.chunks_exact(LANE_LEN);
+    for w in &mut chunks {
+        let w = u64::from_le_bytes(w.try_into().unwrap_or([0u8; LANE_LEN]));
+        acc = gf64_mul(acc ^ w, k);
+    }
+    let rem = chunks.remainder();
+    if !rem.is_empty() {
+        let mut last = [0u8; LANE_LEN];
+        last[..rem.len()].copy_from_slice(rem);
+        acc = gf64_mul(acc ^ u64::from_le_bytes(last), k);
+        last.zeroize();
+    }
+    acc
+}
+
+// GF(2^64) multiply modulo x^64 + x^4 + x^3 + x + 1.
+#[inline(always)]
+fn gf64_mul(a: u64, b: u64) -> u64 {
+    // h * x^64 = h * (x^4 + x^3 + x + 1); the second fold has at most 4 input bits.
+    fn fold(h: u64) -> (u64, u64) {
+        let w = h as u128;
+        let t = w ^ (w << 1) ^ (w << 3) ^ (w << 4);
+        (t as u64, (t >> 64) as u64)
+    }
+    let p = clmul64(a, b);
+    let (t_lo, t_hi) = fold((p >> 64) as u64);
+    let (u_lo, _) = fold(t_hi);
+    (p as u64) ^ t_lo ^ u_lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::sip64::sip64_tag;
+
+    // Bitwise shift-and-add reference.
+    fn gf64_mul_ref(mut a: u64, mut b: u64) -> u64 {
+        let mut r = 0u64;
+        while b != 0 {
+            if b & 1 == 1 { r ^= a; }
+            let carry = a >> 63;
+            a <<= 1;
+            if carry == 1 { a ^= 0x1B; }
+            b >>= 1;
+        }
+        r
+    }
+
+    #[test]
+    fn test_gf64_mul_matches_reference() {
+        assert_eq!(gf64_mul(1 << 63, 2), 0x1B);
+        let mut x = 0x0123_4567_89AB_CDEFu64;
+        for i in 0..200u64 {
+            let y = x.rotate_left(17) ^ i.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            assert_eq!(gf64_mul(x, y), gf64_mul_ref(x, y));
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        }
+    }
+
+    #[test]
+    fn test_poly_tag_detects_tampering() {
+        let key: Vec<u8> = (0..32u8).map(|i| i.wrapping_mul(37).wrapping_add(5)).collect();
+        let mask: Vec<u8> = (0..32u8).map(|i| i.wrapping_mul(91)).collect();
+        let ct = b"short chat message";
+        let tag = poly_tag(ct, b"hdr", &key, &mask).unwrap();
+        assert_eq!(tag.len(), 32);
+        assert!(poly_verify(ct, b"hdr", &key, &mask, &tag));
+        assert!(!poly_verify(b"short chat messagf", b"hdr", &key, &mask, &tag));
+        assert!(!poly_verify(ct, b"hdR", &key, &mask, &tag));
+        // Moving bytes between ciphertext and metadata changes the length words.
+        assert!(!poly_verify(b"short chat messag", b"ehdr", &key, &mask, &tag));
+        assert!(!poly_verify(ct, b"hdr", &key, &mask, &tag[..16]));
+        assert_eq!(poly_tag(ct, b"", &key, &mask[..24]), Err(MacError::InvalidMode));
+        assert_eq!(poly_tag(ct, b"", &key[..16], &mask), Err(MacError::KeyTooShort));
+    }
+
+    #[test]
+    fn test_mode_costs_and_bounds() {
+        let short = MacMode::OneTime { tag_len: 16 };
+        let wc = MacMode::WegmanCarter { tag_len: 32 };
+        assert_eq!((short.tag_len(), short.pad_cost(), short.hash_key_len()), (16, 32, 0));
+        assert_eq!((wc.tag_len(), wc.pad_cost(), wc.hash_key_len()), (32, 32, 32));
+        assert_eq!(MacMode::default().pad_cost(), MAC_LEN);
+
+        // 100-byte message, 35-byte header: 13 + 5 + 2 = 20 words -> 5 bits lost per lane.
+        assert_eq!(short.forgery_bound_bits(100, 35), 2 * 59);
+        assert_eq!(wc.forgery_bound_bits(100, 35), 4 * 59);
+        assert_eq!(MacMode::Sip64.forgery_bound_bits(100, 35), 0);
+
+        for mode in [MacMode::Sip64, short, wc, MacMode::OneTime { tag_len: 32 }] {
+            assert_eq!(MacMode::from_wire(mode.to_wire()), Some(mode));
+        }
+        assert_eq!(MacMode::from_wire(0x13), None);
+        assert_eq!(MacMode::from_wire(0x05), None);
+        assert_eq!(MacMode::WegmanCarter { tag_len: 8 }.validate(), Err(MacError::InvalidMode));
+    }
+
+    #[test]
+    fn test_sip64_constant_term_forgery() {
+        // Documents why `Sip64` reports no bound.
+        let key: [u8; MAC_LEN] = core::array::from_fn(|i| (i as u8).wrapping_mul(7).wrapping_add(1));
+        let ct = [1u8, 2, 3];
+        let tag = sip64_tag(&ct, b"md", &key);
+        let forged_ct = [1u8 ^ 0x5A, 2, 3];
+        let forged_tag = tag.map(|t| t ^ 0x5A);
+        assert_eq!(sip64_tag(&forged_ct, b"md", &key), forged_tag);
+    }
+}
@@ -1,5 +1,6 @@
 #![forbid(unsafe_code)]
 
+use alloc::collections::BTreeSet;
 use crate::core::masterpad::{MasterPad, PadError};
 use crate::core::sip64::{MAC_LEN, verify as sip_verify};
 use crate::core::otp_engine::{encrypt_and_tag, decrypt, split_block};
@@ -8,17 +9,41 @@ use zeroize::Zeroizing;
 // Ratchet provides forward-only session progression over a MasterPad.
 // Each step consumes a block `keystream || mac_key(64B)` and binds the counter
 // into metadata to prevent reordering and replay.
+// Receivers may open blocks out of order within a bounded skip window: blocks
+// skipped over stay unopened until they arrive or the window moves past them,
+// at which point their keystream is burned.
 // No unsafe; constant-time encryption/decryption via otp_engine; strict bounds.
 
+// Default number of counters a receiver may lag behind or jump ahead of.
+pub const DEFAULT_SKIP_WINDOW: u64 = 64;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RatchetError {
     Pad(PadError),
     TagMismatch,
+    // Counter was already opened, evicted, or its block overlaps used pad.
+    Replay,
+    // Counter lies further ahead than the skip window allows.
+    OutsideWindow,
+}
+
+// Pad range skipped over by an out-of-order jump; it may hold the blocks of
+// counters `first_ctr..=last_ctr` that have not arrived yet.
+struct Hole {
+    start: usize,
+    end: usize,
+    first_ctr: u64,
+    last_ctr: u64,
 }
 
 pub struct Ratchet {
     pad: MasterPad,
     ctr: u64,
+    window: u64,
+    // Skipped counters that may still be opened.
+    pending: BTreeSet<u64>,
+    // Unburned pad ranges that may still hold pending blocks.
+    holes: Vec<Hole>,
 }
 
 impl From<PadError> for RatchetError {
@@ -28,7 +53,12 @@ impl From<PadError> for RatchetError {
 impl Ratchet {
     // Create from a MasterPad; counter starts at 0.
     pub fn from_pad(pad: MasterPad) -> Self {
-        Ratchet { pad, ctr: 0 }
+        Self::with_window(pad, DEFAULT_SKIP_WINDOW)
+    }
+
+    // Create from a MasterPad with a custom skip window (0 = strictly in order).
+    pub fn with_window(pad: MasterPad, window: u64) -> Self {
+        Ratchet { pad, ctr: 0, window, pending: BTreeSet::new(), holes: Vec::new() }
     }
 
     // Remaining pad bytes.
@@ -37,9 +67,16 @@ impl Ratchet {
     // Current counter value.
     pub fn counter(&self) -> u64 { self.ctr }
 
-    fn meta(&self, ad: &[u8]) -> Vec<u8> {
+    // Pad offset of the next block; senders transmit it with the counter so
+    // receivers can open out of order via `open_at`.
+    pub fn offset(&self) -> usize { self.pad.position() }
+
+    // Number of skipped counters that can still be opened.
+    pub fn pending(&self) -> usize { self.pending.len() }
+
+    fn meta(ctr: u64, ad: &[u8]) -> Vec<u8> {
         let mut m = Vec::with_capacity(8 + ad.len());
-        m.extend_from_slice(&self.ctr.to_le_bytes());
+        m.extend_from_slice(&ctr.to_le_bytes());
         m.extend_from_slice(ad);
         m
     }
@@ -47,33 +84,120 @@ impl Ratchet {
     // Seal: consume a block, bind counter + ad into MAC, return (ct, tag).
     pub fn seal(&mut self, plaintext: &[u8], ad: &[u8]) -> Result<(Vec<u8>, [u8; MAC_LEN]), RatchetError> {
         let block = self.pad.take_block(plaintext.len())?;
-        let meta = self.meta(ad);
+        let meta = Self::meta(self.ctr, ad);
         let sealed = encrypt_and_tag(plaintext, &meta, &block).map_err(|_| RatchetError::TagMismatch)?;
         self.ctr = self.ctr.wrapping_add(1);
         Ok(sealed)
     }
 
-    // Open: peek block, verify MAC without consuming; on success, consume and decrypt.
+    // Open the next in-order block: verify MAC without consuming; on success, consume and decrypt.
     pub fn open(&mut self, ciphertext: &[u8], ad: &[u8], tag: &[u8; MAC_LEN]) -> Result<Vec<u8>, RatchetError> {
-        let meta = self.meta(ad);
-        let ks_owned = {
-            let block = self.pad.peek_block(ciphertext.len())?;
-            let (keystream, mac_key) = split_block(block, ciphertext.len()).map_err(|_| RatchetError::TagMismatch)?;
-            
-            // Use Zeroizing to ensure sensitive keys are wiped from stack/heap on drop
-            let ks = Zeroizing::new(keystream.to_vec());
-            let mut mk = Zeroizing::new([0u8; MAC_LEN]);
-            mk.copy_from_slice(mac_key);
-            
-            if !sip_verify(ciphertext, &meta, &mk, tag) {
-                return Err(RatchetError::TagMismatch);
+        let offset = self.pad.position();
+        self.open_at(self.ctr, offset, ciphertext, ad, tag)
+    }
+
+    // Open the block sealed under `counter` at pad `offset`.
+    //
+    // Counters ahead of the current one (by at most the skip window) are accepted;
+    // the counters jumped over become pending and can be opened later from the pad
+    // range that was skipped. A jump with no skipped pad leaves nothing to open the
+    // skipped counters from, and pad skipped without a counter jump is burned.
+    // Replays, evicted counters and blocks overlapping already-used pad are
+    // rejected before any MAC check.
+    pub fn open_at(
+        &mut self,
+        counter: u64,
+        offset: usize,
+        ciphertext: &[u8],
+        ad: &[u8],
+        tag: &[u8; MAC_LEN],
+    ) -> Result<Vec<u8>, RatchetError> {
+        let end = offset.checked_add(ciphertext.len() + MAC_LEN).ok_or(PadError::Insufficient)?;
+        if counter >= self.ctr {
+            if counter - self.ctr > self.window {
+                return Err(RatchetError::OutsideWindow);
             }
-            ks
-        };
-        let _ = self.pad.consume_block(ciphertext.len())?;
-        let pt = decrypt(ciphertext, &ks_owned).map_err(|_| RatchetError::TagMismatch)?;
-        self.ctr = self.ctr.wrapping_add(1);
-        Ok(pt)
+            let pos = self.pad.position();
+            if offset < pos {
+                return Err(RatchetError::Replay);
+            }
+            let ks = self.verify_at(counter, offset, ciphertext, ad, tag)?;
+            // Skipped counters stay pending only while a skipped pad range can still
+            // hold their blocks; pad skipped without any counter to fill it is burned.
+            if offset > pos {
+                if counter > self.ctr {
+                    self.pending.extend(self.ctr..counter);
+                    self.holes.push(Hole { start: pos, end: offset, first_ctr: self.ctr, last_ctr: counter - 1 });
+                } else {
+                    self.pad.burn_range(pos, offset)?;
+                }
+            }
+            self.pad.advance_to(offset)?;
+            let _ = self.pad.consume_block(ciphertext.len())?;
+            self.ctr = counter.wrapping_add(1);
+            self.evict()?;
+            decrypt(ciphertext, &ks).map_err(|_| RatchetError::TagMismatch)
+        } else {
+            if !self.pending.contains(&counter) {
+                return Err(RatchetError::Replay);
+            }
+            let idx = self.holes.iter()
+                .position(|h| h.first_ctr <= counter && counter <= h.last_ctr && h.start <= offset && end <= h.end)
+                .ok_or(RatchetError::Replay)?;
+            let ks = self.verify_at(counter, offset, ciphertext, ad, tag)?;
+            self.pending.remove(&counter);
+            let hole = self.holes.swap_remove(idx);
+            if hole.start < offset {
+                self.holes.push(Hole { end: offset, ..hole });
+            }
+            if end < hole.end {
+                self.holes.push(Hole { start: end, ..hole });
+            }
+            self.pad.burn_range(offset, end)?;
+            decrypt(ciphertext, &ks).map_err(|_| RatchetError::TagMismatch)
+        }
+    }
+
+    // Verify the tag of the block at `offset` and return a copy of its keystream.
+    fn verify_at(
+        &self,
+        counter: u64,
+        offset: usize,
+        ciphertext: &[u8],
+        ad: &[u8],
+        tag: &[u8; MAC_LEN],
+    ) -> Result<Zeroizing<Vec<u8>>, RatchetError> {
+        let meta = Self::meta(counter, ad);
+        let block = self.pad.peek_at(offset, ciphertext.len())?;
+        let (keystream, mac_key) = split_block(block, ciphertext.len()).map_err(|_| RatchetError::TagMismatch)?;
+
+        // Use Zeroizing to ensure sensitive keys are wiped from stack/heap on drop
+        let ks = Zeroizing::new(keystream.to_vec());
+        let mut mk = Zeroizing::new([0u8; MAC_LEN]);
+        mk.copy_from_slice(mac_key);
+
+        if !sip_verify(ciphertext, &meta, &mk, tag) {
+            return Err(RatchetError::TagMismatch);
+        }
+        Ok(ks)
+    }
+
+    // Forget pending counters that fell out of the window and burn the pad
+    // ranges that can no longer be opened.
+    fn evict(&mut self) -> Result<(), RatchetError> {
+        // Window is measured from the highest counter opened so far (ctr - 1).
+        let floor = self.ctr.saturating_sub(self.window.saturating_add(1));
+        self.pending.retain(|&c| c >= floor);
+        let mut i = 0;
+        while i < self.holes.len() {
+            if self.holes[i].last_ctr < floor {
+                let hole = self.holes.swap_remove(i);
+                self.pad.burn_range(hole.start, hole.end)?;
+            } else {
+                i += 1;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -126,4 +250,108 @@ mod tests {
         assert_eq!(pt1, b"MSG-ONE!");
         assert_eq!(pt2, b"MSG-TWO!");
     }
+
+    #[test]
+    fn test_ratchet_out_of_order_within_window() {
+        let payload_len = 8;
+        let mut sender = Ratchet::from_pad(make_pad(payload_len, 3));
+        let mut sent = Vec::new();
+        for (i, msg) in [b"MSG-ONE!", b"MSG-TWO!", b"MSG-THR!"].iter().enumerate() {
+            let off = sender.offset();
+            let (ct, tag) = sender.seal(*msg, b"Z").unwrap();
+            sent.push((i as u64, off, ct, tag));
+        }
+
+        let mut receiver = Ratchet::from_pad(make_pad(payload_len, 3));
+        let (c, o, ct, tag) = &sent[2];
+        assert_eq!(receiver.open_at(*c, *o, ct, b"Z", tag).unwrap(), b"MSG-THR!");
+        assert_eq!(receiver.counter(), 3);
+        assert_eq!(receiver.pending(), 2);
+        let (c, o, ct, tag) = &sent[0];
+        assert_eq!(receiver.open_at(*c, *o, ct, b"Z", tag).unwrap(), b"MSG-ONE!");
+        let (c, o, ct, tag) = &sent[1];
+        assert_eq!(receiver.open_at(*c, *o, ct, b"Z", tag).unwrap(), b"MSG-TWO!");
+        assert_eq!(receiver.pending(), 0);
+    }
+
+    #[test]
+    fn test_ratchet_replay_rejected() {
+        let payload_len = 8;
+        let mut sender = Ratchet::from_pad(make_pad(payload_len, 2));
+        let (ct1, tag1) = sender.seal(b"MSG-ONE!", b"X").unwrap();
+        let off2 = sender.offset();
+        let (ct2, tag2) = sender.seal(b"MSG-TWO!", b"Y").unwrap();
+
+        let mut receiver = Ratchet::from_pad(make_pad(payload_len, 2));
+        receiver.open_at(1, off2, &ct2, b"Y", &tag2).unwrap();
+        assert_eq!(receiver.open_at(1, off2, &ct2, b"Y", &tag2), Err(RatchetError::Replay));
+        receiver.open_at(0, 0, &ct1, b"X", &tag1).unwrap();
+        assert_eq!(receiver.open_at(0, 0, &ct1, b"X", &tag1), Err(RatchetError::Replay));
+    }
+
+    #[test]
+    fn test_ratchet_gaps_without_counters_or_pad() {
+        let payload_len = 4;
+        let block = payload_len + MAC_LEN;
+        let mut sender = Ratchet::from_pad(make_pad(payload_len, 3));
+        let (ct, tag) = sender.seal(b"ping", b"").unwrap();
+
+        // Counter 0 placed one block further along: the skipped block is burned.
+        let mut receiver = Ratchet::from_pad(make_pad(payload_len, 3));
+        let mut relocated = Ratchet::from_pad(make_pad(payload_len, 3));
+        relocated.pad.advance_to(block).unwrap();
+        let (ct1, tag1) = relocated.seal(b"pong", b"").unwrap();
+        assert_eq!(receiver.open_at(0, block, &ct1, b"", &tag1).unwrap(), b"pong");
+        assert!(receiver.pad.peek_at(0, payload_len).unwrap().iter().all(|&b| b == 0));
+        assert_eq!(receiver.pending(), 0);
+
+        // Counter jump without skipped pad: nothing can back the skipped counters.
+        let mut receiver = Ratchet::from_pad(make_pad(payload_len, 3));
+        let mut jumped = Ratchet::from_pad(make_pad(payload_len, 3));
+        jumped.ctr = 2;
+        let (ct2, tag2) = jumped.seal(b"pang", b"").unwrap();
+        assert_eq!(receiver.open_at(2, 0, &ct2, b"", &tag2).unwrap(), b"pang");
+        assert_eq!(receiver.pending(), 0);
+        assert_eq!(receiver.open_at(0, 0, &ct, b"", &tag), Err(RatchetError::Replay));
+    }
+
+    #[test]
+    fn test_ratchet_window_exceeded() {
+        let payload_len = 4;
+        let mut sender = Ratchet::with_window(make_pad(payload_len, 4), 2);
+        let mut last = None;
+        for _ in 0..4 {
+            let off = sender.offset();
+            last = Some((off, sender.seal(b"ping", b"").unwrap()));
+        }
+        let (off, (ct, tag)) = last.unwrap();
+        let mut receiver = Ratchet::with_window(make_pad(payload_len, 4), 2);
+        assert_eq!(receiver.open_at(3, off, &ct, b"", &tag), Err(RatchetError::OutsideWindow));
+        assert_eq!(receiver.counter(), 0);
+    }
+
+    #[test]
+    fn test_ratchet_evicted_blocks_burned() {
+        let payload_len = 4;
+        let block = payload_len + MAC_LEN;
+        let mut sender = Ratchet::with_window(make_pad(payload_len, 4), 1);
+        let mut sent = Vec::new();
+        for _ in 0..4 {
+            let off = sender.offset();
+            sent.push((off, sender.seal(b"ping", b"").unwrap()));
+        }
+
+        let mut receiver = Ratchet::with_window(make_pad(payload_len, 4), 1);
+        let (off, (ct, tag)) = &sent[1];
+        receiver.open_at(1, *off, ct, b"", tag).unwrap();
+        assert_eq!(receiver.pending(), 1);
+        let (off, (ct, tag)) = &sent[3];
+        receiver.open_at(3, *off, ct, b"", tag).unwrap();
+        // Counter 0 fell out of the window: its block is burned and it can no longer be opened.
+        assert!(receiver.pad.peek_at(0, payload_len).unwrap()[..block].iter().all(|&b| b == 0));
+        let (off, (ct, tag)) = &sent[0];
+        assert_eq!(receiver.open_at(0, *off, ct, b"", tag), Err(RatchetError::Replay));
+        let (off, (ct, tag)) = &sent[2];
+        assert_eq!(receiver.open_at(2, *off, ct, b"", tag).unwrap(), b"ping");
+    }
 }
@@ -2,10 +2,15 @@
 // SIP-64 information-theoretic MAC over GF(2^8).
 // - Parallel Horner evaluation: each byte of the 64-byte key is an independent x_i.
 // - Constant-time: fixed iterations, no data-dependent branches or memory accesses.
-// - No unsafe and no table lookups, avoiding cache-based side channels.
+// - No table lookups, avoiding cache-based side channels; lanes are multiplied with the
+//   bulk `gf256::mul_pointwise` kernel.
 // - Suitable for message integrity and DBAP proofs per the whitepaper.
+// - Streaming: `Sip64Hasher` absorbs `ciphertext || metadata` front to back and
+//   yields the same tag as `sip64_tag`.
 
-use crate::core::gf256::GF256;
+use crate::core::gf256::{GF256, mul_pointwise};
+use crate::core::xor::xor_inplace;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 pub const MAC_LEN: usize = 64;
 
@@ -18,31 +23,99 @@ const _: () = {
 /// Compute SIP-64 tag for `metadata || ciphertext` with a 64-byte GF(2^8) key.
 #[inline(always)]
 pub fn sip64_tag(ciphertext: &[u8], metadata: &[u8], mac_key: &[u8; MAC_LEN]) -> [u8; MAC_LEN] {
-    // Optimization: Zero-copy cast using transparent representation
-    let xs: [GF256; MAC_LEN] = unsafe { core::mem::transmute(*mac_key) };
-    let mut acc: [GF256; MAC_LEN] = [GF256(0); MAC_LEN];
+    let mut acc = [0u8; MAC_LEN];
 
     // Combine metadata and ciphertext loops to reduce overhead
     for &c in metadata.iter().rev().chain(ciphertext.iter().rev()) {
-        let gc = GF256(c);
-        
-        // Fully unrolled loop for 64 parallel lanes (stride 8)
-        // 64 is divisible by 8, so no remainder loop needed.
-        let mut i = 0;
-        while i < MAC_LEN {
-            acc[i] = acc[i] * xs[i] + gc;
-            acc[i + 1] = acc[i + 1] * xs[i + 1] + gc;
-            acc[i + 2] = acc[i + 2] * xs[i + 2] + gc;
-            acc[i + 3] = acc[i + 3] * xs[i + 3] + gc;
-            acc[i + 4] = acc[i + 4] * xs[i + 4] + gc;
-            acc[i + 5] = acc[i + 5] * xs[i + 5] + gc;
-            acc[i + 6] = acc[i + 6] * xs[i + 6] + gc;
-            acc[i + 7] = acc[i + 7] * xs[i + 7] + gc;
-            i += 8;
+        // 64 parallel lanes: acc_i = acc_i * x_i + c
+        horner_step(&mut acc, mac_key, c);
+    }
+
+    acc
+}
+
+// One Horner step across all 64 lanes via the bulk GF(2^8) pointwise kernel.
+#[inline(always)]
+fn horner_step(acc: &mut [u8; MAC_LEN], xs: &[u8; MAC_LEN], c: u8) {
+    mul_pointwise(acc, xs);
+    for a in acc.iter_mut() {
+        *a ^= c;
+    }
+}
+
+// Bytes absorbed per Horner pass in the streaming hasher.
+const STREAM_CHUNK: usize = 256;
+
+/// Incremental SIP-64 MAC.
+///
+/// `sip64_tag` evaluates `sum f_j * x^j` over `f = ciphertext || metadata` by running
+/// Horner from the last byte backwards. The hasher reorders this front to back: each
+/// chunk is Horner-evaluated on its own and shifted into place by `x^offset`, so the
+/// data never has to be held in memory. Feed the ciphertext first, then the metadata.
+///
+/// Constant-time in the data and key; timing depends only on the absorbed lengths.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct Sip64Hasher {
+    xs: [u8; MAC_LEN],
+    // x^STREAM_CHUNK per lane.
+    xs_chunk: [u8; MAC_LEN],
+    // x^n per lane, n = bytes absorbed so far.
+    pow: [u8; MAC_LEN],
+    acc: [u8; MAC_LEN],
+}
+
+impl Sip64Hasher {
+    /// Start a new tag computation under a 64-byte GF(2^8) key.
+    pub fn new(mac_key: &[u8; MAC_LEN]) -> Self {
+        let xs = *mac_key;
+        let xs_chunk = lane_pow(&xs, STREAM_CHUNK);
+        Sip64Hasher { xs, xs_chunk, pow: [1u8; MAC_LEN], acc: [0u8; MAC_LEN] }
+    }
+
+    /// Absorb the next bytes of `ciphertext || metadata`.
+    pub fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(STREAM_CHUNK) {
+            let mut h = [0u8; MAC_LEN];
+            for &c in chunk.iter().rev() {
+                horner_step(&mut h, &self.xs, c);
+            }
+            let mut shift = if chunk.len() == STREAM_CHUNK { self.xs_chunk } else { lane_pow(&self.xs, chunk.len()) };
+            // acc += x^n * h; x^n *= x^len
+            mul_pointwise(&mut h, &self.pow);
+            xor_inplace(&mut self.acc, &h);
+            mul_pointwise(&mut self.pow, &shift);
+            h.zeroize();
+            shift.zeroize();
+        }
+    }
+
+    /// Finish and return the tag.
+    pub fn finalize(self) -> [u8; MAC_LEN] {
+        self.acc
+    }
+
+    /// Finish and compare against `tag` in constant time.
+    pub fn verify(self, tag: &[u8; MAC_LEN]) -> bool {
+        ct_eq(&self.acc, tag)
+    }
+}
+
+// x_i^e for every lane by square-and-multiply; `e` is a public length.
+fn lane_pow(xs: &[u8; MAC_LEN], mut e: usize) -> [u8; MAC_LEN] {
+    let mut base = *xs;
+    let mut sq = [0u8; MAC_LEN];
+    let mut out = [1u8; MAC_LEN];
+    while e > 0 {
+        if e & 1 == 1 {
+            mul_pointwise(&mut out, &base);
         }
+        sq.copy_from_slice(&base);
+        mul_pointwise(&mut base, &sq);
+        e >>= 1;
     }
-    
-    unsafe { core::mem::transmute(acc) }
+    base.zeroize();
+    sq.zeroize();
+    out
 }
 
 /// Constant-time equality check for two 64-byte tags.
@@ -78,5 +151,32 @@ mod tests {
         wrong[0] ^= 1;
         assert!(!verify(&ct, &md, &key, &wrong));
     }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let ct: Vec<u8> = (0..1000u32).map(|i| (i * 31 + 7) as u8).collect();
+        let md = b"group:7|ctr:42";
+        let mut key = [0u8; MAC_LEN];
+        for (i, k) in key.iter_mut().enumerate() { *k = (i as u8).wrapping_mul(13).wrapping_add(5); }
+        let expected = sip64_tag(&ct, md, &key);
+
+        for split in [1usize, 3, 64, 255, 256, 257, 999] {
+            let mut h = Sip64Hasher::new(&key);
+            for piece in ct.chunks(split) {
+                h.update(piece);
+            }
+            h.update(md);
+            assert_eq!(h.finalize(), expected, "split {}", split);
+        }
+
+        let mut h = Sip64Hasher::new(&key);
+        h.update(&ct);
+        h.update(md);
+        assert!(h.verify(&expected));
+        let mut h = Sip64Hasher::new(&key);
+        h.update(&ct[1..]);
+        h.update(md);
+        assert!(!h.verify(&expected));
+    }
 }
 // End of SIP-64 module.
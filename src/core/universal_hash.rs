@@ -3,11 +3,18 @@
 //!
 //! - Linear over GF(2): `H(a ⊕ b) = H(a) ⊕ H(b)`.
 //! - Constant-time bit convolution via 64-bit word alignment.
-//! - No unsafe and no table lookups to avoid cache side channels.
+//! - No table lookups to avoid cache side channels.
+//! - `ToeplitzExtractor` computes the same hash with carry-less multiplication
+//!   (PCLMULQDQ / PMULL, constant-time software fallback), streaming the input in chunks.
+//!
+//! With input words `x_w` and key words `k_m`, output word `q` is
+//! `y_q = (L_q >> 63) ^ (low64(L_{q+1}) << 1)` where `L_q = XOR_w clmul(rev(x_w), k_{q+w})`,
+//! so the cost is O(in_words * out_words) multiplications instead of O(in_bits * out_bits).
 
 extern crate alloc;
 use alloc::vec::Vec;
 use core::convert::TryInto;
+use zeroize::Zeroizing;
 
 /// Errors returned by universal hash routines.
 #[derive(Debug, PartialEq, Eq)]
@@ -81,6 +88,225 @@ pub fn toeplitz_tag(input: &[u8], key: &[u8], out_len: usize) -> Result<Vec<u8>,
     Ok(out)
 }
 
+/// Streaming Toeplitz extractor producing the same output as `toeplitz_tag`.
+///
+/// Input may be fed in arbitrary chunks; the key length requirement
+/// (`key_bits >= input_bits + out_bits - 1`) is checked in `finalize`, once the
+/// total input length is known. Timing depends only on public lengths.
+pub struct ToeplitzExtractor {
+    key: Zeroizing<Vec<u64>>,
+    key_bits: usize,
+    out_len: usize,
+    // L_0..=L_{out_words}
+    acc: Zeroizing<Vec<u128>>,
+    // Full input words absorbed so far.
+    words: usize,
+    partial: Zeroizing<[u8; 8]>,
+    partial_len: usize,
+}
+
+impl ToeplitzExtractor {
+    /// Create an extractor with `out_len` output bytes under `key`.
+    pub fn new(key: &[u8], out_len: usize) -> Self {
+        #[allow(clippy::manual_div_ceil)]
+        let out_words = (out_len * 8 + 63) / 64;
+        ToeplitzExtractor {
+            key: Zeroizing::new(to_words_le(key.len() * 8, key)),
+            key_bits: key.len() * 8,
+            out_len,
+            acc: Zeroizing::new(vec![0u128; out_words + 1]),
+            words: 0,
+            partial: Zeroizing::new([0u8; 8]),
+            partial_len: 0,
+        }
+    }
+
+    /// Absorb the next chunk of input.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if self.partial_len > 0 {
+            let take = core::cmp::min(8 - self.partial_len, data.len());
+            self.partial[self.partial_len..self.partial_len + take].copy_from_slice(&data[..take]);
+            self.partial_len += take;
+            data = &data[take..];
+            if self.partial_len < 8 {
+                return;
+            }
+            let w = u64::from_le_bytes(*self.partial);
+            self.absorb(&[w]);
+            self.partial_len = 0;
+        }
+
+        let mut chunks = data.chunks_exact(8);
+        let mut batch = Zeroizing::new([0u64; 64]);
+        let mut n = 0;
+        for chunk in chunks.by_ref() {
+            batch[n] = u64::from_le_bytes(chunk.try_into().unwrap());
+            n += 1;
+            if n == batch.len() {
+                self.absorb(&batch[..]);
+                n = 0;
+            }
+        }
+        if n > 0 {
+            self.absorb(&batch[..n]);
+        }
+
+        let rem = chunks.remainder();
+        self.partial[..rem.len()].copy_from_slice(rem);
+        self.partial_len = rem.len();
+    }
+
+    /// Finish: zero-pad the last word and return `out_len` bytes.
+    pub fn finalize(mut self) -> Result<Vec<u8>, UhError> {
+        let in_bits = (self.words * 8 + self.partial_len) * 8;
+        let need_bits = (in_bits + self.out_len * 8).saturating_sub(1);
+        if self.key_bits < need_bits { return Err(UhError::KeyTooShort); }
+
+        if self.partial_len > 0 {
+            self.partial[self.partial_len..].fill(0);
+            let w = u64::from_le_bytes(*self.partial);
+            self.absorb(&[w]);
+        }
+
+        let mut out = Vec::with_capacity(self.acc.len() * 8);
+        for q in 0..self.acc.len() - 1 {
+            let y = ((self.acc[q] >> 63) as u64) ^ ((self.acc[q + 1] as u64) << 1);
+            out.extend_from_slice(&y.to_le_bytes());
+        }
+        out.truncate(self.out_len);
+        Ok(out)
+    }
+
+    fn absorb(&mut self, words: &[u64]) {
+        clmul_accumulate(&mut self.acc, &self.key, self.words, words);
+        self.words += words.len();
+    }
+}
+
+/// Toeplitz hash via the carry-less-multiply extractor; same result as `toeplitz_tag`.
+pub fn toeplitz_tag_fast(input: &[u8], key: &[u8], out_len: usize) -> Result<Vec<u8>, UhError> {
+    let mut ext = ToeplitzExtractor::new(key, out_len);
+    ext.update(input);
+    ext.finalize()
+}
+
+// acc[q] ^= clmul(rev(x_w), key[q + w]) for each input word; key words past the end read as 0
+// (the length check in `finalize` guarantees those never reach the truncated output).
+#[inline(always)]
+fn clmul_accumulate(acc: &mut [u128], key: &[u64], first_word: usize, words: &[u64]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("pclmulqdq") {
+            // SAFETY: feature checked at runtime; the kernel only touches the given slices.
+            return unsafe { clmul_accumulate_pclmul(acc, key, first_word, words) };
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if core::arch::is_aarch64_feature_detected!("aes") {
+            // SAFETY: feature checked at runtime; the kernel only touches the given slices.
+            return unsafe { clmul_accumulate_pmull(acc, key, first_word, words) };
+        }
+    }
+
+    for (i, &x) in words.iter().enumerate() {
+        let rx = x.reverse_bits();
+        for (q, a) in acc.iter_mut().enumerate() {
+            *a ^= clmul_soft(rx, key_word(key, q + first_word + i));
+        }
+    }
+}
+
+/// Single 64x64 -> 128 carry-less multiply, dispatched like `clmul_accumulate`.
+#[inline(always)]
+pub(crate) fn clmul64(a: u64, b: u64) -> u128 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("pclmulqdq") {
+            // SAFETY: feature checked at runtime; operates on register values only.
+            return unsafe { clmul64_pclmul(a, b) };
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if core::arch::is_aarch64_feature_detected!("aes") {
+            // SAFETY: feature checked at runtime; operates on register values only.
+            return unsafe { core::arch::aarch64::vmull_p64(a, b) };
+        }
+    }
+
+    clmul_soft(a, b)
+}
+
+#[inline(always)]
+fn key_word(key: &[u64], idx: usize) -> u64 {
+    key.get(idx).copied().unwrap_or(0)
+}
+
+// Constant-time 64x64 -> 128 carry-less multiply (mask-based, fixed 64 iterations).
+#[inline(always)]
+fn clmul_soft(a: u64, b: u64) -> u128 {
+    let a = a as u128;
+    let mut r = 0u128;
+    for i in 0..64 {
+        let mask = 0u128.wrapping_sub(((b >> i) & 1) as u128);
+        r ^= (a << i) & mask;
+    }
+    r
+}
+
+/// PCLMULQDQ path.
+///
+/// # Safety
+/// - Requires PCLMULQDQ (checked at runtime via `is_x86_feature_detected!`).
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq", enable = "sse2")]
+unsafe fn clmul_accumulate_pclmul(acc: &mut [u128], key: &[u64], first_word: usize, words: &[u64]) {
+    use core::arch::x86_64::*;
+    for (i, &x) in words.iter().enumerate() {
+        let rx = _mm_set_epi64x(0, x.reverse_bits() as i64);
+        for (q, a) in acc.iter_mut().enumerate() {
+            let k = _mm_set_epi64x(0, key_word(key, q + first_word + i) as i64);
+            let p = _mm_clmulepi64_si128(rx, k, 0x00);
+            let lo = _mm_cvtsi128_si64(p) as u64;
+            let hi = _mm_cvtsi128_si64(_mm_unpackhi_epi64(p, p)) as u64;
+            *a ^= ((hi as u128) << 64) | lo as u128;
+        }
+    }
+}
+
+/// Single-product PCLMULQDQ path.
+///
+/// # Safety
+/// - Requires PCLMULQDQ (checked at runtime via `is_x86_feature_detected!`).
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq", enable = "sse2")]
+unsafe fn clmul64_pclmul(a: u64, b: u64) -> u128 {
+    use core::arch::x86_64::*;
+    let p = _mm_clmulepi64_si128(_mm_set_epi64x(0, a as i64), _mm_set_epi64x(0, b as i64), 0x00);
+    let lo = _mm_cvtsi128_si64(p) as u64;
+    let hi = _mm_cvtsi128_si64(_mm_unpackhi_epi64(p, p)) as u64;
+    ((hi as u128) << 64) | lo as u128
+}
+
+/// PMULL path.
+///
+/// # Safety
+/// - Requires the AES extension (PMULL.64, checked at runtime on aarch64).
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon", enable = "aes")]
+unsafe fn clmul_accumulate_pmull(acc: &mut [u128], key: &[u64], first_word: usize, words: &[u64]) {
+    use core::arch::aarch64::*;
+    for (i, &x) in words.iter().enumerate() {
+        let rx = x.reverse_bits();
+        for (q, a) in acc.iter_mut().enumerate() {
+            *a ^= vmull_p64(rx, key_word(key, q + first_word + i));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +342,54 @@ mod tests {
         let r = toeplitz_tag(&x, &k, 64);
         assert!(matches!(r, Err(UhError::KeyTooShort)));
     }
+
+    #[test]
+    fn test_fast_matches_reference() {
+        let key: Vec<u8> = (0..400u32).map(|i| (i.wrapping_mul(0x9E37) >> 3) as u8).collect();
+        for in_len in [1usize, 7, 8, 9, 64, 100, 257] {
+            let input: Vec<u8> = (0..in_len as u32).map(|i| (i * 31 + 5) as u8).collect();
+            for out_len in [1usize, 5, 8, 16, 33] {
+                let reference = toeplitz_tag(&input, &key, out_len).unwrap();
+                assert_eq!(toeplitz_tag_fast(&input, &key, out_len).unwrap(), reference,
+                    "in {} out {}", in_len, out_len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clmul_paths_agree() {
+        assert_eq!(clmul_soft(0b11, 0b11), 0b101);
+        let key: Vec<u64> = (0..9u64).map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15)).collect();
+        let words: Vec<u64> = (0..5u64).map(|i| !i.wrapping_mul(0xC2B2_AE3D_27D4_EB4F)).collect();
+        let mut soft = vec![0u128; 4];
+        for (i, &x) in words.iter().enumerate() {
+            for (q, a) in soft.iter_mut().enumerate() {
+                *a ^= clmul_soft(x.reverse_bits(), key_word(&key, q + 1 + i));
+            }
+        }
+        let mut dispatched = vec![0u128; 4];
+        clmul_accumulate(&mut dispatched, &key, 1, &words);
+        assert_eq!(soft, dispatched);
+        for (&a, &b) in words.iter().zip(key.iter()) {
+            assert_eq!(clmul64(a, b), clmul_soft(a, b));
+        }
+    }
+
+    #[test]
+    fn test_extractor_streaming() {
+        let key: Vec<u8> = (0..1200u32).map(|i| (i * 7 + 1) as u8).collect();
+        let input: Vec<u8> = (0..1000u32).map(|i| (i ^ (i >> 3)) as u8).collect();
+        let reference = toeplitz_tag(&input, &key, 32).unwrap();
+        for split in [1usize, 3, 8, 13, 512] {
+            let mut ext = ToeplitzExtractor::new(&key, 32);
+            for piece in input.chunks(split) {
+                ext.update(piece);
+            }
+            assert_eq!(ext.finalize().unwrap(), reference, "split {}", split);
+        }
+
+        let mut ext = ToeplitzExtractor::new(&key[..100], 32);
+        ext.update(&input);
+        assert_eq!(ext.finalize(), Err(UhError::KeyTooShort));
+    }
 }
@@ -1,16 +1,23 @@
 //! Entropy Aggregator.
 //!
 //! Combines multiple entropy sources into a unified stream.
+//!
+//! Each source carries the SP 800-90B estimator `Track` it is assessed on; sources start on
+//! the non-IID track until `classify` runs the IID tests on their raw samples.
 
 extern crate alloc;
 use alloc::vec::Vec;
 use alloc::boxed::Box;
 use zeroize::Zeroizing;
 use super::{EntropySource, EntropyError};
+use super::sp800_90b::Track;
+#[cfg(feature = "std")]
+use super::sp800_90b::{iid_test, IidVerdict};
 
 /// Aggregator that collects from multiple sources.
 pub struct EntropyAggregator {
     sources: Vec<Box<dyn EntropySource + Send + Sync>>,
+    tracks: Vec<Track>,
 }
 
 impl EntropyAggregator {
@@ -18,6 +25,7 @@ impl EntropyAggregator {
     pub fn new() -> Self {
         Self {
             sources: Vec::new(),
+            tracks: Vec::new(),
         }
     }
 
@@ -27,6 +35,48 @@ impl EntropyAggregator {
         S: EntropySource + Send + Sync + 'static,
     {
         self.sources.push(Box::new(source));
+        self.tracks.push(Track::NonIid);
+    }
+
+    /// Estimator track of the source at `index` (in `add_source` order).
+    pub fn track(&self, index: usize) -> Option<Track> {
+        self.tracks.get(index).copied()
+    }
+
+    /// Moves the source at `index` back to the non-IID track.
+    ///
+    /// Only `classify` can put a source on the IID track.
+    pub fn demote(&mut self, index: usize) -> Result<(), EntropyError> {
+        let slot = self.tracks.get_mut(index).ok_or(EntropyError::UnknownSource)?;
+        *slot = Track::NonIid;
+        Ok(())
+    }
+
+    /// Runs the SP 800-90B IID tests on raw `samples` from the source at `index` and moves it
+    /// to the track the verdict allows.
+    ///
+    /// `rng` shuffles the samples; it should not be the source under test.
+    #[cfg(feature = "std")]
+    pub fn classify<R: EntropySource + ?Sized>(
+        &mut self,
+        index: usize,
+        samples: &[u8],
+        bits_per_symbol: u8,
+        permutations: usize,
+        rng: &mut R
+    ) -> Result<IidVerdict, EntropyError> {
+        if index >= self.tracks.len() {
+            return Err(EntropyError::UnknownSource);
+        }
+        let verdict = iid_test(samples, bits_per_symbol, permutations, rng)?;
+        self.tracks[index] = verdict.track();
+        Ok(verdict)
+    }
+
+    /// Min-entropy per sample of raw `samples` from the source at `index`, on its track.
+    #[cfg(feature = "std")]
+    pub fn assess(&self, index: usize, samples: &[u8], bits_per_symbol: u8) -> Result<f64, EntropyError> {
+        self.track(index).ok_or(EntropyError::UnknownSource)?.min_entropy(samples, bits_per_symbol)
     }
 
     /// Fills the destination buffer by collecting from all sources and XORing the results.
@@ -147,4 +197,17 @@ mod tests {
         // 00 ^ 0102 = 0102
         assert_eq!(buf, [0x01, 0x02]);
     }
+
+    #[test]
+    fn test_source_tracks() {
+        let mut agg = EntropyAggregator::new();
+        agg.add_source(CustomSource::new());
+        assert_eq!(agg.track(0), Some(Track::NonIid));
+        assert_eq!(agg.track(1), None);
+
+        agg.demote(0).unwrap();
+        assert_eq!(agg.track(0), Some(Track::NonIid));
+        assert_eq!(agg.demote(1), Err(EntropyError::UnknownSource));
+        assert_eq!(agg.assess(1, &[0u8; 16], 1), Err(EntropyError::UnknownSource));
+    }
 }
@@ -40,6 +40,12 @@ pub enum EntropyError {
     HealthTestFailed,
     /// Platform not supported.
     NotSupported,
+    /// Too few samples for an entropy assessment.
+    InsufficientSamples,
+    /// Samples do not fit the declared sample width.
+    InvalidSamples,
+    /// No source at the given index.
+    UnknownSource,
 }
 
 /// A trait for entropy sources.
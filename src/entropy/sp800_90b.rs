@@ -1,13 +1,39 @@
-//! NIST SP 800-90B Health Tests.
+//! NIST SP 800-90B Health Tests and Entropy Assessment.
 //!
-//! Implements basic health monitoring for entropy sources as per NIST SP 800-90B.
+//! Implements basic health monitoring for entropy sources as per NIST SP 800-90B, and an
+//! offline min-entropy assessment of raw sample buffers.
 //!
 //! # Tests
 //! - Repetition Count Test: Detects catastrophic failures where the source becomes stuck.
 //! - Adaptive Proportion Test: Detects if a value becomes too common.
+//!
+//! # Assessment
+//! - `non_iid`: The ten non-IID min-entropy estimators (`std`).
+//! - `iid`: Permutation and chi-square IID tests, and restart tests (`std`). Their verdict
+//!   selects a source's `Track`.
 
 use super::EntropyError;
 
+#[cfg(feature = "std")]
+pub mod non_iid;
+#[cfg(feature = "std")]
+pub mod iid;
+
+#[cfg(feature = "std")]
+pub use non_iid::{assess_non_iid, NonIidAssessment, BOOTSTRAP_MIN_ENTROPY};
+#[cfg(feature = "std")]
+pub use iid::{iid_test, restart_test, IidVerdict, RestartVerdict};
+
+/// Estimator track a source is assessed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Track {
+    /// Samples passed the IID tests: the most common value estimate applies.
+    Iid,
+    /// Untested or failed: the full non-IID estimator suite applies.
+    #[default]
+    NonIid,
+}
+
 /// Health tester for entropy sources.
 pub struct HealthTester {
     // Repetition Count Test state
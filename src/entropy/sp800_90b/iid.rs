@@ -0,0 +1,684 @@
+//! SP 800-90B IID testing (Section 5) and restart tests (Section 3.1.4).
+//!
+//! A source may only be credited on the IID track (most common value estimate alone) after
+//! its samples pass the permutation test battery and both chi-square tests. `iid_test`
+//! runs them and returns an `IidVerdict`; `IidVerdict::track` picks the estimator track.
+//!
+//! # Permutation Tests
+//! Eleven statistics (periodicity and covariance at lags 1, 2, 8, 16 and 32, so nineteen
+//! values) are computed on the samples and on random shuffles of them. A statistic fails if
+//! the original ranks in the top or bottom 0.05% of the shuffles (the SP 800-90B cut-offs of
+//! 5 in 10 000, scaled to the number of shuffles). Binary samples use Hamming weights of
+//! 8-bit blocks for the directional, periodicity and covariance statistics and 8-bit block
+//! values for the collision statistics.
+//!
+//! There is no bzip2 implementation in this crate, so the compression statistic is the
+//! number of LZ78 phrases needed to encode the samples: like the compressed length, it
+//! drops when the sample order carries structure.
+//!
+//! # Chi-Square Tests
+//! Independence of successive samples and goodness-of-fit across ten sub-sequences, at
+//! significance 0.001. Critical values use the Wilson–Hilferty approximation.
+//!
+//! # Restart Tests
+//! `restart_test` validates an entropy estimate `H_I` on a `RESTARTS × RESTARTS` matrix,
+//! one row per restart of the source: a sanity check on the most common value in every row
+//! and column, then the chosen track's estimate on the row-wise and column-wise datasets.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+use crate::entropy::{EntropyError, EntropySource};
+use super::non_iid::{most_common_value, MIN_SAMPLES};
+use super::Track;
+
+/// Shuffles SP 800-90B prescribes for the permutation tests.
+pub const PERMUTATIONS: usize = 10_000;
+
+/// Restarts, and samples per restart, in a restart matrix.
+pub const RESTARTS: usize = 1000;
+
+/// Lags of the periodicity and covariance statistics.
+pub const LAGS: [usize; 5] = [1, 2, 8, 16, 32];
+
+/// Upper 0.1% point of the standard normal, for chi-square critical values.
+const Z_999: f64 = 3.0902;
+
+/// Significance of each restart sanity check (0.01 over the 2000 rows and columns).
+const RESTART_ALPHA: f64 = 0.01 / 2000.0;
+
+/// One permutation test statistic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statistic {
+    /// Largest distance of the running sum from its expected value.
+    Excursion,
+    /// Number of runs of increases or decreases.
+    DirectionalRuns,
+    /// Longest run of increases or decreases.
+    LongestDirectionalRun,
+    /// The larger of the number of increases and of decreases.
+    IncreasesDecreases,
+    /// Number of runs above or below the median.
+    MedianRuns,
+    /// Longest run above or below the median.
+    LongestMedianRun,
+    /// Mean number of samples until a value repeats.
+    AverageCollision,
+    /// Largest number of samples until a value repeats.
+    MaximumCollision,
+    /// Samples equal to the sample at the given lag.
+    Periodicity(usize),
+    /// Sum of products with the sample at the given lag.
+    Covariance(usize),
+    /// LZ78 phrase count (stands in for the bzip2 length).
+    Compression,
+}
+
+impl Statistic {
+    /// All nineteen statistics, in the order `IidVerdict::permutation` reports them.
+    pub fn all() -> Vec<Statistic> {
+        let mut all = vec![
+            Statistic::Excursion,
+            Statistic::DirectionalRuns,
+            Statistic::LongestDirectionalRun,
+            Statistic::IncreasesDecreases,
+            Statistic::MedianRuns,
+            Statistic::LongestMedianRun,
+            Statistic::AverageCollision,
+            Statistic::MaximumCollision,
+        ];
+        all.extend(LAGS.iter().map(|&p| Statistic::Periodicity(p)));
+        all.extend(LAGS.iter().map(|&p| Statistic::Covariance(p)));
+        all.push(Statistic::Compression);
+        all
+    }
+}
+
+/// Ranking of one statistic against its shuffles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermutationResult {
+    /// The statistic.
+    pub statistic: Statistic,
+    /// Its value on the samples in their original order.
+    pub value: f64,
+    /// Shuffles whose value is greater.
+    pub greater: usize,
+    /// Shuffles whose value is equal.
+    pub equal: usize,
+    /// Whether the original is not an outlier.
+    pub passed: bool,
+}
+
+/// Outcome of one chi-square test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquareResult {
+    /// The chi-square statistic.
+    pub statistic: f64,
+    /// Degrees of freedom.
+    pub df: usize,
+    /// Critical value at significance 0.001.
+    pub critical: f64,
+    /// Whether `statistic` stays at or below `critical` (and the test could be run).
+    pub passed: bool,
+}
+
+/// Result of `iid_test`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IidVerdict {
+    /// Shuffles run per statistic.
+    pub permutations: usize,
+    /// One entry per `Statistic::all()`.
+    pub permutation: Vec<PermutationResult>,
+    /// Chi-square independence of successive samples.
+    pub independence: ChiSquareResult,
+    /// Chi-square goodness-of-fit across ten sub-sequences.
+    pub goodness_of_fit: ChiSquareResult,
+}
+
+impl IidVerdict {
+    /// Whether every test passed.
+    pub fn is_iid(&self) -> bool {
+        self.permutation.iter().all(|r| r.passed) && self.independence.passed && self.goodness_of_fit.passed
+    }
+
+    /// The estimator track the source qualifies for.
+    pub fn track(&self) -> Track {
+        if self.is_iid() { Track::Iid } else { Track::NonIid }
+    }
+}
+
+/// Checks the sample width, as in `assess_non_iid`.
+fn validate(samples: &[u8], bits_per_symbol: u8) -> Result<(), EntropyError> {
+    if !(1..=8).contains(&bits_per_symbol) || samples.iter().any(|&s| (s as u16) >> bits_per_symbol != 0) {
+        return Err(EntropyError::InvalidSamples);
+    }
+    Ok(())
+}
+
+/// Runs the permutation tests with `permutations` shuffles drawn from `rng`, then both
+/// chi-square tests.
+///
+/// # Errors
+/// * `EntropyError::InvalidSamples` as for `assess_non_iid`.
+/// * `EntropyError::InsufficientSamples` with fewer than `MIN_SAMPLES` samples or no shuffles.
+/// * Any error of `rng`.
+pub fn iid_test<R: EntropySource + ?Sized>(
+    samples: &[u8],
+    bits_per_symbol: u8,
+    permutations: usize,
+    rng: &mut R
+) -> Result<IidVerdict, EntropyError> {
+    validate(samples, bits_per_symbol)?;
+    if samples.len() < MIN_SAMPLES || permutations == 0 {
+        return Err(EntropyError::InsufficientSamples);
+    }
+    let binary = bits_per_symbol == 1;
+    let k = 1usize << bits_per_symbol;
+
+    let fixed = Invariants::new(samples);
+    let original = statistics(samples, binary, &fixed);
+    let mut greater = vec![0usize; original.len()];
+    let mut equal = vec![0usize; original.len()];
+
+    let mut shuffled = samples.to_vec();
+    let mut draws = vec![0u8; 8 * samples.len()];
+    for _ in 0..permutations {
+        rng.fill(&mut draws)?;
+        // Fisher–Yates; the modulo bias of a 64-bit draw is negligible here.
+        for i in (1..shuffled.len()).rev() {
+            let r = u64::from_le_bytes(draws[8 * i..8 * i + 8].try_into().unwrap_or_default());
+            shuffled.swap(i, (r % (i as u64 + 1)) as usize);
+        }
+        for (j, t) in statistics(&shuffled, binary, &fixed).into_iter().enumerate() {
+            if t > original[j] {
+                greater[j] += 1;
+            } else if t == original[j] {
+                equal[j] += 1;
+            }
+        }
+    }
+
+    let tail = permutations / 2000;
+    let permutation = Statistic::all()
+        .into_iter()
+        .enumerate()
+        .map(|(j, statistic)| PermutationResult {
+            statistic,
+            value: original[j],
+            greater: greater[j],
+            equal: equal[j],
+            passed: greater[j] + equal[j] > tail && greater[j] < permutations - tail,
+        })
+        .collect();
+
+    let (independence, goodness_of_fit) = if binary {
+        (independence_binary(samples), goodness_of_fit_binary(samples))
+    } else {
+        (independence_symbols(samples, k), goodness_of_fit_symbols(samples, k))
+    };
+    Ok(IidVerdict { permutations, permutation, independence, goodness_of_fit })
+}
+
+/// Values every shuffle shares.
+struct Invariants {
+    mean: f64,
+    median: f64,
+}
+
+impl Invariants {
+    fn new(s: &[u8]) -> Self {
+        let mean = s.iter().map(|&x| x as f64).sum::<f64>() / s.len() as f64;
+        let mut sorted = s.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0
+        } else {
+            sorted[n / 2] as f64
+        };
+        Self { mean, median }
+    }
+}
+
+/// All statistics of `s`, in `Statistic::all()` order.
+fn statistics(s: &[u8], binary: bool, fixed: &Invariants) -> Vec<f64> {
+    // Conversion I (block weights) and II (block values) for binary samples.
+    let (weights, blocks);
+    let (directional, collisions) = if binary {
+        weights = s.chunks(8).map(|c| c.iter().sum::<u8>()).collect::<Vec<u8>>();
+        blocks = s.chunks(8).map(|c| (0..8).fold(0u8, |acc, i| (acc << 1) | c.get(i).copied().unwrap_or(0))).collect::<Vec<u8>>();
+        (&weights[..], &blocks[..])
+    } else {
+        (s, s)
+    };
+
+    let mut out = Vec::with_capacity(19);
+    out.push(excursion(s, fixed.mean));
+
+    let steps: Vec<bool> = directional.windows(2).map(|w| w[0] <= w[1]).collect();
+    let (count, longest) = runs(&steps);
+    let ups = steps.iter().filter(|&&up| up).count();
+    out.push(count as f64);
+    out.push(longest as f64);
+    out.push(ups.max(steps.len() - ups) as f64);
+
+    let above: Vec<bool> = s.iter().map(|&x| x as f64 >= fixed.median).collect();
+    let (count, longest) = runs(&above);
+    out.push(count as f64);
+    out.push(longest as f64);
+
+    let (average, maximum) = collision_times(collisions);
+    out.push(average);
+    out.push(maximum);
+
+    let lagged = |p: usize| directional.iter().zip(directional.get(p..).unwrap_or(&[]));
+    for &p in &LAGS {
+        out.push(lagged(p).filter(|(a, b)| a == b).count() as f64);
+    }
+    for &p in &LAGS {
+        out.push(lagged(p).map(|(&a, &b)| a as u64 * b as u64).sum::<u64>() as f64);
+    }
+    out.push(lz78_phrases(s) as f64);
+    out
+}
+
+fn excursion(s: &[u8], mean: f64) -> f64 {
+    let mut sum = 0.0;
+    let mut max = 0.0f64;
+    for (i, &x) in s.iter().enumerate() {
+        sum += x as f64;
+        max = max.max((sum - (i + 1) as f64 * mean).abs());
+    }
+    max
+}
+
+/// Number of runs and length of the longest run in `flags`.
+fn runs(flags: &[bool]) -> (usize, usize) {
+    let (mut count, mut longest, mut current) = (0usize, 0usize, 0usize);
+    for (i, &f) in flags.iter().enumerate() {
+        if i > 0 && flags[i - 1] == f {
+            current += 1;
+        } else {
+            count += 1;
+            current = 1;
+        }
+        longest = longest.max(current);
+    }
+    (count, longest)
+}
+
+/// Mean and maximum number of samples read until some value repeats, restarting after each
+/// repeat.
+fn collision_times(s: &[u8]) -> (f64, f64) {
+    let mut seen = [usize::MAX; 256];
+    let (mut start, mut count, mut total, mut max) = (0usize, 0usize, 0usize, 0usize);
+    for (j, &x) in s.iter().enumerate() {
+        if seen[x as usize] != usize::MAX && seen[x as usize] >= start {
+            let t = j - start + 1;
+            count += 1;
+            total += t;
+            max = max.max(t);
+            start = j + 1;
+        } else {
+            seen[x as usize] = j;
+        }
+    }
+    if count == 0 {
+        return (0.0, 0.0);
+    }
+    (total as f64 / count as f64, max as f64)
+}
+
+fn lz78_phrases(s: &[u8]) -> usize {
+    let mut dict: HashMap<(u32, u8), u32> = HashMap::with_capacity(s.len());
+    let mut node = 0u32;
+    let mut phrases = 0usize;
+    for &x in s {
+        match dict.get(&(node, x)) {
+            Some(&next) => node = next,
+            None => {
+                phrases += 1;
+                dict.insert((node, x), phrases as u32);
+                node = 0;
+            }
+        }
+    }
+    phrases + (node != 0) as usize
+}
+
+/// Chi-square critical value at significance 0.001 (Wilson–Hilferty).
+fn critical_value(df: usize) -> f64 {
+    let d = df as f64;
+    let h = 2.0 / (9.0 * d);
+    d * (1.0 - h + Z_999 * h.sqrt()).powi(3)
+}
+
+fn chi_square(statistic: f64, df: usize) -> ChiSquareResult {
+    if df == 0 {
+        return ChiSquareResult { statistic, df, critical: 0.0, passed: false };
+    }
+    let critical = critical_value(df);
+    ChiSquareResult { statistic, df, critical, passed: statistic <= critical }
+}
+
+/// Groups cells, smallest expectation first, into bins expecting at least 5 each; the last
+/// bin is merged into its predecessor if short. Returns each cell's bin and the bins'
+/// expectations.
+fn bins(expected: &[f64]) -> (Vec<usize>, Vec<f64>) {
+    let mut order: Vec<usize> = (0..expected.len()).filter(|&i| expected[i] > 0.0).collect();
+    order.sort_by(|&a, &b| expected[a].total_cmp(&expected[b]));
+    let mut bin_of = vec![usize::MAX; expected.len()];
+    let mut totals = vec![0.0f64];
+    for &i in &order {
+        if *totals.last().unwrap_or(&0.0) >= 5.0 {
+            totals.push(0.0);
+        }
+        let b = totals.len() - 1;
+        bin_of[i] = b;
+        totals[b] += expected[i];
+    }
+    if totals.len() > 1 && totals[totals.len() - 1] < 5.0 {
+        let last = totals.len() - 1;
+        totals[last - 1] += totals[last];
+        totals.pop();
+        for b in bin_of.iter_mut().filter(|b| **b == last) {
+            *b = last - 1;
+        }
+    }
+    (bin_of, totals)
+}
+
+fn independence_symbols(s: &[u8], k: usize) -> ChiSquareResult {
+    let mut counts = vec![0usize; k];
+    for &x in s {
+        counts[x as usize] += 1;
+    }
+    let pairs = (s.len() - 1) as f64;
+    let p: Vec<f64> = counts.iter().map(|&c| c as f64 / s.len() as f64).collect();
+    let expected: Vec<f64> = (0..k * k).map(|ij| p[ij / k] * p[ij % k] * pairs).collect();
+    let (bin_of, totals) = bins(&expected);
+
+    let mut observed = vec![0.0f64; totals.len()];
+    for w in s.windows(2) {
+        observed[bin_of[w[0] as usize * k + w[1] as usize]] += 1.0;
+    }
+    let statistic = observed.iter().zip(&totals).map(|(o, e)| (o - e) * (o - e) / e).sum();
+    chi_square(statistic, totals.len().saturating_sub(1))
+}
+
+fn independence_binary(s: &[u8]) -> ChiSquareResult {
+    let ones = s.iter().filter(|&&b| b == 1).count() as f64;
+    let p1 = ones / s.len() as f64;
+    let p0 = 1.0 - p1;
+    let rarest = p0.min(p1);
+    // Longest tuple whose least likely value still expects 5 occurrences.
+    let m = (1..=11usize).rev().find(|&m| rarest.powi(m as i32) * (s.len() / m) as f64 >= 5.0).unwrap_or(0);
+    if m < 2 {
+        return chi_square(f64::INFINITY, 0);
+    }
+
+    let tuples = s.len() / m;
+    let mut observed = vec![0.0f64; 1 << m];
+    for c in s.chunks_exact(m) {
+        observed[c.iter().fold(0usize, |acc, &b| (acc << 1) | b as usize)] += 1.0;
+    }
+    let statistic = observed
+        .iter()
+        .enumerate()
+        .map(|(v, o)| {
+            let w = v.count_ones() as i32;
+            let e = p1.powi(w) * p0.powi(m as i32 - w) * tuples as f64;
+            (o - e) * (o - e) / e
+        })
+        .sum();
+    chi_square(statistic, (1 << m) - 2)
+}
+
+fn goodness_of_fit_symbols(s: &[u8], k: usize) -> ChiSquareResult {
+    let mut counts = vec![0usize; k];
+    for &x in s {
+        counts[x as usize] += 1;
+    }
+    let expected: Vec<f64> = counts.iter().map(|&c| c as f64 / 10.0).collect();
+    let (bin_of, totals) = bins(&expected);
+
+    let mut statistic = 0.0;
+    for part in s.chunks_exact(s.len() / 10).take(10) {
+        let mut observed = vec![0.0f64; totals.len()];
+        for &x in part {
+            observed[bin_of[x as usize]] += 1.0;
+        }
+        statistic += observed.iter().zip(&totals).map(|(o, e)| (o - e) * (o - e) / e).sum::<f64>();
+    }
+    chi_square(statistic, 9 * totals.len().saturating_sub(1))
+}
+
+fn goodness_of_fit_binary(s: &[u8]) -> ChiSquareResult {
+    let n = s.len() / 10;
+    let p = s.iter().filter(|&&b| b == 1).count() as f64 / s.len() as f64;
+    let (e1, e0) = (p * n as f64, (1.0 - p) * n as f64);
+    if e1 == 0.0 || e0 == 0.0 {
+        return chi_square(f64::INFINITY, 0);
+    }
+    let statistic = s
+        .chunks_exact(n)
+        .take(10)
+        .map(|part| {
+            let o1 = part.iter().filter(|&&b| b == 1).count() as f64;
+            let o0 = n as f64 - o1;
+            (o1 - e1) * (o1 - e1) / e1 + (o0 - e0) * (o0 - e0) / e0
+        })
+        .sum();
+    chi_square(statistic, 9)
+}
+
+/// Result of `restart_test`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestartVerdict {
+    /// Count of the most common value in any single row (restart).
+    pub max_row_frequency: usize,
+    /// Count of the most common value in any single column (sample index).
+    pub max_column_frequency: usize,
+    /// Frequency at which the sanity check fails.
+    pub cutoff: usize,
+    /// Whether every row and column stayed below `cutoff`.
+    pub sanity_passed: bool,
+    /// Track estimate on the rows, concatenated.
+    pub h_row: f64,
+    /// Track estimate on the columns, concatenated.
+    pub h_column: f64,
+    /// `min(H_I, h_row, h_column)`: the validated estimate per sample.
+    pub min_entropy: f64,
+    /// Sanity check passed and `min(h_row, h_column) >= H_I / 2`.
+    pub passed: bool,
+}
+
+/// Validates `h_initial` on a restart matrix: row `i` holds the first `RESTARTS` samples
+/// after restart `i`, rows concatenated.
+///
+/// # Errors
+/// * `EntropyError::InvalidSamples` as for `assess_non_iid`.
+/// * `EntropyError::InsufficientSamples` unless the matrix is `RESTARTS × RESTARTS`.
+pub fn restart_test(matrix: &[u8], bits_per_symbol: u8, h_initial: f64, track: Track) -> Result<RestartVerdict, EntropyError> {
+    validate(matrix, bits_per_symbol)?;
+    if matrix.len() != RESTARTS * RESTARTS {
+        return Err(EntropyError::InsufficientSamples);
+    }
+    let k = 1usize << bits_per_symbol;
+    let columns: Vec<u8> = (0..RESTARTS).flat_map(|c| (0..RESTARTS).map(move |r| matrix[r * RESTARTS + c])).collect();
+
+    let max_frequency = |data: &[u8]| -> usize {
+        data.chunks_exact(RESTARTS)
+            .map(|line| {
+                let mut counts = vec![0usize; k];
+                line.iter().for_each(|&x| counts[x as usize] += 1);
+                counts.into_iter().max().unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0)
+    };
+    let max_row_frequency = max_frequency(matrix);
+    let max_column_frequency = max_frequency(&columns);
+    let cutoff = binomial_cutoff(RESTARTS, 2f64.powf(-h_initial), RESTART_ALPHA);
+    let sanity_passed = max_row_frequency < cutoff && max_column_frequency < cutoff;
+
+    let h_row = track.min_entropy(matrix, bits_per_symbol)?;
+    let h_column = track.min_entropy(&columns, bits_per_symbol)?;
+    let min_entropy = h_initial.min(h_row).min(h_column);
+    let passed = sanity_passed && h_row.min(h_column) >= h_initial / 2.0;
+    Ok(RestartVerdict { max_row_frequency, max_column_frequency, cutoff, sanity_passed, h_row, h_column, min_entropy, passed })
+}
+
+/// Smallest `u` with `P(Binomial(n, p) >= u) <= alpha`.
+fn binomial_cutoff(n: usize, p: f64, alpha: f64) -> usize {
+    if p >= 1.0 {
+        return n + 1;
+    }
+    // Walk the upper tail down from `n` in log space.
+    let ln_pmf = |x: usize| -> f64 {
+        let ln_choose: f64 = (0..x).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum();
+        ln_choose + x as f64 * p.ln() + (n - x) as f64 * (1.0 - p).ln()
+    };
+    let mut tail = 0.0;
+    for u in (0..=n).rev() {
+        tail += ln_pmf(u).exp();
+        if tail > alpha {
+            return u + 1;
+        }
+    }
+    0
+}
+
+impl Track {
+    /// Min-entropy per sample of `samples` on this track: the most common value estimate
+    /// for IID sources, the full non-IID suite otherwise.
+    ///
+    /// # Errors
+    /// As `assess_non_iid`.
+    pub fn min_entropy(self, samples: &[u8], bits_per_symbol: u8) -> Result<f64, EntropyError> {
+        match self {
+            Track::Iid => {
+                validate(samples, bits_per_symbol)?;
+                if samples.len() < MIN_SAMPLES {
+                    return Err(EntropyError::InsufficientSamples);
+                }
+                Ok(most_common_value(samples, 1 << bits_per_symbol))
+            }
+            Track::NonIid => super::assess_non_iid(samples, bits_per_symbol).map(|a| a.min_entropy),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SplitMix64 byte stream for samples and shuffles.
+    struct MockEntropy {
+        state: u64,
+    }
+
+    impl MockEntropy {
+        fn bytes(&mut self, n: usize) -> Vec<u8> {
+            let mut v = vec![0u8; n];
+            self.fill(&mut v).unwrap();
+            v
+        }
+    }
+
+    impl EntropySource for MockEntropy {
+        fn name(&self) -> &'static str { "Mock" }
+        fn fill(&mut self, dest: &mut [u8]) -> Result<(), EntropyError> {
+            for chunk in dest.chunks_mut(8) {
+                self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let mut z = self.state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                z ^= z >> 31;
+                chunk.copy_from_slice(&z.to_le_bytes()[..chunk.len()]);
+            }
+            Ok(())
+        }
+        fn entropy_estimate(&self) -> f64 { 8.0 }
+    }
+
+    #[test]
+    fn test_statistics_on_known_sequence() {
+        let s = [2u8, 5, 5, 1, 3, 5, 0];
+        let fixed = Invariants::new(&s);
+        assert_eq!(fixed.median, 3.0);
+        let t = statistics(&s, false, &fixed);
+        // Steps: up, up, down, up, up, down -> 4 runs, longest 2, 4 increases.
+        assert_eq!(&t[1..4], &[4.0, 2.0, 4.0]);
+        // Above median: F T T F T T F -> 5 runs, longest 2.
+        assert_eq!(&t[4..6], &[5.0, 2.0]);
+        // Collisions: [2, 5, 5] then [1, 3, 5, 0] has none.
+        assert_eq!(&t[6..8], &[3.0, 3.0]);
+        // Lag 1: one repeat; covariance 10 + 25 + 5 + 3 + 15 + 0.
+        assert_eq!(t[8], 1.0);
+        assert_eq!(t[13], 58.0);
+        assert_eq!(lz78_phrases(&[1, 1, 1, 1, 1, 1]), 3);
+    }
+
+    #[test]
+    fn test_random_bytes_are_iid() {
+        let mut rng = MockEntropy { state: 11 };
+        let samples = rng.bytes(MIN_SAMPLES);
+        let verdict = iid_test(&samples, 8, 1000, &mut rng).unwrap();
+        assert_eq!(verdict.permutation.len(), 19);
+        assert!(verdict.independence.passed, "{:?}", verdict.independence);
+        assert!(verdict.goodness_of_fit.passed, "{:?}", verdict.goodness_of_fit);
+        assert!(verdict.is_iid(), "{:?}", verdict.permutation.iter().filter(|r| !r.passed).collect::<Vec<_>>());
+        assert_eq!(verdict.track(), Track::Iid);
+    }
+
+    #[test]
+    fn test_correlated_source_is_not_iid() {
+        // A random walk: each sample close to the previous one.
+        let mut rng = MockEntropy { state: 12 };
+        let steps = rng.bytes(MIN_SAMPLES);
+        let mut x = 128u8;
+        let walk: Vec<u8> = steps.iter().map(|&d| { x = x.wrapping_add(d % 5).wrapping_sub(2); x }).collect();
+        let verdict = iid_test(&walk, 8, 1000, &mut rng).unwrap();
+        assert!(!verdict.independence.passed);
+        let failed: Vec<Statistic> = verdict.permutation.iter().filter(|r| !r.passed).map(|r| r.statistic).collect();
+        assert!(failed.contains(&Statistic::Covariance(1)), "{:?}", failed);
+        assert_eq!(verdict.track(), Track::NonIid);
+
+        // Sticky bits: each bit repeats the previous one three times in four.
+        let flips = rng.bytes(MIN_SAMPLES);
+        let mut bit = 0u8;
+        let sticky: Vec<u8> = flips.iter().map(|&r| { bit ^= (r & 3 == 0) as u8; bit }).collect();
+        let verdict = iid_test(&sticky, 1, 1000, &mut rng).unwrap();
+        assert!(!verdict.independence.passed);
+        assert!(!verdict.is_iid());
+    }
+
+    #[test]
+    fn test_restart_matrix() {
+        let mut rng = MockEntropy { state: 13 };
+        let matrix = rng.bytes(RESTARTS * RESTARTS);
+        let verdict = restart_test(&matrix, 8, 7.0, Track::Iid).unwrap();
+        assert!(verdict.sanity_passed && verdict.passed, "{:?}", verdict);
+        assert_eq!(verdict.min_entropy, 7.0);
+
+        // Every restart replays the same output: columns are constant.
+        let row = rng.bytes(RESTARTS);
+        let replayed: Vec<u8> = (0..RESTARTS).flat_map(|_| row.iter().copied()).collect();
+        let verdict = restart_test(&replayed, 8, 7.0, Track::Iid).unwrap();
+        assert_eq!(verdict.max_column_frequency, RESTARTS);
+        // Rows and columns hold the same values; only the sanity check sees the replay.
+        assert_eq!(verdict.h_column, verdict.h_row);
+        assert!(!verdict.sanity_passed && !verdict.passed);
+
+        assert_eq!(restart_test(&row, 8, 7.0, Track::Iid).map(|_| ()), Err(EntropyError::InsufficientSamples));
+    }
+
+    #[test]
+    fn test_binomial_cutoff() {
+        // Fair coin over 1000 flips: P(X >= 570) ≈ 4.4e-6.
+        let u = binomial_cutoff(1000, 0.5, RESTART_ALPHA);
+        assert!((565..=575).contains(&u), "{}", u);
+        assert_eq!(binomial_cutoff(1000, 1.0, RESTART_ALPHA), 1001);
+    }
+}
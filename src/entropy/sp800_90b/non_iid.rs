@@ -0,0 +1,784 @@
+//! SP 800-90B non-IID min-entropy assessment (Section 6.3).
+//!
+//! `assess_non_iid` takes a raw sample buffer from a noise source and runs the ten
+//! non-IID estimators. Samples narrower than a byte carry one symbol each in their low
+//! `bits_per_symbol` bits.
+//!
+//! # Method
+//! - `H_original`: minimum over the estimators run on the samples as given. Collision, Markov
+//!   and compression only apply to binary samples and are skipped otherwise.
+//! - `H_bitstring`: for non-binary samples, minimum over all ten estimators run on the
+//!   samples' bits (most significant first), capped at `MAX_BITSTRING` bits.
+//! - Final estimate: `min(H_original, bits_per_symbol * H_bitstring)` per sample.
+//!
+//! Every estimator uses the 99% upper confidence bound on the probability it measures, so
+//! estimates are conservative. A certified assessment needs `FULL_ASSESSMENT_SAMPLES`
+//! samples; `assess_non_iid` accepts down to `MIN_SAMPLES` for quick checks.
+//!
+//! # Estimators
+//! Most common value, collision, Markov, compression, t-tuple, longest repeated substring
+//! (the last two share one suffix array), and the MultiMCW, lag, MultiMMC and LZ78Y
+//! predictors. Where a predictor has several symbols with the top count, ties go to the
+//! most recent symbol (MultiMCW) or to the larger symbol (MultiMMC, LZ78Y).
+
+extern crate alloc;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+use crate::entropy::EntropyError;
+
+/// Samples needed for a full SP 800-90B assessment.
+pub const FULL_ASSESSMENT_SAMPLES: usize = 1_000_000;
+
+/// Fewest samples `assess_non_iid` accepts.
+pub const MIN_SAMPLES: usize = 8192;
+
+/// Longest bitstring examined for `H_bitstring`, bounding the cost of wide samples.
+pub const MAX_BITSTRING: usize = 1_000_000;
+
+/// Bootstrap rule: abort and burn below this many bits of min-entropy per byte.
+pub const BOOTSTRAP_MIN_ENTROPY: f64 = 0.8;
+
+/// Two-sided 99% normal quantile used for every confidence bound.
+const Z_ALPHA: f64 = 2.576;
+
+/// Occurrence count a tuple needs for the t-tuple estimate.
+const TUPLE_CUTOFF: usize = 35;
+
+/// The ten non-IID estimators, in SP 800-90B order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Estimator {
+    /// Most common value (6.3.1).
+    MostCommonValue,
+    /// Mean time to the first repeated sample (6.3.2, binary only).
+    Collision,
+    /// Most likely 128-bit sequence under a first-order Markov model (6.3.3, binary only).
+    Markov,
+    /// Maurer-style compression of 6-bit blocks (6.3.4, binary only).
+    Compression,
+    /// Frequency of the most common tuples (6.3.5).
+    TTuple,
+    /// Collision rate of repeated substrings (6.3.6).
+    LongestRepeatedSubstring,
+    /// Most common value in four sliding windows (6.3.7).
+    MultiMcw,
+    /// Repeats at 128 lags (6.3.8).
+    Lag,
+    /// Markov models of order 1..=16 (6.3.9).
+    MultiMmc,
+    /// LZ78-style dictionary of contexts up to 16 symbols (6.3.10).
+    Lz78y,
+}
+
+impl Estimator {
+    /// All estimators, in SP 800-90B order.
+    pub const ALL: [Estimator; 10] = [
+        Estimator::MostCommonValue,
+        Estimator::Collision,
+        Estimator::Markov,
+        Estimator::Compression,
+        Estimator::TTuple,
+        Estimator::LongestRepeatedSubstring,
+        Estimator::MultiMcw,
+        Estimator::Lag,
+        Estimator::MultiMmc,
+        Estimator::Lz78y,
+    ];
+
+    /// Short name used in reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Estimator::MostCommonValue => "MCV",
+            Estimator::Collision => "Collision",
+            Estimator::Markov => "Markov",
+            Estimator::Compression => "Compression",
+            Estimator::TTuple => "t-Tuple",
+            Estimator::LongestRepeatedSubstring => "LRS",
+            Estimator::MultiMcw => "MultiMCW",
+            Estimator::Lag => "Lag",
+            Estimator::MultiMmc => "MultiMMC",
+            Estimator::Lz78y => "LZ78Y",
+        }
+    }
+
+    /// Whether the estimator only applies to binary samples.
+    pub fn binary_only(self) -> bool {
+        matches!(self, Estimator::Collision | Estimator::Markov | Estimator::Compression)
+    }
+
+    /// Min-entropy per symbol of `s` over an alphabet of `k` symbols, or `None` when the data
+    /// gives the estimator nothing to measure (no tuple reaches the t-tuple cutoff, or no
+    /// substring longer than that repeats).
+    fn estimate(self, s: &[u8], k: usize, tuples: &TupleStats) -> Option<f64> {
+        match self {
+            Estimator::MostCommonValue => Some(most_common_value(s, k)),
+            Estimator::Collision => Some(collision(s)),
+            Estimator::Markov => Some(markov(s)),
+            Estimator::Compression => Some(compression(s)),
+            Estimator::TTuple => tuples.t_tuple(s.len()),
+            Estimator::LongestRepeatedSubstring => tuples.lrs(s.len()),
+            Estimator::MultiMcw => Some(multi_mcw(s, k).min_entropy(k)),
+            Estimator::Lag => Some(lag(s).min_entropy(k)),
+            Estimator::MultiMmc => Some(multi_mmc(s).min_entropy(k)),
+            Estimator::Lz78y => Some(lz78y(s).min_entropy(k)),
+        }
+    }
+}
+
+/// Result of `assess_non_iid`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonIidAssessment {
+    /// Width of one sample in bits.
+    pub bits_per_symbol: u8,
+    /// Per-sample estimates on the samples as given (inapplicable estimators omitted).
+    pub original: Vec<(Estimator, f64)>,
+    /// Per-bit estimates on the bitstring view; empty for binary samples.
+    pub bitstring: Vec<(Estimator, f64)>,
+    /// Minimum of `original`.
+    pub h_original: f64,
+    /// Minimum of `bitstring`, for non-binary samples.
+    pub h_bitstring: Option<f64>,
+    /// Final min-entropy per sample.
+    pub min_entropy: f64,
+}
+
+impl NonIidAssessment {
+    /// Final min-entropy per byte of sample output.
+    pub fn per_byte(&self) -> f64 {
+        self.min_entropy * 8.0 / self.bits_per_symbol as f64
+    }
+
+    /// Fails with `EntropyError::HealthTestFailed` below `min` bits per byte.
+    pub fn require(&self, min: f64) -> Result<(), EntropyError> {
+        if self.per_byte() >= min { Ok(()) } else { Err(EntropyError::HealthTestFailed) }
+    }
+}
+
+/// Runs the non-IID track over `samples`.
+///
+/// # Errors
+/// * `EntropyError::InvalidSamples` if `bits_per_symbol` is not in `1..=8` or a sample does
+///   not fit in it.
+/// * `EntropyError::InsufficientSamples` with fewer than `MIN_SAMPLES` samples.
+pub fn assess_non_iid(samples: &[u8], bits_per_symbol: u8) -> Result<NonIidAssessment, EntropyError> {
+    if !(1..=8).contains(&bits_per_symbol) || samples.iter().any(|&s| (s as u16) >> bits_per_symbol != 0) {
+        return Err(EntropyError::InvalidSamples);
+    }
+    if samples.len() < MIN_SAMPLES {
+        return Err(EntropyError::InsufficientSamples);
+    }
+
+    let binary = bits_per_symbol == 1;
+    let original = run_estimators(samples, 1 << bits_per_symbol, !binary);
+    let h_original = minimum(&original);
+
+    let (bitstring, h_bitstring) = if binary {
+        (Vec::new(), None)
+    } else {
+        let bits: Vec<u8> = samples
+            .iter()
+            .flat_map(|&s| (0..bits_per_symbol).rev().map(move |i| (s >> i) & 1))
+            .take(MAX_BITSTRING)
+            .collect();
+        let estimates = run_estimators(&bits, 2, false);
+        let h = minimum(&estimates);
+        (estimates, Some(h))
+    };
+
+    let min_entropy = match h_bitstring {
+        Some(h) => h_original.min(bits_per_symbol as f64 * h),
+        None => h_original,
+    };
+    Ok(NonIidAssessment { bits_per_symbol, original, bitstring, h_original, h_bitstring, min_entropy })
+}
+
+fn run_estimators(s: &[u8], k: usize, skip_binary_only: bool) -> Vec<(Estimator, f64)> {
+    let tuples = TupleStats::new(s);
+    Estimator::ALL
+        .iter()
+        .filter(|e| !(skip_binary_only && e.binary_only()))
+        .filter_map(|&e| e.estimate(s, k, &tuples).map(|h| (e, h)))
+        .collect()
+}
+
+fn minimum(estimates: &[(Estimator, f64)]) -> f64 {
+    estimates.iter().map(|&(_, h)| h).fold(f64::INFINITY, f64::min)
+}
+
+/// Upper 99% bound on a proportion `p` observed over `n` trials.
+fn upper_bound(p: f64, n: usize) -> f64 {
+    (p + Z_ALPHA * (p * (1.0 - p) / (n as f64 - 1.0)).sqrt()).min(1.0)
+}
+
+pub(super) fn most_common_value(s: &[u8], k: usize) -> f64 {
+    let mut counts = vec![0usize; k];
+    for &x in s {
+        counts[x as usize] += 1;
+    }
+    let max = counts.iter().copied().max().unwrap_or(0);
+    -upper_bound(max as f64 / s.len() as f64, s.len()).log2()
+}
+
+fn collision(s: &[u8]) -> f64 {
+    // With two symbols every collision happens within three samples.
+    let (mut i, mut v, mut sum, mut sum_sq) = (0usize, 0usize, 0.0f64, 0.0f64);
+    while i + 1 < s.len() {
+        let t = if s[i] == s[i + 1] {
+            2
+        } else if i + 2 < s.len() {
+            3
+        } else {
+            break;
+        };
+        v += 1;
+        sum += t as f64;
+        sum_sq += (t * t) as f64;
+        i += t;
+    }
+    let mean = sum / v as f64;
+    let sigma = ((sum_sq - v as f64 * mean * mean) / (v as f64 - 1.0)).max(0.0).sqrt();
+    let lower = mean - Z_ALPHA * sigma / (v as f64).sqrt();
+
+    // The expected collision time is 2 + 2p(1 - p); solve for p >= 1/2.
+    let pq = (lower - 2.0) / 2.0;
+    if pq >= 0.25 {
+        1.0
+    } else if pq <= 0.0 {
+        0.0
+    } else {
+        -((1.0 + (1.0 - 4.0 * pq).sqrt()) / 2.0).log2()
+    }
+}
+
+fn markov(s: &[u8]) -> f64 {
+    let ones = s.iter().filter(|&&b| b == 1).count() as f64;
+    let p1 = ones / s.len() as f64;
+    let p0 = 1.0 - p1;
+    let mut t = [[0.0f64; 2]; 2];
+    for w in s.windows(2) {
+        t[w[0] as usize][w[1] as usize] += 1.0;
+    }
+    let row = |a: usize, b: usize| {
+        let total = t[a][0] + t[a][1];
+        if total > 0.0 { t[a][b] / total } else { 0.0 }
+    };
+    let (p00, p01, p10, p11) = (row(0, 0), row(0, 1), row(1, 0), row(1, 1));
+
+    // log2 probabilities of the most likely 128-bit sequences.
+    let lg = |p: f64| p.log2();
+    let candidates = [
+        lg(p0) + 127.0 * lg(p00),
+        lg(p0) + 64.0 * lg(p01) + 63.0 * lg(p10),
+        lg(p0) + lg(p01) + 126.0 * lg(p11),
+        lg(p1) + lg(p10) + 126.0 * lg(p00),
+        lg(p1) + 64.0 * lg(p10) + 63.0 * lg(p01),
+        lg(p1) + 127.0 * lg(p11),
+    ];
+    let best = candidates.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (-best / 128.0).min(1.0)
+}
+
+fn compression(s: &[u8]) -> f64 {
+    const B: usize = 6;
+    const D: usize = 1000;
+    const C: f64 = 0.5907;
+
+    let blocks: Vec<usize> = s
+        .chunks_exact(B)
+        .map(|c| c.iter().fold(0usize, |acc, &bit| (acc << 1) | bit as usize))
+        .collect();
+    let total = blocks.len();
+    let nu = total - D;
+
+    // Positions are 1-based; zero marks a block value not seen yet.
+    let mut dict = [0usize; 1 << B];
+    for (i, &x) in blocks[..D].iter().enumerate() {
+        dict[x] = i + 1;
+    }
+    let (mut sum, mut sum_sq) = (0.0f64, 0.0f64);
+    for (i, &x) in blocks.iter().enumerate().skip(D) {
+        let pos = i + 1;
+        let dist = if dict[x] != 0 { pos - dict[x] } else { pos };
+        dict[x] = pos;
+        let l = (dist as f64).log2();
+        sum += l;
+        sum_sq += l * l;
+    }
+    let mean = sum / nu as f64;
+    let sigma = C * (sum_sq / (nu as f64 - 1.0) - mean * mean).max(0.0).sqrt();
+    let lower = mean - Z_ALPHA * sigma / (nu as f64).sqrt();
+
+    // Expected mean of log2 distances when one block value has probability p and the
+    // others share the rest evenly.
+    let g = |z: f64| -> f64 {
+        let mut acc = 0.0;
+        let mut pow = 1.0; // (1 - z)^(u - 1)
+        for u in 1..=total {
+            let lu = (u as f64).log2();
+            if u < total {
+                acc += lu * z * z * pow * (total - u.max(D)) as f64;
+            }
+            if u > D {
+                acc += lu * z * pow;
+            }
+            pow *= 1.0 - z;
+        }
+        acc / nu as f64
+    };
+    let others = ((1 << B) - 1) as f64;
+    let expected = |p: f64| g(p) + others * g((1.0 - p) / others);
+
+    let (mut lo, mut hi) = (1.0 / (1 << B) as f64, 1.0f64);
+    if lower >= expected(lo) {
+        return 1.0;
+    }
+    for _ in 0..48 {
+        let mid = (lo + hi) / 2.0;
+        if expected(mid) > lower { lo = mid } else { hi = mid }
+    }
+    (-((lo + hi) / 2.0).log2() / B as f64).min(1.0)
+}
+
+/// Tuple statistics for all lengths, from the suffix array's LCP intervals.
+struct TupleStats {
+    /// `max_count[w]`: occurrences of the most common `w`-tuple.
+    max_count: Vec<usize>,
+    /// `pairs[w]`: number of pairs of equal `w`-tuples.
+    pairs: Vec<f64>,
+}
+
+impl TupleStats {
+    fn new(s: &[u8]) -> Self {
+        let sa = suffix_array(s);
+        let lcp = lcp_array(s, &sa);
+        let longest = lcp.iter().copied().max().unwrap_or(0);
+
+        // Every LCP interval of depth `l` with `c` suffixes (and parent depth `p`) is a
+        // group of `c` equal `w`-tuples for each `w` in `p + 1..=l`.
+        let mut best = vec![1usize; longest + 2];
+        let mut diff = vec![0.0f64; longest + 2];
+        let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+        for i in 1..=s.len() {
+            let cur = lcp.get(i).copied().unwrap_or(0);
+            let mut lb = i - 1;
+            while let Some(&(depth, start)) = stack.last() {
+                if cur >= depth {
+                    break;
+                }
+                stack.pop();
+                let parent = cur.max(stack.last().map_or(0, |t| t.0));
+                let c = i - start;
+                best[depth] = best[depth].max(c);
+                let pairs = (c * (c - 1) / 2) as f64;
+                diff[parent + 1] += pairs;
+                diff[depth + 1] -= pairs;
+                lb = start;
+            }
+            if stack.last().map_or(true, |t| cur > t.0) {
+                stack.push((cur, lb));
+            }
+        }
+
+        // A group at depth `l` is contained in one at every shallower depth.
+        for w in (1..=longest).rev() {
+            best[w] = best[w].max(best[w + 1]);
+        }
+        let mut pairs = vec![0.0f64; longest + 2];
+        let mut run = 0.0;
+        for w in 1..=longest + 1 {
+            run += diff[w];
+            pairs[w] = run;
+        }
+        Self { max_count: best, pairs }
+    }
+
+    fn longest_repeat(&self) -> usize {
+        self.max_count.len() - 2
+    }
+
+    /// Largest `t` whose most common `t`-tuple reaches the cutoff.
+    fn cutoff_len(&self) -> usize {
+        (1..=self.longest_repeat()).take_while(|&w| self.max_count[w] >= TUPLE_CUTOFF).last().unwrap_or(0)
+    }
+
+    fn t_tuple(&self, n: usize) -> Option<f64> {
+        let t = self.cutoff_len();
+        if t == 0 {
+            return None;
+        }
+        let p = (1..=t)
+            .map(|w| (self.max_count[w] as f64 / (n - w + 1) as f64).powf(1.0 / w as f64))
+            .fold(0.0, f64::max);
+        Some(-upper_bound(p, n).log2())
+    }
+
+    fn lrs(&self, n: usize) -> Option<f64> {
+        let (u, v) = (self.cutoff_len() + 1, self.longest_repeat());
+        if u > v {
+            return None;
+        }
+        let p = (u..=v)
+            .map(|w| {
+                let m = (n - w + 1) as f64;
+                (self.pairs[w] / (m * (m - 1.0) / 2.0)).powf(1.0 / w as f64)
+            })
+            .fold(0.0, f64::max);
+        Some(-upper_bound(p, n).log2())
+    }
+}
+
+/// Suffix array by prefix doubling.
+fn suffix_array(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = s.iter().map(|&b| b as usize).collect();
+    let mut keys = vec![0u64; n];
+    let mut next = vec![0usize; n];
+    let mut step = 1;
+    loop {
+        for i in 0..n {
+            let second = if i + step < n { rank[i + step] as u64 + 1 } else { 0 };
+            keys[i] = ((rank[i] as u64) << 32) | second;
+        }
+        sa.sort_unstable_by_key(|&i| keys[i]);
+        next[sa[0]] = 0;
+        for j in 1..n {
+            next[sa[j]] = next[sa[j - 1]] + (keys[sa[j - 1]] != keys[sa[j]]) as usize;
+        }
+        core::mem::swap(&mut rank, &mut next);
+        if rank[sa[n - 1]] == n - 1 || step >= n {
+            return sa;
+        }
+        step *= 2;
+    }
+}
+
+/// Kasai's algorithm: `lcp[i]` is the common prefix of suffixes `sa[i - 1]` and `sa[i]`.
+fn lcp_array(s: &[u8], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0usize; n];
+    for (i, &p) in sa.iter().enumerate() {
+        rank[p] = i;
+    }
+    let mut lcp = vec![0usize; n];
+    let mut h = 0usize;
+    for p in 0..n {
+        if rank[p] > 0 {
+            let q = sa[rank[p] - 1];
+            while p + h < n && q + h < n && s[p + h] == s[q + h] {
+                h += 1;
+            }
+            lcp[rank[p]] = h;
+            h = h.saturating_sub(1);
+        } else {
+            h = 0;
+        }
+    }
+    lcp
+}
+
+/// Outcome of a prediction estimator: hits, trials and the longest run of hits.
+struct Predictions {
+    correct: usize,
+    total: usize,
+    longest_run: usize,
+    run: usize,
+}
+
+impl Predictions {
+    fn new() -> Self {
+        Self { correct: 0, total: 0, longest_run: 0, run: 0 }
+    }
+
+    fn record(&mut self, hit: bool) {
+        self.total += 1;
+        if hit {
+            self.correct += 1;
+            self.run += 1;
+            self.longest_run = self.longest_run.max(self.run);
+        } else {
+            self.run = 0;
+        }
+    }
+
+    /// `-log2(max(P'_global, P_local, 1/k))`.
+    fn min_entropy(&self, k: usize) -> f64 {
+        let n = self.total as f64;
+        let global = if self.correct == 0 {
+            1.0 - 0.01f64.powf(1.0 / n)
+        } else {
+            upper_bound(self.correct as f64 / n, self.total)
+        };
+        let local = local_probability(self.total, self.longest_run + 1);
+        -global.max(local).max(1.0 / k as f64).log2()
+    }
+}
+
+/// Success probability at which a run of `r` hits in `n` trials has probability 1%.
+fn local_probability(n: usize, r: usize) -> f64 {
+    // Probability of no run of `r` successes in `n` trials.
+    let no_run = |p: f64| -> f64 {
+        let q = 1.0 - p;
+        let mut x = 1.0f64;
+        for _ in 0..10 {
+            x = 1.0 + q * p.powi(r as i32) * x.powi(r as i32 + 1);
+        }
+        (1.0 - p * x) / ((r as f64 + 1.0 - r as f64 * x) * q) / x.powf(n as f64 + 1.0)
+    };
+    let (mut lo, mut hi) = (0.0f64, 1.0f64);
+    for _ in 0..48 {
+        let mid = (lo + hi) / 2.0;
+        if no_run(mid) > 0.99 { lo = mid } else { hi = mid }
+    }
+    lo
+}
+
+/// Picks a winning sub-predictor: the latest to match or pass the current winner's score.
+struct Scoreboard {
+    scores: Vec<usize>,
+    winner: usize,
+}
+
+impl Scoreboard {
+    fn new(len: usize) -> Self {
+        Self { scores: vec![0; len], winner: 0 }
+    }
+
+    fn update(&mut self, predictions: &[Option<u8>], actual: u8) {
+        for (j, &p) in predictions.iter().enumerate() {
+            if p == Some(actual) {
+                self.scores[j] += 1;
+                if self.scores[j] >= self.scores[self.winner] {
+                    self.winner = j;
+                }
+            }
+        }
+    }
+}
+
+fn multi_mcw(s: &[u8], k: usize) -> Predictions {
+    const WINDOWS: [usize; 4] = [63, 255, 1023, 4095];
+    let mut counts = vec![vec![0usize; k]; WINDOWS.len()];
+    let mut last_seen = vec![0usize; k];
+    let mut board = Scoreboard::new(WINDOWS.len());
+    let mut out = Predictions::new();
+    let mut frequent = [None; WINDOWS.len()];
+
+    for (i, &x) in s.iter().enumerate() {
+        if i >= WINDOWS[0] {
+            for (j, &w) in WINDOWS.iter().enumerate() {
+                frequent[j] = (i >= w).then(|| {
+                    let c = &counts[j];
+                    // Most common symbol in the window; ties go to the most recent.
+                    (0..k).max_by_key(|&y| (c[y], last_seen[y])).unwrap_or(0) as u8
+                });
+            }
+            out.record(frequent[board.winner] == Some(x));
+            board.update(&frequent, x);
+        }
+        for (j, &w) in WINDOWS.iter().enumerate() {
+            counts[j][x as usize] += 1;
+            if i >= w {
+                counts[j][s[i - w] as usize] -= 1;
+            }
+        }
+        last_seen[x as usize] = i;
+    }
+    out
+}
+
+fn lag(s: &[u8]) -> Predictions {
+    const D: usize = 128;
+    let mut board = Scoreboard::new(D);
+    let mut out = Predictions::new();
+    let mut lags = [None; D];
+    for i in 1..s.len() {
+        for (d, slot) in lags.iter_mut().enumerate() {
+            *slot = (d < i).then(|| s[i - d - 1]);
+        }
+        out.record(lags[board.winner] == Some(s[i]));
+        board.update(&lags, s[i]);
+    }
+    out
+}
+
+/// Follower counts per context, tracking each context's most frequent follower.
+struct ContextCounts {
+    followers: HashMap<(u128, u8), u32>,
+    best: HashMap<u128, (u8, u32)>,
+}
+
+impl ContextCounts {
+    fn new() -> Self {
+        Self { followers: HashMap::new(), best: HashMap::new() }
+    }
+
+    fn increment(&mut self, ctx: u128, y: u8) {
+        let c = self.followers.entry((ctx, y)).or_insert(0);
+        *c += 1;
+        let best = self.best.entry(ctx).or_insert((y, 0));
+        if *c > best.1 || (*c == best.1 && y > best.0) {
+            *best = (y, *c);
+        }
+    }
+
+    fn predict(&self, ctx: u128) -> Option<(u8, u32)> {
+        self.best.get(&ctx).copied().filter(|&(_, c)| c > 0)
+    }
+}
+
+/// Packs `s[end - d..end]` into a context key (`d <= 16`).
+fn context(s: &[u8], end: usize, d: usize) -> u128 {
+    s[end - d..end].iter().fold(0u128, |acc, &x| (acc << 8) | x as u128)
+}
+
+fn multi_mmc(s: &[u8]) -> Predictions {
+    const D: usize = 16;
+    const MAX_ENTRIES: usize = 100_000;
+    let mut models: Vec<ContextCounts> = (0..D).map(|_| ContextCounts::new()).collect();
+    let mut board = Scoreboard::new(D);
+    let mut out = Predictions::new();
+    let mut predictions = [None; D];
+
+    for i in 2..s.len() {
+        for (d, model) in (1..=D).zip(models.iter_mut()) {
+            if d < i {
+                let ctx = context(s, i - 1, d);
+                if model.followers.contains_key(&(ctx, s[i - 1])) || model.followers.len() < MAX_ENTRIES {
+                    model.increment(ctx, s[i - 1]);
+                }
+            }
+        }
+        for (d, model) in (1..=D).zip(models.iter()) {
+            predictions[d - 1] = if d <= i { model.predict(context(s, i, d)).map(|(y, _)| y) } else { None };
+        }
+        out.record(predictions[board.winner] == Some(s[i]));
+        board.update(&predictions, s[i]);
+    }
+    out
+}
+
+fn lz78y(s: &[u8]) -> Predictions {
+    const B: usize = 16;
+    const MAX_DICTIONARY: usize = 65_536;
+    // One table per context length; the dictionary size counts contexts over all of them.
+    let mut dict: Vec<ContextCounts> = (0..B).map(|_| ContextCounts::new()).collect();
+    let mut size = 0usize;
+    let mut out = Predictions::new();
+
+    for i in B + 1..s.len() {
+        for j in (1..=B).rev() {
+            let ctx = context(s, i - 1, j);
+            let table = &mut dict[j - 1];
+            if !table.best.contains_key(&ctx) && size < MAX_DICTIONARY {
+                table.best.insert(ctx, (0, 0));
+                size += 1;
+            }
+            if table.best.contains_key(&ctx) {
+                table.increment(ctx, s[i - 1]);
+            }
+        }
+        let mut prediction = None;
+        let mut max_count = 0;
+        for j in (1..=B).rev() {
+            if let Some((y, c)) = dict[j - 1].predict(context(s, i, j)) {
+                if c > max_count {
+                    prediction = Some(y);
+                    max_count = c;
+                }
+            }
+        }
+        out.record(prediction == Some(s[i]));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SplitMix64 byte stream for reproducible test samples.
+    fn random_bytes(n: usize, mut state: u64) -> Vec<u8> {
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                (z ^ (z >> 31)) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_uniform_bytes() {
+        let samples = random_bytes(20_000, 1);
+        let a = assess_non_iid(&samples, 8).unwrap();
+        assert_eq!(a.bitstring.len(), 10);
+        assert!(a.original.iter().all(|&(e, _)| !e.binary_only()));
+        let per_byte = a.bitstring.iter().map(|&(e, h)| (e, 8.0 * h));
+        for (e, h) in a.original.iter().copied().chain(per_byte) {
+            assert!(h > 5.0 && h <= 8.0, "{} = {}", e.name(), h);
+        }
+        assert!(a.min_entropy > 5.0 && a.min_entropy <= 8.0);
+        assert!(a.require(BOOTSTRAP_MIN_ENTROPY).is_ok());
+    }
+
+    #[test]
+    fn test_biased_and_stuck_sources() {
+        // Only the low nibble varies: at most 4 bits per byte.
+        let nibbles: Vec<u8> = random_bytes(20_000, 2).iter().map(|b| b & 0x0F).collect();
+        let a = assess_non_iid(&nibbles, 8).unwrap();
+        assert!(a.min_entropy < 4.0, "{}", a.min_entropy);
+        assert!(a.min_entropy > 1.0, "{}", a.min_entropy);
+
+        // A source that repeats a short pattern is caught by the predictors.
+        let cyclic: Vec<u8> = (0..20_000u32).map(|i| (i % 7) as u8).collect();
+        let a = assess_non_iid(&cyclic, 8).unwrap();
+        assert!(a.per_byte() < BOOTSTRAP_MIN_ENTROPY, "{}", a.per_byte());
+        assert_eq!(a.require(BOOTSTRAP_MIN_ENTROPY), Err(EntropyError::HealthTestFailed));
+    }
+
+    #[test]
+    fn test_binary_samples() {
+        let bits: Vec<u8> = random_bytes(20_000, 3).iter().map(|b| b & 1).collect();
+        let a = assess_non_iid(&bits, 1).unwrap();
+        assert_eq!(a.original.len(), 10);
+        assert_eq!(a.h_bitstring, None);
+        for &(e, h) in &a.original {
+            // Collision and compression are pessimistic at this sample size.
+            assert!(h > 0.5 && h <= 1.0, "{} = {}", e.name(), h);
+        }
+
+        // 3:1 biased bits: min-entropy -log2(0.75) ≈ 0.415.
+        let biased: Vec<u8> = random_bytes(20_000, 4).iter().map(|b| (b & 3 != 0) as u8).collect();
+        let a = assess_non_iid(&biased, 1).unwrap();
+        let (e, mcv) = a.original[0];
+        assert_eq!(e, Estimator::MostCommonValue);
+        assert!(mcv < 0.415 && mcv > 0.35, "{}", mcv);
+        assert!(a.min_entropy <= mcv);
+    }
+
+    #[test]
+    fn test_tuple_stats() {
+        // "abab" + "c": 2-tuple "ab" twice, longest repeat 2.
+        let stats = TupleStats::new(b"ababc");
+        assert_eq!(stats.longest_repeat(), 2);
+        assert_eq!(stats.max_count[1], 2);
+        assert_eq!(stats.max_count[2], 2);
+        assert_eq!(stats.pairs[1], 2.0);
+        assert_eq!(stats.pairs[2], 1.0);
+
+        let constant = [7u8; 50];
+        let stats = TupleStats::new(&constant);
+        assert_eq!(stats.longest_repeat(), 49);
+        assert_eq!(stats.max_count[10], 41);
+        assert_eq!(stats.pairs[10], (41.0 * 40.0) / 2.0);
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert_eq!(assess_non_iid(&[0u8; 100], 8), Err(EntropyError::InsufficientSamples));
+        assert_eq!(assess_non_iid(&[2u8; MIN_SAMPLES], 1), Err(EntropyError::InvalidSamples));
+        assert_eq!(assess_non_iid(&[0u8; MIN_SAMPLES], 9), Err(EntropyError::InvalidSamples));
+    }
+}
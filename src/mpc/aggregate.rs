@@ -18,14 +18,19 @@ extern crate alloc;
 use alloc::vec::Vec;
 use zeroize::Zeroizing;
 use crate::core::gf256::GF256;
-use crate::mpc::{MpcError, share::Share};
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF65536, SHARE_VERSION_PACKED}};
 use crate::entropy::EntropySource;
-use crate::mpc::polynomial::evaluate_polynomial;
+use crate::core::xor::xor_inplace;
+use crate::mpc::polynomial::evaluate_rows;
+
+/// Bytes of share refreshed per pass in `refresh_shares`.
+const REFRESH_CHUNK: usize = 4096;
 
 /// Refreshes a set of shares by adding a polynomial of 0.
 ///
 /// This changes the shares but keeps the underlying secret constant.
 /// Useful for Proactive Secret Sharing (PSS) to render old shares useless.
+/// Needs every share in one process; see `mpc::refresh` for the distributed protocol.
 ///
 /// # Arguments
 /// * `shares` - Mutable slice of shares to refresh.
@@ -58,48 +63,38 @@ pub fn refresh_shares<R: EntropySource + ?Sized>(
         if share.value.len() != share_len {
             return Err(MpcError::ShareLengthMismatch);
         }
+        share.gf256_x()?;
     }
 
-    // Temporary buffer for random coefficients (k-1 coefficients per byte)
-    // Coeffs: c0=0, c1..c(k-1) random
-    let mut random_buf = Zeroizing::new(vec![0u8; (k - 1) as usize]);
-
-    for p in 0..share_len {
-        // Generate random coefficients for g(x) = 0 + c1*x + ... + c(k-1)*x^(k-1)
-        if k > 1 {
-            match rng.fill(&mut random_buf) {
-                Ok(_) => {},
-                Err(_) => return Err(MpcError::RngFailure),
-            }
-        }
-
-        // Construct polynomial coefficients
-        let mut coeffs: Vec<GF256> = Vec::with_capacity(k as usize);
-        coeffs.push(GF256(0)); // Intercept is 0
-        for &r in random_buf.iter() {
-            coeffs.push(GF256(r));
+    // Random coefficient rows for g(x) = 0 + c1*x + ... + c(k-1)*x^(k-1), one row per
+    // coefficient spanning a whole chunk of share bytes.
+    let mut random_rows = Zeroizing::new(vec![0u8; (k - 1) as usize * REFRESH_CHUNK]);
+    let mut update = Zeroizing::new(vec![0u8; REFRESH_CHUNK]);
+
+    let mut start = 0;
+    while start < share_len {
+        let len = core::cmp::min(REFRESH_CHUNK, share_len - start);
+        let rows = &mut random_rows[..(k - 1) as usize * len];
+        if rng.fill(rows).is_err() {
+            return Err(MpcError::RngFailure);
         }
-        let coeffs = Zeroizing::new(coeffs);
 
         // Update shares
-        update_shares_constant_time(shares, &coeffs, p);
+        for share in shares.iter_mut() {
+            let g = &mut update[..len];
+            evaluate_rows(rows, None, share.gf256_x()?, g);
+            // value = value + g(x) (GF256 add is XOR)
+            xor_inplace(&mut share.value[start..start + len], g);
+        }
+        start += len;
     }
 
-    Ok(())
-}
-
-/// Helper to update shares in a loop that attempts to be constant time.
-/// Marked inline(never) to prevent loop unrolling optimizations that might leak index info.
-#[inline(never)]
-fn update_shares_constant_time(shares: &mut [Share], coeffs: &[GF256], p: usize) {
+    // Old tags cover the old values; the dealer re-tags with fresh keys (see `auth`).
     for share in shares.iter_mut() {
-        let x = GF256(share.identifier);
-        let update_val = evaluate_polynomial(coeffs, x);
-        
-        // value = value + update_val (GF256 add is XOR)
-        let current_val = GF256(share.value[p]);
-        share.value[p] = (current_val + update_val).0;
+        share.tags.clear();
     }
+
+    Ok(())
 }
 
 /// Adds two shares homomorphically.
@@ -114,13 +109,28 @@ pub fn add_shares(share1: &Share, share2: &Share) -> Result<Share, MpcError> {
         return Err(MpcError::ShareLengthMismatch);
     }
 
+    if share1.version != share2.version {
+        return Err(MpcError::UnsupportedVersion);
+    }
+
+    // Addition is XOR in both GF(2^8) and GF(2^16).
     let mut new_value = Vec::with_capacity(share1.value.len());
     for (v1, v2) in share1.value.iter().zip(share2.value.iter()) {
         let sum = GF256(*v1) + GF256(*v2);
         new_value.push(sum.0);
     }
+    // The layout header (padding count, pack size) is not field data.
+    let header = match share1.version {
+        SHARE_VERSION_GF65536 => 1,
+        SHARE_VERSION_PACKED => 2,
+        _ => 0,
+    };
+    if share1.value[..header] != share2.value[..header] {
+        return Err(MpcError::ShareLengthMismatch);
+    }
+    new_value[..header].copy_from_slice(&share1.value[..header]);
 
-    Share::new(share1.identifier, new_value)
+    Share::with_version(share1.version, share1.identifier, new_value)
 }
 
 #[cfg(test)]
@@ -0,0 +1,291 @@
+//! Information-theoretic share authentication.
+//!
+//! The dealer holds a one-time key for every (verifier, holder) pair and attaches to each
+//! share one tag per verifier. At reconstruction a verifier checks every share it is handed
+//! against its own keys, so a holder that corrupts its share (or its tags) is caught without
+//! any computational assumption.
+//!
+//! Tags are the Wegman–Carter polynomial MAC of `core::poly_mac`: every 8-byte lane is
+//! `s + sum_j m_j * x^(j+1)` over GF(2^64), with `x` from the hash key, `s` from a mask and
+//! `m_j` the words of `value`, then `TAG_DOMAIN || version || identifier`, then both lengths.
+//! Every message term carries a factor of `x`, so neither the value nor the tag can be
+//! shifted by a constant. Values are limited to `MAX_TAGGED_LEN` bytes, i.e. at most
+//! `n = 2^17 + 3` words, and a forgery against one verifier succeeds with probability at
+//! most `(n / 2^64)^4 < 2^-187`.
+//!
+//! A key is `hash key || mask 0 || mask 1`. Dealer tags use mask 0. Pairwise tags (`refresh`,
+//! `reshare`, `repair`) use mask 0 from the lower to the higher identifier and mask 1 the
+//! other way, so a symmetric pairwise key still masks each direction once.
+//!
+//! # Security
+//! - **One-Time Keys**: A key may only tag one share value per mask. `VerifierKeys` refuses
+//!   to tag twice for the same holder; re-tag with fresh keys after a refresh.
+//! - **Pairwise**: Verifiers never share keys, so a verifier cannot forge towards another.
+//! - **Reconstruction**: `reconstruct_secret` has no keys and rejects tagged shares outright;
+//!   use `reconstruct::reconstruct_authenticated`. Untagged shares are not authenticated.
+//! - **Zeroization**: `VerifierKeys` wipes its keys on drop.
+//! - **Constant-Time**: Tag comparison is constant-time; all shares are checked before
+//!   reporting.
+
+extern crate alloc;
+use alloc::collections::{BTreeMap, BTreeSet};
+use zeroize::{Zeroize, Zeroizing};
+use crate::core::poly_mac::poly_tag;
+use crate::entropy::EntropySource;
+use crate::mpc::{MpcError, share::{Share, ShareTag}};
+
+/// Bytes per share tag (four GF(2^64) lanes).
+pub const TAG_LEN: usize = 32;
+
+/// Bytes per key: a hash key and two masks (see module docs).
+pub const KEY_LEN: usize = 3 * TAG_LEN;
+
+/// Longest share value that can be tagged.
+pub const MAX_TAGGED_LEN: usize = 1 << 20;
+
+/// Domain byte of the tagged share metadata.
+const TAG_DOMAIN: u8 = 0x53;
+
+/// One verifier's pairwise one-time keys, indexed by share holder.
+///
+/// The dealer keeps one `VerifierKeys` per verifier; each verifier receives its own over a
+/// private channel.
+pub struct VerifierKeys {
+    verifier: u16,
+    keys: BTreeMap<u16, Zeroizing<[u8; KEY_LEN]>>,
+    spent: BTreeSet<u16>,
+}
+
+impl VerifierKeys {
+    /// Empty key set for `verifier`.
+    pub fn new(verifier: u16) -> Self {
+        Self { verifier, keys: BTreeMap::new(), spent: BTreeSet::new() }
+    }
+
+    /// Draws a fresh key for every identifier in `holders`.
+    pub fn generate<R: EntropySource + ?Sized>(verifier: u16, holders: &[u16], rng: &mut R) -> Result<Self, MpcError> {
+        let mut out = Self::new(verifier);
+        for &holder in holders {
+            let mut key = Zeroizing::new([0u8; KEY_LEN]);
+            rng.fill(&mut key[..]).map_err(|_| MpcError::RngFailure)?;
+            out.keys.insert(holder, key);
+        }
+        Ok(out)
+    }
+
+    /// Installs (or replaces) the key for `holder`; a replaced key may tag again.
+    pub fn insert(&mut self, holder: u16, mut key: [u8; KEY_LEN]) {
+        self.keys.insert(holder, Zeroizing::new(key));
+        self.spent.remove(&holder);
+        key.zeroize();
+    }
+
+    /// The verifier these keys belong to.
+    pub fn verifier(&self) -> u16 {
+        self.verifier
+    }
+
+    pub(crate) fn key(&self, holder: u16) -> Result<&[u8; KEY_LEN], MpcError> {
+        self.keys.get(&holder).map(|k| &**k).ok_or(MpcError::InvalidShareIndex)
+    }
+
+    /// Tags `share` with the key for `holder` and marks that key spent.
+    fn tag_once(&mut self, holder: u16, share: &Share, mask: usize) -> Result<[u8; TAG_LEN], MpcError> {
+        if self.spent.contains(&holder) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        let tag = share_tag(share, self.key(holder)?, mask)?;
+        self.spent.insert(holder);
+        Ok(tag)
+    }
+}
+
+/// Dealer side: attaches (or replaces) a tag for every verifier to every share.
+///
+/// Each key tags one share; tagging the same holder twice needs fresh keys.
+///
+/// # Errors
+/// * `MpcError::InvalidShareIndex` if a verifier has no unused key for some share holder.
+/// * `MpcError::ShareLengthMismatch` if a value exceeds `MAX_TAGGED_LEN`.
+pub fn authenticate_shares(shares: &mut [Share], verifiers: &mut [VerifierKeys]) -> Result<(), MpcError> {
+    for share in shares.iter_mut() {
+        for keys in verifiers.iter_mut() {
+            let tag = keys.tag_once(share.identifier, share, 0)?;
+            share.tags.retain(|t| t.verifier != keys.verifier);
+            share.tags.push(ShareTag { verifier: keys.verifier, tag });
+        }
+    }
+    Ok(())
+}
+
+/// Verifier side: checks the tag addressed to `keys.verifier()` on every share.
+///
+/// # Errors
+/// * `MpcError::IntegrityFailure` if any share lacks a valid tag for this verifier.
+pub fn verify_shares(shares: &[Share], keys: &VerifierKeys) -> Result<(), MpcError> {
+    let mut ok = true;
+    for share in shares {
+        ok &= tag_matches(share, keys.verifier, keys.key(share.identifier), 0);
+    }
+    if ok { Ok(()) } else { Err(MpcError::IntegrityFailure) }
+}
+
+/// Tags `share` for `recipient` under the pairwise key `keys` holds for it.
+///
+/// The sender is `keys.verifier()`; the key is spent afterwards.
+///
+/// # Errors
+/// * `MpcError::InvalidShareIndex` if no unused key is held for `recipient`.
+/// * `MpcError::ShareLengthMismatch` if the value exceeds `MAX_TAGGED_LEN`.
+pub(crate) fn tag_pairwise(share: &mut Share, recipient: u16, keys: &mut VerifierKeys) -> Result<(), MpcError> {
+    let mask = direction(keys.verifier, recipient);
+    let tag = keys.tag_once(recipient, share, mask)?;
+    share.tags.push(ShareTag { verifier: recipient, tag });
+    Ok(())
+}
+
+/// Checks a share sent point-to-point by `sender` to `keys.verifier()`.
+///
+/// The tag is computed under the pairwise key `keys` holds for `sender`.
+///
+/// # Errors
+/// * `MpcError::IntegrityFailure` if the tag is missing or wrong, or no key is held for `sender`.
+pub(crate) fn verify_pairwise(share: &Share, sender: u16, keys: &VerifierKeys) -> Result<(), MpcError> {
+    let mask = direction(sender, keys.verifier);
+    if tag_matches(share, keys.verifier, keys.key(sender), mask) { Ok(()) } else { Err(MpcError::IntegrityFailure) }
+}
+
+/// Fails closed on shares whose tags the caller has not checked.
+///
+/// # Errors
+/// * `MpcError::IntegrityFailure` if any share carries a tag.
+pub(crate) fn reject_tagged(shares: &[Share]) -> Result<(), MpcError> {
+    if shares.iter().any(|s| !s.tags.is_empty()) { Err(MpcError::IntegrityFailure) } else { Ok(()) }
+}
+
+/// Mask used by `sender` towards `recipient`.
+fn direction(sender: u16, recipient: u16) -> usize {
+    (sender > recipient) as usize
+}
+
+fn tag_matches(share: &Share, verifier: u16, key: Result<&[u8; KEY_LEN], MpcError>, mask: usize) -> bool {
+    match (share.tag_for(verifier), key) {
+        (Some(t), Ok(key)) => share_tag(share, key, mask).is_ok_and(|tag| ct_eq(&tag, &t.tag)),
+        _ => false,
+    }
+}
+
+fn share_tag(share: &Share, key: &[u8; KEY_LEN], mask: usize) -> Result<[u8; TAG_LEN], MpcError> {
+    if share.value.len() > MAX_TAGGED_LEN {
+        return Err(MpcError::ShareLengthMismatch);
+    }
+    let id = share.identifier.to_le_bytes();
+    let metadata = [TAG_DOMAIN, share.version, id[0], id[1]];
+    let mask = &key[TAG_LEN * (1 + mask)..TAG_LEN * (2 + mask)];
+    let tag = poly_tag(&share.value, &metadata, &key[..TAG_LEN], mask).map_err(|_| MpcError::IntegrityFailure)?;
+    let mut out = [0u8; TAG_LEN];
+    out.copy_from_slice(&tag);
+    Ok(out)
+}
+
+fn ct_eq(a: &[u8; TAG_LEN], b: &[u8; TAG_LEN]) -> bool {
+    a.iter().zip(b.iter()).fold(0u8, |d, (x, y)| d | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::MockEntropy;
+    use crate::mpc::quorum::split_secret;
+    use crate::mpc::reconstruct::{interpolate_at, reconstruct_authenticated, reconstruct_robust, reconstruct_secret, ReconstructionContext};
+
+    fn dealt(rng: &mut MockEntropy) -> (Vec<Share>, Vec<VerifierKeys>) {
+        let mut shares = split_secret(b"group key", 3, 4, rng).unwrap();
+        let holders: Vec<u16> = shares.iter().map(|s| s.identifier).collect();
+        let mut keys: Vec<VerifierKeys> = holders.iter().map(|&v| VerifierKeys::generate(v, &holders, rng).unwrap()).collect();
+        authenticate_shares(&mut shares, &mut keys).unwrap();
+        (shares, keys)
+    }
+
+    #[test]
+    fn test_tags_verify_per_verifier() {
+        let mut rng = MockEntropy { state: 3 };
+        let (shares, keys) = dealt(&mut rng);
+        assert!(shares.iter().all(|s| s.tags.len() == 4));
+        for k in &keys {
+            assert_eq!(verify_shares(&shares, k), Ok(()));
+        }
+        // A tag addressed to verifier 1 does not verify under verifier 2's keys.
+        let mut swapped = shares[0].clone();
+        swapped.tags[1].tag = swapped.tags[0].tag;
+        assert_eq!(verify_shares(&[swapped], &keys[1]), Err(MpcError::IntegrityFailure));
+    }
+
+    #[test]
+    fn test_keys_are_one_time() {
+        let mut rng = MockEntropy { state: 4 };
+        let (mut shares, mut keys) = dealt(&mut rng);
+        // Re-tagging a changed value under the same keys is refused.
+        shares[0].value[0] ^= 1;
+        assert_eq!(authenticate_shares(&mut shares[..1], &mut keys), Err(MpcError::InvalidShareIndex));
+        keys[0].insert(1, [7u8; KEY_LEN]);
+        assert_eq!(authenticate_shares(&mut shares[..1], &mut keys[..1]), Ok(()));
+
+        let mut long = Share::new(1, vec![0u8; MAX_TAGGED_LEN + 1]).unwrap();
+        let mut fresh = VerifierKeys::generate(2, &[1], &mut rng).unwrap();
+        assert_eq!(authenticate_shares(core::slice::from_mut(&mut long), core::slice::from_mut(&mut fresh)), Err(MpcError::ShareLengthMismatch));
+    }
+
+    #[test]
+    fn test_long_share_differences_rejected() {
+        let mut rng = MockEntropy { state: 17 };
+        let mut shares = split_secret(&[0x42u8; 600], 2, 3, &mut rng).unwrap();
+        let mut keys = [VerifierKeys::generate(3, &[1, 2, 3], &mut rng).unwrap()];
+        authenticate_shares(&mut shares, &mut keys).unwrap();
+
+        // Equal changes 255 bytes apart cancelled out in the SIP-64 lanes.
+        for a in [0usize, 1, 100, 344] {
+            let mut bad = shares[0].clone();
+            bad.value[a] ^= 0x33;
+            bad.value[a + 255] ^= 0x33;
+            assert_eq!(verify_shares(&[bad], &keys[0]), Err(MpcError::IntegrityFailure));
+        }
+        // So did a tag-only change.
+        let mut bad = shares[1].clone();
+        bad.tags[0].tag[0] ^= 1;
+        assert_eq!(verify_shares(&[bad], &keys[0]), Err(MpcError::IntegrityFailure));
+        assert_eq!(verify_shares(&shares, &keys[0]), Ok(()));
+    }
+
+    #[test]
+    fn test_corrupted_share_rejected() {
+        let mut rng = MockEntropy { state: 11 };
+        let (shares, keys) = dealt(&mut rng);
+        assert_eq!(reconstruct_authenticated(&shares[..3], 3, &keys[3]).unwrap(), b"group key");
+
+        let mut bad = shares.clone();
+        bad[1].value[4] ^= 0x01;
+        assert_eq!(reconstruct_authenticated(&bad[..3], 3, &keys[3]), Err(MpcError::IntegrityFailure));
+
+        // The constant-term shift on the first value byte is caught too.
+        let mut shifted = shares.clone();
+        shifted[2].value[0] ^= 0x5A;
+        for t in shifted[2].tags.iter_mut() {
+            t.tag.iter_mut().for_each(|b| *b ^= 0x5A);
+        }
+        assert_eq!(reconstruct_authenticated(&shifted[..3], 3, &keys[0]), Err(MpcError::IntegrityFailure));
+
+        // Tagged shares only reconstruct through the verifying path.
+        assert_eq!(reconstruct_secret(&shares[..3], 3), Err(MpcError::IntegrityFailure));
+        assert_eq!(reconstruct_robust(&shares, 3), Err(MpcError::IntegrityFailure));
+        assert_eq!(interpolate_at(&shares[..3], 0), Err(MpcError::IntegrityFailure));
+        let ctx = ReconstructionContext::new(&[1, 2, 3], 3).unwrap();
+        assert_eq!(ctx.reconstruct(&shares[..3]), Err(MpcError::IntegrityFailure));
+        assert!(ctx.stream(vec![shares[..3].to_vec()]).all(|r| r.is_err()));
+
+        // Untagged shares are rejected as well.
+        let mut untagged = shares.clone();
+        untagged[0].tags.clear();
+        assert_eq!(reconstruct_authenticated(&untagged[..3], 3, &keys[0]), Err(MpcError::IntegrityFailure));
+    }
+}
@@ -0,0 +1,307 @@
+//! BGW multiplication on GF(2^8) shares.
+//!
+//! Addition of shared values is local (`aggregate::add_shares`, `add_public`). Multiplication
+//! is local too (`mul_local`), but doubles the polynomial degree to `2(k - 1)`. A `MulGate`
+//! brings it back to `k - 1` in one round: every party reshares its product share with a fresh
+//! degree `k - 1` polynomial, and every party combines the sub-shares it receives with the
+//! recombination vector (the first row of the inverse Vandermonde matrix of the party set,
+//! i.e. the Lagrange weights at zero).
+//!
+//! # Rounds
+//! Parties exchange `RoundMessage`s through any `RoundChannel` (pairwise, one message per
+//! ordered pair per round). `LocalNetwork` is an in-process channel that also drives all
+//! parties through a gate, for tests and single-device simulation.
+//!
+//! # Security
+//! - **Semi-Honest**: Correct for honest-but-curious parties; sub-shares are not tagged, so
+//!   the transport must authenticate them (e.g. Noise XX sessions).
+//! - **Parties**: Multiplication needs at least `2k - 1` parties online.
+//! - **Zeroization**: Product shares and resharing coefficients are zeroized.
+//! - **GF(2^8) only**: Version 1 shares.
+//!
+//! # Whitepaper Compliance
+//! - Section 1.1: BGW multiplication gates.
+
+extern crate alloc;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+use crate::core::gf256::{GF256, mul_add_slice, mul_pointwise};
+use crate::core::xor::xor_inplace;
+use crate::entropy::EntropySource;
+use crate::mpc::polynomial::evaluate_rows;
+use crate::mpc::reconstruct::lagrange_weights;
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF256}};
+
+/// One pairwise message of a protocol round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundMessage {
+    /// Sending party.
+    pub from: u16,
+    /// Receiving party.
+    pub to: u16,
+    /// Sub-share carried by the message (`identifier == to`).
+    pub payload: Share,
+}
+
+/// Transport for round messages over pairwise channels.
+pub trait RoundChannel {
+    /// Queues `msg` for delivery to `msg.to`.
+    fn send(&mut self, msg: RoundMessage) -> Result<(), MpcError>;
+
+    /// Every message of the current round addressed to `me`.
+    fn receive(&mut self, me: u16) -> Result<Vec<RoundMessage>, MpcError>;
+}
+
+/// Share-wise product of two sharings (degree `2(k - 1)`; reduce with a `MulGate`).
+///
+/// # Errors
+/// * `MpcError::InvalidShareIndex` if the identifiers differ.
+/// * `MpcError::ShareLengthMismatch` if the lengths differ.
+/// * `MpcError::UnsupportedVersion` unless both are GF(2^8) shares.
+pub fn mul_local(a: &Share, b: &Share) -> Result<Share, MpcError> {
+    if a.identifier != b.identifier {
+        return Err(MpcError::InvalidShareIndex);
+    }
+    if a.version != SHARE_VERSION_GF256 || b.version != SHARE_VERSION_GF256 {
+        return Err(MpcError::UnsupportedVersion);
+    }
+    if a.value.len() != b.value.len() {
+        return Err(MpcError::ShareLengthMismatch);
+    }
+    let mut value = a.value.clone();
+    mul_pointwise(&mut value, &b.value);
+    Share::new(a.identifier, value)
+}
+
+/// Adds a public constant to a shared value (every holder adds it to its share).
+pub fn add_public(share: &Share, constant: &[u8]) -> Result<Share, MpcError> {
+    share.gf256_x()?;
+    if share.value.len() != constant.len() {
+        return Err(MpcError::ShareLengthMismatch);
+    }
+    let mut value = share.value.clone();
+    xor_inplace(&mut value, constant);
+    Share::new(share.identifier, value)
+}
+
+/// One party's side of a degree-reduction round.
+pub struct MulGate {
+    me: u16,
+    parties: Vec<u16>,
+    k: u8,
+    recombination: Zeroizing<Vec<GF256>>,
+}
+
+impl MulGate {
+    /// Gate for party `me` among `parties`, reducing to threshold `k`.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidThreshold` if `k < 2`.
+    /// * `MpcError::InsufficientShares` with fewer than `2k - 1` parties.
+    /// * `MpcError::InvalidShareIndex` / `MpcError::DuplicateShareIndex` on bad identifiers,
+    ///   or if `me` is not a party.
+    pub fn new(me: u16, parties: &[u16], k: u8) -> Result<Self, MpcError> {
+        if k < 2 {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if parties.len() < 2 * k as usize - 1 {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut seen = BTreeSet::new();
+        let mut xs = Vec::with_capacity(parties.len());
+        for &p in parties {
+            let x = u8::try_from(p).ok().filter(|&x| x != 0).ok_or(MpcError::InvalidShareIndex)?;
+            if !seen.insert(p) {
+                return Err(MpcError::DuplicateShareIndex);
+            }
+            xs.push(GF256(x));
+        }
+        if !seen.contains(&me) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        Ok(Self { me, parties: parties.to_vec(), k, recombination: lagrange_weights(&xs, GF256(0)) })
+    }
+
+    /// Reshares this party's product share: one message per party (including itself).
+    pub fn deal<R: EntropySource + ?Sized>(&self, product: &Share, rng: &mut R) -> Result<Vec<RoundMessage>, MpcError> {
+        if product.identifier != self.me {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        product.gf256_x()?;
+        let len = product.value.len();
+        let mut rows = Zeroizing::new(vec![0u8; (self.k - 1) as usize * len]);
+        rng.fill(&mut rows[..]).map_err(|_| MpcError::RngFailure)?;
+
+        let mut out = Vec::with_capacity(self.parties.len());
+        for &p in &self.parties {
+            let mut value = vec![0u8; len];
+            evaluate_rows(&rows, Some(&product.value), GF256(p as u8), &mut value);
+            out.push(RoundMessage { from: self.me, to: p, payload: Share::new(p, value)? });
+        }
+        Ok(out)
+    }
+
+    /// Recombines one sub-share from every party into a degree `k - 1` share.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` if a party's message is missing.
+    /// * `MpcError::InvalidShareIndex` / `MpcError::DuplicateShareIndex` on misaddressed,
+    ///   unknown or repeated senders.
+    /// * `MpcError::ShareLengthMismatch` if the sub-shares differ in length.
+    pub fn combine(&self, incoming: &[RoundMessage]) -> Result<Share, MpcError> {
+        let mut by_sender = BTreeMap::new();
+        for msg in incoming {
+            if msg.to != self.me || msg.payload.identifier != self.me {
+                return Err(MpcError::InvalidShareIndex);
+            }
+            msg.payload.gf256_x()?;
+            if by_sender.insert(msg.from, &msg.payload).is_some() {
+                return Err(MpcError::DuplicateShareIndex);
+            }
+        }
+        if by_sender.len() != self.parties.len() {
+            return Err(if by_sender.keys().all(|f| self.parties.contains(f)) {
+                MpcError::InsufficientShares
+            } else {
+                MpcError::InvalidShareIndex
+            });
+        }
+
+        let len = incoming[0].payload.value.len();
+        let mut value = vec![0u8; len];
+        for (p, &r) in self.parties.iter().zip(self.recombination.iter()) {
+            let sub = by_sender.get(p).ok_or(MpcError::InvalidShareIndex)?;
+            if sub.value.len() != len {
+                return Err(MpcError::ShareLengthMismatch);
+            }
+            mul_add_slice(&mut value, &sub.value, r);
+        }
+        Share::new(self.me, value)
+    }
+}
+
+/// In-process `RoundChannel`: one mailbox per party.
+#[derive(Default)]
+pub struct LocalNetwork {
+    mailboxes: BTreeMap<u16, Vec<RoundMessage>>,
+}
+
+impl LocalNetwork {
+    /// Empty network.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a full multiplication for every party holding `a[i]` and `b[i]`.
+    ///
+    /// Returns the degree `k - 1` shares of the product, in party order.
+    pub fn multiply<R: EntropySource + ?Sized>(
+        &mut self,
+        a: &[Share],
+        b: &[Share],
+        k: u8,
+        rng: &mut R
+    ) -> Result<Vec<Share>, MpcError> {
+        if a.len() != b.len() {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        let parties: Vec<u16> = a.iter().map(|s| s.identifier).collect();
+        let mut gates = Vec::with_capacity(parties.len());
+        for (sa, sb) in a.iter().zip(b) {
+            let gate = MulGate::new(sa.identifier, &parties, k)?;
+            let product = mul_local(sa, sb)?;
+            for msg in gate.deal(&product, rng)? {
+                self.send(msg)?;
+            }
+            gates.push(gate);
+        }
+        let mut out = Vec::with_capacity(parties.len());
+        for gate in &gates {
+            let incoming = self.receive(gate.me)?;
+            out.push(gate.combine(&incoming)?);
+        }
+        Ok(out)
+    }
+}
+
+impl RoundChannel for LocalNetwork {
+    fn send(&mut self, msg: RoundMessage) -> Result<(), MpcError> {
+        self.mailboxes.entry(msg.to).or_default().push(msg);
+        Ok(())
+    }
+
+    fn receive(&mut self, me: u16) -> Result<Vec<RoundMessage>, MpcError> {
+        Ok(self.mailboxes.remove(&me).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::MockEntropy;
+    use crate::mpc::aggregate::add_shares;
+    use crate::mpc::quorum::split_secret;
+    use crate::mpc::reconstruct::reconstruct_secret;
+
+    #[test]
+    fn test_multiply_with_degree_reduction() {
+        let mut rng = MockEntropy { state: 2 };
+        let x = [0x57u8, 0x02, 0xFF];
+        let y = [0x83u8, 0x80, 0x01];
+        let a = split_secret(&x, 3, 5, &mut rng).unwrap();
+        let b = split_secret(&y, 3, 5, &mut rng).unwrap();
+
+        let c = LocalNetwork::new().multiply(&a, &b, 3, &mut rng).unwrap();
+        let expected: Vec<u8> = x.iter().zip(&y).map(|(&p, &q)| (GF256(p) * GF256(q)).0).collect();
+        // Back to threshold 3: any three shares open the product.
+        assert_eq!(reconstruct_secret(&c[2..], 3).unwrap(), expected);
+        // Without reduction three product shares do not suffice.
+        let raw: Vec<Share> = a.iter().zip(&b).map(|(p, q)| mul_local(p, q).unwrap()).collect();
+        assert_ne!(reconstruct_secret(&raw[..3], 3).unwrap(), expected);
+        assert_eq!(reconstruct_secret(&raw, 5).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_joint_coin_flip_with_bias_check() {
+        let mut rng = MockEntropy { state: 17 };
+        let k = 2;
+        let n = 4;
+        // Every party shares a bit; the third one cheats with a non-bit.
+        let bits = [[1u8], [0u8], [2u8], [1u8]];
+        let dealt: Vec<Vec<Share>> = bits.iter().map(|b| split_secret(b, k, n, &mut rng).unwrap()).collect();
+
+        let mut net = LocalNetwork::new();
+        let mut cheaters = Vec::new();
+        for (i, shares) in dealt.iter().enumerate() {
+            // b * (b + 1) is zero exactly for b in {0, 1}.
+            let plus_one: Vec<Share> = shares.iter().map(|s| add_public(s, &[1]).unwrap()).collect();
+            let check = net.multiply(shares, &plus_one, k, &mut rng).unwrap();
+            if reconstruct_secret(&check[..k as usize], k).unwrap() != [0] {
+                cheaters.push(i);
+            }
+        }
+        assert_eq!(cheaters, vec![2]);
+
+        // The coin is the XOR of the honest bits (1, 0, 1), opened from any k shares.
+        let mut coin: Vec<Share> = dealt[0].clone();
+        for shares in [&dealt[1], &dealt[3]] {
+            coin = coin.iter().zip(shares.iter()).map(|(p, q)| add_shares(p, q).unwrap()).collect();
+        }
+        assert_eq!(reconstruct_secret(&coin[1..3], k).unwrap(), [0]);
+    }
+
+    #[test]
+    fn test_gate_errors() {
+        let mut rng = MockEntropy { state: 4 };
+        assert_eq!(MulGate::new(1, &[1, 2, 3, 4], 3).map(|_| ()), Err(MpcError::InsufficientShares));
+        assert_eq!(MulGate::new(9, &[1, 2, 3], 2).map(|_| ()), Err(MpcError::InvalidShareIndex));
+        let gate = MulGate::new(1, &[1, 2, 3], 2).unwrap();
+        let product = Share::new(1, vec![5, 6]).unwrap();
+        let msgs = gate.deal(&product, &mut rng).unwrap();
+        assert_eq!(msgs.len(), 3);
+        assert_eq!(gate.combine(&msgs[1..2]), Err(MpcError::InvalidShareIndex));
+        assert_eq!(gate.combine(&[msgs[0].clone()]).map(|_| ()), Err(MpcError::InsufficientShares));
+        assert_eq!(mul_local(&product, &Share::new(2, vec![1, 1]).unwrap()), Err(MpcError::InvalidShareIndex));
+    }
+}
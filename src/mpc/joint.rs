@@ -0,0 +1,568 @@
+//! Dealer-free joint random generation (`MasterPad = XOR R_i`).
+//!
+//! Every member contributes its own extracted randomness `R_i`. No single member's source
+//! (or its absence) decides the result, and nobody ever needs a trusted dealer.
+//!
+//! # Protocol
+//! 1. **Commit**: every member calls `JointRandom::deal`, broadcasts the `Commitments` and
+//!    sends sub-share `j` of `R_i` (threshold `k`) to member `j`. Members record what they
+//!    receive with `receive_commitment` and `receive_share`.
+//! 2. **Check**: once `committed()` holds, every member broadcasts `challenge_share` and
+//!    records the others with `receive_challenge`; then broadcasts `openings` and records
+//!    the others with `receive_openings`. `check` accepts every dealing or aborts the round.
+//! 3. After `check`, every member either keeps `share()` (a share of `XOR R_i`, nobody
+//!    learns the value), or:
+//! 4. **Reveal**: broadcasts `contribution()`; members check it with `reveal`. A member that
+//!    withholds it is opened by `recover` from its sub-shares. `output` is `XOR R_i`.
+//!
+//! Reveals are refused until every dealing has passed `check`, so the last member to
+//! reveal has already fixed its contribution and cannot choose it after seeing the others.
+//!
+//! # Check
+//! Each dealing shares `R_i` followed by a `BLIND_LEN`-byte random blind, and the commitment
+//! covers both. The challenge is the XOR of one contribution per member, each committed in
+//! step 1 and opened only after every sub-share was delivered, so no dealer can predict it.
+//! For every dealer, each member publishes the blinded fold (see `refresh`) of the sub-share
+//! it holds; the blind hides the sub-share. `check` confirms that every dealer's openings lie
+//! on one polynomial of degree below `k`; an inconsistent dealing passes with probability
+//! `2^-(8 * BLIND_LEN)`. The openings also fix each dealer's folded intercept, and `reveal`
+//! requires the revealed value to fold to it, so a dealer cannot commit to one value and
+//! share another: whether it reveals or withholds, the same value is opened.
+//!
+//! With exactly `k` members any sub-shares form a valid sharing and only the intercept
+//! binding applies.
+//!
+//! # Aborts
+//! A failed `check` aborts the round before anything is revealed, so it cannot bias the
+//! output; `rejected` names the dealers to exclude from the rerun. A member that publishes
+//! wrong openings makes the dealers it lies about fail as well, so a dealer rejected only
+//! alongside such a member may be honest. Withholding a challenge share or the openings
+//! also stalls the round before any reveal.
+//!
+//! # Limits
+//! `recover` decodes with `reconstruct_robust`: handing in every available sub-share
+//! corrects up to `⌊(m - k) / 2⌋` wrong ones among `m`. A coalition that withholds its
+//! contribution and submits more wrong sub-shares than that still forces an abort after
+//! the reveals. Sub-shares are not authenticated here.
+//!
+//! # Security
+//! - **Commitment**: BLAKE3 (derived keys `TOGM_JOINT_COMMIT` and `TOGM_JOINT_CHALLENGE`)
+//!   over the member id and the committed value. Binding is computational; hiding relies on
+//!   the committed values being high-entropy.
+//! - **Sub-Shares**: Sent over authenticated pairwise channels.
+//! - **Zeroization**: Contributions, sub-shares and the output are zeroized.
+//! - **GF(2^8) only**: Identifiers 1..=255.
+
+extern crate alloc;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+use crate::core::gf256::GF256;
+use crate::core::xor::xor_inplace;
+use crate::entropy::EntropySource;
+use crate::mpc::polynomial::evaluate_rows;
+use crate::mpc::reconstruct::{interpolate_at, reconstruct_robust};
+use crate::mpc::refresh::{fold, BLIND_LEN};
+use crate::mpc::reshare::check_consistency;
+use crate::mpc::{MpcError, share::Share};
+
+/// Length of a contribution commitment.
+pub const COMMITMENT_LEN: usize = 32;
+
+/// Commitment to member `member`'s contribution `r`.
+pub fn commit(member: u16, r: &[u8]) -> [u8; COMMITMENT_LEN] {
+    commit_in("TOGM_JOINT_COMMIT", member, r)
+}
+
+fn commit_in(context: &str, member: u16, r: &[u8]) -> [u8; COMMITMENT_LEN] {
+    let mut h = blake3::Hasher::new_derive_key(context);
+    h.update(&member.to_le_bytes());
+    h.update(&(r.len() as u64).to_le_bytes());
+    h.update(r);
+    h.finalize().into()
+}
+
+/// What a member broadcasts in the commit phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitments {
+    /// `commit` over the contribution followed by its blind.
+    pub contribution: [u8; COMMITMENT_LEN],
+    /// Commitment to the member's challenge share.
+    pub challenge: [u8; COMMITMENT_LEN],
+}
+
+/// One member's state during joint random generation.
+pub struct JointRandom {
+    me: u16,
+    members: BTreeSet<u16>,
+    k: u8,
+    len: usize,
+    own_challenge: Option<Zeroizing<Vec<u8>>>,
+    own_contribution: Option<Zeroizing<Vec<u8>>>,
+    commitments: BTreeMap<u16, Commitments>,
+    sub_shares: BTreeMap<u16, Share>,
+    challenges: BTreeMap<u16, Zeroizing<Vec<u8>>>,
+    openings: BTreeMap<u16, Vec<Share>>,
+    /// Folded intercept of every dealing, set once `check` passes.
+    intercepts: Option<BTreeMap<u16, Vec<u8>>>,
+    rejected: Vec<u16>,
+    revealed: BTreeMap<u16, Zeroizing<Vec<u8>>>,
+}
+
+impl JointRandom {
+    /// Opens a round for member `me` producing `len` random bytes among `members`.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidThreshold` if `k < 2` or `k` exceeds the member count.
+    /// * `MpcError::InvalidShareIndex` / `MpcError::DuplicateShareIndex` on bad identifiers,
+    ///   or if `me` is not a member.
+    /// * `MpcError::EmptyShare` if `len` is zero.
+    pub fn new(me: u16, members: &[u16], k: u8, len: usize) -> Result<Self, MpcError> {
+        let mut set = BTreeSet::new();
+        for &m in members {
+            if m == 0 || m > u8::MAX as u16 {
+                return Err(MpcError::InvalidShareIndex);
+            }
+            if !set.insert(m) {
+                return Err(MpcError::DuplicateShareIndex);
+            }
+        }
+        if k < 2 || k as usize > set.len() {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if !set.contains(&me) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if len == 0 {
+            return Err(MpcError::EmptyShare);
+        }
+        Ok(Self {
+            me,
+            members: set,
+            k,
+            len,
+            own_challenge: None,
+            own_contribution: None,
+            commitments: BTreeMap::new(),
+            sub_shares: BTreeMap::new(),
+            challenges: BTreeMap::new(),
+            openings: BTreeMap::new(),
+            intercepts: None,
+            rejected: Vec::new(),
+            revealed: BTreeMap::new(),
+        })
+    }
+
+    /// Commits to `r` and shares it: returns the commitments to broadcast and one sub-share
+    /// per member (identifier = recipient), including this member's own.
+    ///
+    /// Sub-shares hold `len + BLIND_LEN` bytes: `r`, then a fresh blind (see module docs).
+    ///
+    /// # Errors
+    /// * `MpcError::ShareLengthMismatch` if `r` is not `len` bytes.
+    /// * `MpcError::DuplicateShareIndex` if this member already dealt.
+    /// * `MpcError::RngFailure` on rng failure.
+    pub fn deal<R: EntropySource + ?Sized>(
+        &mut self,
+        r: &[u8],
+        rng: &mut R
+    ) -> Result<(Commitments, Vec<Share>), MpcError> {
+        if r.len() != self.len {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        if self.own_contribution.is_some() {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        let width = self.len + BLIND_LEN;
+        let mut value = Zeroizing::new(vec![0u8; width]);
+        value[..self.len].copy_from_slice(r);
+        let mut challenge = Zeroizing::new(vec![0u8; width - 1]);
+        let mut rows = Zeroizing::new(vec![0u8; (self.k - 1) as usize * width]);
+        rng.fill(&mut value[self.len..]).map_err(|_| MpcError::RngFailure)?;
+        rng.fill(&mut challenge).map_err(|_| MpcError::RngFailure)?;
+        rng.fill(&mut rows[..]).map_err(|_| MpcError::RngFailure)?;
+
+        let mut subs = Vec::with_capacity(self.members.len());
+        for &m in &self.members {
+            let mut sub = vec![0u8; width];
+            evaluate_rows(&rows, Some(&value), GF256(m as u8), &mut sub);
+            subs.push(Share::new(m, sub)?);
+        }
+        let commitments = Commitments {
+            contribution: commit(self.me, &value),
+            challenge: commit_in("TOGM_JOINT_CHALLENGE", self.me, &challenge),
+        };
+        self.own_contribution = Some(value);
+        self.own_challenge = Some(challenge);
+        Ok((commitments, subs))
+    }
+
+    /// Records the commitments broadcast by `from`.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidShareIndex` for a non-member; `MpcError::DuplicateShareIndex` if
+    ///   `from` already committed.
+    pub fn receive_commitment(&mut self, from: u16, commitments: Commitments) -> Result<(), MpcError> {
+        if !self.members.contains(&from) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if self.commitments.contains_key(&from) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        self.commitments.insert(from, commitments);
+        Ok(())
+    }
+
+    /// Records the sub-share of `R_from` addressed to this member.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidShareIndex` for a non-member sender or a misaddressed sub-share.
+    /// * `MpcError::DuplicateShareIndex` if `from` already sent one.
+    /// * `MpcError::ShareLengthMismatch` / `MpcError::UnsupportedVersion` on format mismatch.
+    pub fn receive_share(&mut self, from: u16, sub: Share) -> Result<(), MpcError> {
+        if !self.members.contains(&from) || sub.identifier != self.me {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        sub.gf256_x()?;
+        if sub.value.len() != self.len + BLIND_LEN {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        if self.sub_shares.contains_key(&from) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        self.sub_shares.insert(from, sub);
+        Ok(())
+    }
+
+    /// Whether every member's commitments and sub-share have arrived.
+    pub fn committed(&self) -> bool {
+        self.commitments.len() == self.members.len() && self.sub_shares.len() == self.members.len()
+    }
+
+    /// This member's challenge share, to be broadcast once `committed()`.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until `committed()` (or before `deal`).
+    pub fn challenge_share(&self) -> Result<Zeroizing<Vec<u8>>, MpcError> {
+        match &self.own_challenge {
+            Some(c) if self.committed() => Ok(c.clone()),
+            _ => Err(MpcError::InsufficientShares),
+        }
+    }
+
+    /// Checks the challenge share of `from` against its commitment and records it.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until `committed()`.
+    /// * `MpcError::InvalidShareIndex` for a non-member; `MpcError::DuplicateShareIndex` if
+    ///   already received.
+    /// * `MpcError::IntegrityFailure` if `c` does not match the commitment.
+    pub fn receive_challenge(&mut self, from: u16, c: &[u8]) -> Result<(), MpcError> {
+        if !self.committed() {
+            return Err(MpcError::InsufficientShares);
+        }
+        let expected = self.commitments.get(&from).ok_or(MpcError::InvalidShareIndex)?;
+        if self.challenges.contains_key(&from) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        if !ct_eq(&commit_in("TOGM_JOINT_CHALLENGE", from, c), &expected.challenge) {
+            return Err(MpcError::IntegrityFailure);
+        }
+        self.challenges.insert(from, Zeroizing::new(c.to_vec()));
+        Ok(())
+    }
+
+    /// XOR of all challenge shares, once every one is in.
+    fn challenge(&self) -> Result<Zeroizing<Vec<u8>>, MpcError> {
+        if self.challenges.len() != self.members.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut out = Zeroizing::new(vec![0u8; self.len + BLIND_LEN - 1]);
+        for c in self.challenges.values() {
+            xor_inplace(&mut out, c);
+        }
+        Ok(out)
+    }
+
+    /// This member's blinded openings, one per dealer in ascending member order, to be
+    /// broadcast to every member.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until every challenge share is in.
+    pub fn openings(&self) -> Result<Vec<Share>, MpcError> {
+        let challenge = self.challenge()?;
+        self.sub_shares
+            .values()
+            .map(|sub| Share::new(self.me, fold(&sub.value, &challenge)))
+            .collect()
+    }
+
+    /// Records the openings broadcast by `from` (as returned by its `openings`).
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until every challenge share is in, or if an opening
+    ///   per dealer is missing.
+    /// * `MpcError::InvalidShareIndex` for a non-member or openings not made by `from`.
+    /// * `MpcError::DuplicateShareIndex` if `from` already sent its openings.
+    /// * `MpcError::ShareLengthMismatch` unless every opening is `BLIND_LEN` bytes.
+    pub fn receive_openings(&mut self, from: u16, openings: Vec<Share>) -> Result<(), MpcError> {
+        self.challenge()?;
+        if !self.members.contains(&from) || openings.iter().any(|o| o.identifier != from) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if self.openings.contains_key(&from) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        if openings.len() != self.members.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        if openings.iter().any(|o| o.value.len() != BLIND_LEN) {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        self.openings.insert(from, openings);
+        Ok(())
+    }
+
+    /// Checks every dealing against the openings of all members (see module docs).
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until every member's openings are in.
+    /// * `MpcError::IntegrityFailure` if any dealing fails; `rejected` names the dealers and
+    ///   the round must be abandoned.
+    pub fn check(&mut self) -> Result<(), MpcError> {
+        if self.openings.len() != self.members.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut intercepts = BTreeMap::new();
+        self.rejected.clear();
+        for (i, &dealer) in self.members.iter().enumerate() {
+            let points: Vec<Share> = self.openings.values().map(|o| o[i].clone()).collect();
+            if points.len() > self.k as usize && check_consistency(&points, self.k).is_err() {
+                self.rejected.push(dealer);
+                continue;
+            }
+            intercepts.insert(dealer, interpolate_at(&points[..self.k as usize], 0)?);
+        }
+        if !self.rejected.is_empty() {
+            return Err(MpcError::IntegrityFailure);
+        }
+        self.intercepts = Some(intercepts);
+        Ok(())
+    }
+
+    /// Dealers whose dealing failed the last `check`.
+    pub fn rejected(&self) -> &[u16] {
+        &self.rejected
+    }
+
+    /// This member's share of `XOR R_i` (sum of all sub-shares, blinds dropped).
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until `check` has passed.
+    pub fn share(&self) -> Result<Share, MpcError> {
+        if self.intercepts.is_none() {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut value = vec![0u8; self.len];
+        for sub in self.sub_shares.values() {
+            xor_inplace(&mut value, &sub.value[..self.len]);
+        }
+        Share::new(self.me, value)
+    }
+
+    /// This member's contribution followed by its blind, to be broadcast in the reveal phase.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until `check` has passed (or before `deal`).
+    pub fn contribution(&self) -> Result<Zeroizing<Vec<u8>>, MpcError> {
+        match &self.own_contribution {
+            Some(value) if self.intercepts.is_some() => Ok(value.clone()),
+            _ => Err(MpcError::InsufficientShares),
+        }
+    }
+
+    /// Checks the `contribution()` of `from` against its commitment and dealing, and records it.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` until `check` has passed (reveals must not precede it).
+    /// * `MpcError::InvalidShareIndex` for a non-member; `MpcError::DuplicateShareIndex` if
+    ///   already revealed.
+    /// * `MpcError::ShareLengthMismatch` unless `value` is `len + BLIND_LEN` bytes.
+    /// * `MpcError::IntegrityFailure` if `value` does not match the commitment, or is not the
+    ///   value `from` shared.
+    pub fn reveal(&mut self, from: u16, value: &[u8]) -> Result<(), MpcError> {
+        let intercepts = self.intercepts.as_ref().ok_or(MpcError::InsufficientShares)?;
+        let expected = self.commitments.get(&from).ok_or(MpcError::InvalidShareIndex)?;
+        if self.revealed.contains_key(&from) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        if value.len() != self.len + BLIND_LEN {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        let folded = fold(value, &self.challenge()?);
+        if !ct_eq(&commit(from, value), &expected.contribution) || folded != intercepts[&from] {
+            return Err(MpcError::IntegrityFailure);
+        }
+        self.revealed.insert(from, Zeroizing::new(value[..self.len].to_vec()));
+        Ok(())
+    }
+
+    /// Opens the contribution of a member that withheld it from its sub-shares (as held by
+    /// the other members) and checks it as `reveal` does.
+    ///
+    /// Hand in every sub-share available: up to `⌊(m - k) / 2⌋` wrong ones among `m` are
+    /// corrected. `MpcError::IntegrityFailure` means the round must abort (see module docs).
+    ///
+    /// # Errors
+    /// * As `reveal`, plus the errors of `reconstruct_robust`.
+    pub fn recover(&mut self, from: u16, subs: &[Share]) -> Result<(), MpcError> {
+        let opened = reconstruct_robust(subs, self.k)?;
+        self.reveal(from, &opened.secret)
+    }
+
+    /// Members whose contribution is not yet revealed or recovered.
+    pub fn pending(&self) -> Vec<u16> {
+        self.members.iter().copied().filter(|m| !self.revealed.contains_key(m)).collect()
+    }
+
+    /// `XOR R_i` over all members.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` while any contribution is pending.
+    pub fn output(&self) -> Result<Zeroizing<Vec<u8>>, MpcError> {
+        if self.revealed.len() != self.members.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut out = Zeroizing::new(vec![0u8; self.len]);
+        for r in self.revealed.values() {
+            xor_inplace(&mut out, r);
+        }
+        Ok(out)
+    }
+}
+
+fn ct_eq(a: &[u8; COMMITMENT_LEN], b: &[u8; COMMITMENT_LEN]) -> bool {
+    a.iter().zip(b.iter()).fold(0u8, |d, (x, y)| d | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::reconstruct::reconstruct_secret;
+    use crate::mpc::test_util::MockEntropy;
+
+    /// Runs the commit phase for `members` with contributions `rs`.
+    fn committed(members: &[u16], rs: &[Vec<u8>], rng: &mut MockEntropy) -> Vec<JointRandom> {
+        let mut states: Vec<JointRandom> =
+            members.iter().map(|&m| JointRandom::new(m, members, 2, rs[0].len()).unwrap()).collect();
+        let dealt: Vec<_> = states.iter_mut().zip(rs).map(|(s, r)| s.deal(r, rng).unwrap()).collect();
+        for (i, (c, subs)) in dealt.into_iter().enumerate() {
+            for (j, sub) in subs.into_iter().enumerate() {
+                states[j].receive_commitment(members[i], c).unwrap();
+                states[j].receive_share(members[i], sub).unwrap();
+            }
+        }
+        states
+    }
+
+    /// Exchanges challenge shares and openings; returns each member's `check` result.
+    fn checked(members: &[u16], states: &mut [JointRandom]) -> Vec<Result<(), MpcError>> {
+        let challenges: Vec<_> = states.iter().map(|s| s.challenge_share().unwrap()).collect();
+        for s in states.iter_mut() {
+            for (&m, c) in members.iter().zip(&challenges) {
+                s.receive_challenge(m, c).unwrap();
+            }
+        }
+        let openings: Vec<_> = states.iter().map(|s| s.openings().unwrap()).collect();
+        for s in states.iter_mut() {
+            for (&m, o) in members.iter().zip(&openings) {
+                s.receive_openings(m, o.clone()).unwrap();
+            }
+        }
+        states.iter_mut().map(|s| s.check()).collect()
+    }
+
+    #[test]
+    fn test_joint_random_xor_and_shares() {
+        let mut rng = MockEntropy { state: 6 };
+        let members = [1u16, 2, 3];
+        let rs: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i.wrapping_mul(0x3D) ^ 0xA5; 16]).collect();
+        let mut expected = vec![0u8; 16];
+        for r in &rs {
+            xor_inplace(&mut expected, r);
+        }
+
+        let mut states = committed(&members, &rs, &mut rng);
+        assert_eq!(states[0].share().map(|_| ()), Err(MpcError::InsufficientShares));
+        assert!(checked(&members, &mut states).iter().all(|r| r.is_ok()));
+        // Shared mode: shares open to XOR R_i.
+        let shares: Vec<Share> = states.iter().map(|s| s.share().unwrap()).collect();
+        assert_eq!(reconstruct_secret(&shares[1..], 2).unwrap(), expected);
+
+        // Reveal mode, with member 3 withholding: members 1 and 2 open it from their sub-shares.
+        let values: Vec<_> = states.iter().map(|s| s.contribution().unwrap()).collect();
+        for s in states.iter_mut() {
+            s.reveal(1, &values[0]).unwrap();
+            s.reveal(2, &values[1]).unwrap();
+        }
+        let withheld: Vec<Share> = states[..2].iter().map(|s| s.sub_shares[&3].clone()).collect();
+        assert_eq!(states[0].pending(), vec![3]);
+        states[0].recover(3, &withheld).unwrap();
+        let out = states[0].output().unwrap();
+        assert_eq!(&*out, &expected[..]);
+        let pad = crate::pad::lifecycle::MasterPad::from_material(out, [1; 16]);
+        assert_eq!(pad.total_capacity(), 16);
+    }
+
+    #[test]
+    fn test_reveal_requires_check_and_matches() {
+        let mut rng = MockEntropy { state: 13 };
+        let members = [4u16, 9];
+        let rs = vec![vec![7u8; 8], vec![9u8; 8]];
+
+        // Nothing is revealed before every commitment, sub-share and check is in.
+        let mut early = JointRandom::new(4, &members, 2, 8).unwrap();
+        let (c, _) = early.deal(&rs[0], &mut rng).unwrap();
+        assert_eq!(early.deal(&rs[0], &mut rng).map(|_| ()), Err(MpcError::DuplicateShareIndex));
+        early.receive_commitment(4, c).unwrap();
+        assert_eq!(early.challenge_share().map(|_| ()), Err(MpcError::InsufficientShares));
+        assert_eq!(early.reveal(4, &[0u8; 8 + BLIND_LEN]), Err(MpcError::InsufficientShares));
+        assert_eq!(early.share().map(|_| ()), Err(MpcError::InsufficientShares));
+
+        let mut states = committed(&members, &rs, &mut rng);
+        // A challenge share must match its commitment.
+        assert_eq!(states[0].receive_challenge(9, &[0u8; 8 + BLIND_LEN - 1]), Err(MpcError::IntegrityFailure));
+        assert_eq!(states[0].openings().map(|_| ()), Err(MpcError::InsufficientShares));
+        assert!(checked(&members, &mut states).iter().all(|r| r.is_ok()));
+
+        // A changed contribution fails its commitment.
+        let value = states[1].contribution().unwrap();
+        assert_eq!(states[0].reveal(9, &[0u8; 8 + BLIND_LEN]), Err(MpcError::IntegrityFailure));
+        assert_eq!(states[0].reveal(9, &rs[1]), Err(MpcError::ShareLengthMismatch));
+        assert_eq!(states[0].reveal(5, &value), Err(MpcError::InvalidShareIndex));
+        states[0].reveal(9, &value).unwrap();
+        assert_eq!(states[0].reveal(9, &value), Err(MpcError::DuplicateShareIndex));
+        assert_eq!(states[0].output().map(|_| ()), Err(MpcError::InsufficientShares));
+        assert_eq!(JointRandom::new(3, &members, 2, 8).map(|_| ()), Err(MpcError::InvalidShareIndex));
+    }
+
+    #[test]
+    fn test_inconsistent_dealing_rejected_before_reveal() {
+        let mut rng = MockEntropy { state: 29 };
+        let members = [1u16, 2, 3];
+        let rs: Vec<Vec<u8>> = (1..4u8).map(|i| vec![i; 4]).collect();
+        let mut states = committed(&members, &rs, &mut rng);
+        // Member 3's sub-share for member 2 is off its polynomial.
+        states[1].sub_shares.get_mut(&3).unwrap().value[0] ^= 1;
+
+        let results = checked(&members, &mut states);
+        // Every member aborts before anything is revealed, and all agree on whom to exclude.
+        for (s, result) in states.iter_mut().zip(results) {
+            assert_eq!(result, Err(MpcError::IntegrityFailure));
+            assert_eq!(s.rejected(), &[3]);
+            assert_eq!(s.contribution().map(|_| ()), Err(MpcError::InsufficientShares));
+            assert_eq!(s.share().map(|_| ()), Err(MpcError::InsufficientShares));
+            assert_eq!(s.reveal(1, &[0u8; 4 + BLIND_LEN]), Err(MpcError::InsufficientShares));
+        }
+    }
+}
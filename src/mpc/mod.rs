@@ -1,24 +1,48 @@
 //! Multi-Party Computation (MPC) and Secret Sharing.
 //!
 //! This module implements Threshold Cryptography primitives, primarily
-//! Shamir's Secret Sharing (SSS) over GF(256).
+//! Shamir's Secret Sharing (SSS) over GF(256), and over GF(2^16) for groups of more
+//! than 255 members.
 //!
 //! # Components
-//! - `share`: Definition of a secret share.
+//! - `share`: Definition of a secret share (versioned by field).
 //! - `quorum`: Threshold logic and polynomial generation.
-//! - `reconstruct`: Lagrange interpolation for secret recovery.
-//! - `aggregate`: Operations on shares (e.g., proactive refresh).
+//! - `reconstruct`: Lagrange interpolation for secret recovery, and Berlekamp–Welch
+//!   decoding (`reconstruct_robust`) that corrects faulty shares.
+//! - `aggregate`: Operations on shares (e.g., single-process proactive refresh).
+//! - `wide`: Split and reconstruction over GF(2^16) (up to 65535 shares).
+//! - `auth`: Pairwise information-theoretic share tags.
+//! - `refresh`: Distributed proactive refresh (each party deals a sharing of zero).
+//! - `reshare`: Redistribution from `(k, n)` to `(k', n')` among a new holder set.
+//! - `packed`: Packed Shamir over GF(256), ℓ secret bytes per polynomial (version 3 shares).
+//! - `bgw`: BGW multiplication with degree reduction over pairwise round messages.
+//! - `joint`: Dealer-free joint random generation (`MasterPad = XOR R_i`) with commit, consistency check and reveal.
+//! - `repair`: Restores a lost member's share at its identifier from masked helper contributions.
+//! - `stream`: Chunked split/reconstruct over `Read`/`Write` for large secrets (`std`).
 //!
 //! # Security
-//! - **Constant-Time**: All GF(256) operations are constant-time.
+//! - **Constant-Time**: All GF(256) and GF(2^16) operations are constant-time.
 //! - **Zeroization**: Shares and secrets are zeroized on drop.
-//! - **Integrity**: Shares include integrity checks (optional, depending on implementation).
+//! - **Integrity**: Dealers may tag shares per verifier (`auth`); `reconstruct_authenticated`
+//!   rejects shares whose tags fail, and `reconstruct_secret` refuses tagged shares.
 
 pub mod share;
 pub mod quorum;
 pub mod reconstruct;
 pub mod aggregate;
+pub mod wide;
+pub mod auth;
+pub mod refresh;
+pub mod reshare;
+pub mod packed;
+pub mod bgw;
+pub mod joint;
+pub mod repair;
+#[cfg(feature = "std")]
+pub mod stream;
 pub(crate) mod polynomial;
+#[cfg(test)]
+pub(crate) mod test_util;
 
 use alloc::vec::Vec;
 use crate::entropy::EntropySource;
@@ -26,7 +50,7 @@ use crate::entropy::EntropySource;
 /// Errors for MPC operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MpcError {
-    /// Invalid share index (must be 1..=255).
+    /// Invalid share index (must be non-zero and fit the share's field).
     InvalidShareIndex,
     /// Share value is empty.
     EmptyShare,
@@ -42,6 +66,10 @@ pub enum MpcError {
     IntegrityFailure,
     /// Random number generator failure.
     RngFailure,
+    /// Share format version not supported by this operation.
+    UnsupportedVersion,
+    /// I/O failure on a share stream.
+    Io,
 }
 
 /// Trait for Secret Sharing Schemes.
@@ -56,8 +84,8 @@ pub trait SecretSharingScheme {
     fn split<R: EntropySource + ?Sized>(
         &self, 
         secret: &Self::Secret, 
-        k: u8, 
-        n: u8, 
+        k: u16, 
+        n: u16, 
         rng: &mut R
     ) -> Result<Vec<Self::Share>, Self::Error>;
 
@@ -65,11 +93,11 @@ pub trait SecretSharingScheme {
     fn reconstruct(
         &self, 
         shares: &[Self::Share],
-        k: u8
+        k: u16
     ) -> Result<Self::Secret, Self::Error>;
 }
 
-/// Shamir's Secret Sharing over GF(256).
+/// Shamir's Secret Sharing over GF(256) (at most 255 shares).
 pub struct ShamirGF256;
 
 impl SecretSharingScheme for ShamirGF256 {
@@ -80,18 +108,85 @@ impl SecretSharingScheme for ShamirGF256 {
     fn split<R: EntropySource + ?Sized>(
         &self, 
         secret: &Self::Secret, 
-        k: u8, 
-        n: u8, 
+        k: u16, 
+        n: u16, 
         rng: &mut R
     ) -> Result<Vec<Self::Share>, Self::Error> {
+        let k = u8::try_from(k).map_err(|_| MpcError::InvalidThreshold)?;
+        let n = u8::try_from(n).map_err(|_| MpcError::InvalidShareIndex)?;
         quorum::split_secret(secret, k, n, rng)
     }
 
     fn reconstruct(
         &self, 
         shares: &[Self::Share],
-        k: u8
+        k: u16
     ) -> Result<Self::Secret, Self::Error> {
+        let k = u8::try_from(k).map_err(|_| MpcError::InvalidThreshold)?;
         reconstruct::reconstruct_secret(shares, k)
     }
 }
+
+/// Shamir's Secret Sharing over GF(2^16) (up to 65535 shares, version 2 shares).
+pub struct ShamirGF65536;
+
+impl SecretSharingScheme for ShamirGF65536 {
+    type Share = share::Share;
+    type Secret = Vec<u8>;
+    type Error = MpcError;
+
+    fn split<R: EntropySource + ?Sized>(
+        &self, 
+        secret: &Self::Secret, 
+        k: u16, 
+        n: u16, 
+        rng: &mut R
+    ) -> Result<Vec<Self::Share>, Self::Error> {
+        wide::split_secret(secret, k, n, rng)
+    }
+
+    fn reconstruct(
+        &self, 
+        shares: &[Self::Share],
+        k: u16
+    ) -> Result<Self::Secret, Self::Error> {
+        wide::reconstruct_secret(shares, k)
+    }
+}
+
+/// Packed Shamir over GF(256): `k` is the privacy threshold `t`, reconstruction needs
+/// `t + pack - 1` shares.
+pub struct PackedShamirGF256 {
+    /// Secret bytes per polynomial (ℓ).
+    pub pack: u8,
+}
+
+impl SecretSharingScheme for PackedShamirGF256 {
+    type Share = share::Share;
+    type Secret = Vec<u8>;
+    type Error = MpcError;
+
+    fn split<R: EntropySource + ?Sized>(
+        &self,
+        secret: &Self::Secret,
+        k: u16,
+        n: u16,
+        rng: &mut R
+    ) -> Result<Vec<Self::Share>, Self::Error> {
+        let t = u8::try_from(k).map_err(|_| MpcError::InvalidThreshold)?;
+        let n = u8::try_from(n).map_err(|_| MpcError::InvalidShareIndex)?;
+        packed::split_secret(secret, packed::PackedParams { t, pack: self.pack }, n, rng)
+    }
+
+    fn reconstruct(
+        &self,
+        shares: &[Self::Share],
+        k: u16
+    ) -> Result<Self::Secret, Self::Error> {
+        let t = u8::try_from(k).map_err(|_| MpcError::InvalidThreshold)?;
+        if shares.iter().any(|s| s.value.first() != Some(&self.pack)) {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        packed::reconstruct_secret(shares, t)
+    }
+}
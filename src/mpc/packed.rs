@@ -0,0 +1,285 @@
+//! Packed Shamir secret sharing over GF(2^8).
+//!
+//! Plain Shamir hides one secret byte per polynomial, so every share is as large as the
+//! secret. The packed variant (the whitepaper's `PackedAdditiveShare(MasterPad, t, n)`) hides
+//! ℓ bytes per polynomial of degree `t + ℓ - 2`: the secret bytes are its values at the ℓ
+//! top field points `255, 254, ..., 256 - ℓ`, and shares are its values at `1..=n`. Shares
+//! shrink to `⌈L / ℓ⌉` bytes for an `L`-byte secret.
+//!
+//! # Thresholds
+//! - **Privacy**: any `t - 1` shares are uniformly distributed, independent of the secret.
+//! - **Reconstruction**: any `t + ℓ - 1` shares determine the secret.
+//!
+//! Between the two, `t..t + ℓ - 1` shares leak partial information: the gap is the price of
+//! the smaller shares. With ℓ = 1 both thresholds match plain Shamir with `k = t`.
+//!
+//! # Construction
+//! The polynomial is fixed by the ℓ secret values and `t - 1` uniformly random shares
+//! (identifiers `1..t`); the remaining shares are Lagrange-interpolated from these anchors.
+//!
+//! # Security
+//! - **Constant-Time**: Uses `GF256` arithmetic and the bulk kernels.
+//! - **Zeroization**: Secret rows and random anchors are zeroized after use.
+//! - **Validation**: `n ≤ 255 - ℓ` so no share sits on a secret point.
+
+extern crate alloc;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+use crate::core::gf256::{GF256, mul_add_slice};
+use crate::entropy::EntropySource;
+use crate::mpc::reconstruct::lagrange_weights;
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_PACKED}};
+
+/// Bytes of layout header in a packed share (pack size, padding count).
+const HEADER_LEN: usize = 2;
+
+/// Threshold accounting for packed sharing with privacy threshold `t` and pack size ℓ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedParams {
+    /// Privacy threshold: fewer than `t` shares learn nothing.
+    pub t: u8,
+    /// Secret bytes per polynomial (ℓ).
+    pub pack: u8,
+}
+
+impl PackedParams {
+    /// Most shares that learn nothing about the secret (`t - 1`).
+    pub fn privacy_threshold(&self) -> usize {
+        self.t as usize - 1
+    }
+
+    /// Fewest shares that reconstruct the secret (`t + ℓ - 1`).
+    pub fn reconstruction_threshold(&self) -> usize {
+        self.t as usize + self.pack as usize - 1
+    }
+
+    /// Polynomial degree (`t + ℓ - 2`).
+    pub fn degree(&self) -> usize {
+        self.reconstruction_threshold() - 1
+    }
+
+    /// Share value bytes (excluding the header) for a secret of `secret_len` bytes.
+    pub fn share_len(&self, secret_len: usize) -> usize {
+        secret_len.div_ceil(self.pack as usize)
+    }
+
+    /// Checks the parameters for `n` shares.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidThreshold` if `t < 2`, ℓ = 0, or `n < t + ℓ - 1`.
+    /// * `MpcError::InvalidShareIndex` if `n > 255 - ℓ`.
+    pub fn validate(&self, n: u8) -> Result<(), MpcError> {
+        if self.t < 2 || self.pack == 0 || (n as usize) < self.reconstruction_threshold() {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if n as usize + self.pack as usize > u8::MAX as usize {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        Ok(())
+    }
+}
+
+/// The field point carrying secret byte `j` of every group.
+fn secret_point(j: usize) -> GF256 {
+    GF256(u8::MAX - j as u8)
+}
+
+/// Splits `secret` into `n` packed shares (version 3) with parameters `params`.
+///
+/// # Returns
+/// * `Ok(Vec<Share>)` containing `n` shares with identifiers `1..=n`.
+/// * `Err(MpcError)` on invalid parameters, an empty secret, or rng failure.
+pub fn split_secret<R: EntropySource + ?Sized>(
+    secret: &[u8],
+    params: PackedParams,
+    n: u8,
+    rng: &mut R
+) -> Result<Vec<Share>, MpcError> {
+    if secret.is_empty() {
+        return Err(MpcError::EmptyShare);
+    }
+    params.validate(n)?;
+
+    let pack = params.pack as usize;
+    let groups = params.share_len(secret.len());
+    let padding = (groups * pack - secret.len()) as u8;
+    let randoms = params.privacy_threshold();
+
+    // Anchor rows: row j < ℓ holds secret byte j of every group, then t - 1 random rows
+    // that become shares 1..t verbatim.
+    let mut anchors = Zeroizing::new(vec![0u8; (pack + randoms) * groups]);
+    for (g, chunk) in secret.chunks(pack).enumerate() {
+        for (j, &b) in chunk.iter().enumerate() {
+            anchors[j * groups + g] = b;
+        }
+    }
+    if rng.fill(&mut anchors[pack * groups..]).is_err() {
+        return Err(MpcError::RngFailure);
+    }
+
+    let mut points: Vec<GF256> = (0..pack).map(secret_point).collect();
+    points.extend((1..=randoms).map(|x| GF256(x as u8)));
+
+    let mut shares = Vec::with_capacity(n as usize);
+    for x in 1..=n {
+        let mut value = vec![0u8; HEADER_LEN + groups];
+        value[0] = params.pack;
+        value[1] = padding;
+        if (x as usize) <= randoms {
+            let row = pack + x as usize - 1;
+            value[HEADER_LEN..].copy_from_slice(&anchors[row * groups..(row + 1) * groups]);
+        } else {
+            let lambdas = lagrange_weights(&points, GF256(x));
+            for (row, &lambda) in anchors.chunks_exact(groups).zip(lambdas.iter()) {
+                mul_add_slice(&mut value[HEADER_LEN..], row, lambda);
+            }
+        }
+        shares.push(Share::with_version(SHARE_VERSION_PACKED, x as u16, value)?);
+    }
+    Ok(shares)
+}
+
+/// Reconstructs the secret from at least `t + ℓ - 1` packed shares.
+///
+/// ℓ and the padding are read from the share headers.
+///
+/// # Errors
+/// * `MpcError::UnsupportedVersion` if any share is not a packed share.
+/// * `MpcError::InsufficientShares` with fewer than `t + ℓ - 1` shares.
+/// * `MpcError::ShareLengthMismatch` on a malformed header (padding not below ℓ).
+/// * `MpcError::DuplicateShareIndex`, `ShareLengthMismatch` as for plain Shamir.
+pub fn reconstruct_secret(shares: &[Share], t: u8) -> Result<Vec<u8>, MpcError> {
+    let first = shares.first().ok_or(MpcError::InsufficientShares)?;
+    if first.version != SHARE_VERSION_PACKED {
+        return Err(MpcError::UnsupportedVersion);
+    }
+    // `Share` fields are public: the header may not come from `split_secret`.
+    if first.value.len() <= HEADER_LEN || first.value[0] == 0 || first.value[1] >= first.value[0] {
+        return Err(MpcError::ShareLengthMismatch);
+    }
+    let params = PackedParams { t, pack: first.value[0] };
+    if t < 2 {
+        return Err(MpcError::InvalidThreshold);
+    }
+    if shares.len() < params.reconstruction_threshold() {
+        return Err(MpcError::InsufficientShares);
+    }
+
+    let pack = params.pack as usize;
+    let layout = (first.value.len(), first.value[0], first.value[1]);
+    let mut seen = BTreeSet::new();
+    let mut xs = Vec::with_capacity(shares.len());
+    for share in shares {
+        if share.version != SHARE_VERSION_PACKED {
+            return Err(MpcError::UnsupportedVersion);
+        }
+        // Length first: later shares are as unchecked as the first one.
+        if share.value.len() != layout.0 || (share.value[0], share.value[1]) != (layout.1, layout.2) {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        if share.identifier == 0 || share.identifier as usize + pack > u8::MAX as usize {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if !seen.insert(share.identifier) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        xs.push(GF256(share.identifier as u8));
+    }
+
+    let groups = layout.0 - HEADER_LEN;
+    let mut row = Zeroizing::new(vec![0u8; groups]);
+    let mut secret = vec![0u8; groups * pack];
+    for j in 0..pack {
+        row.fill(0);
+        let lambdas = lagrange_weights(&xs, secret_point(j));
+        for (share, &lambda) in shares.iter().zip(lambdas.iter()) {
+            mul_add_slice(&mut row, &share.value[HEADER_LEN..], lambda);
+        }
+        for (g, &b) in row.iter().enumerate() {
+            secret[g * pack + j] = b;
+        }
+    }
+    secret.truncate(groups * pack - layout.2 as usize);
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::MockEntropy;
+    use crate::mpc::{PackedShamirGF256, SecretSharingScheme, ShamirGF256};
+
+    #[test]
+    fn test_packed_roundtrip() {
+        let mut rng = MockEntropy { state: 4 };
+        let secret: Vec<u8> = (0..1001u32).map(|i| (i * 7 % 256) as u8).collect();
+        let scheme = PackedShamirGF256 { pack: 4 };
+        let shares = scheme.split(&secret, 3, 10, &mut rng).unwrap();
+        assert_eq!(shares.len(), 10);
+        assert_eq!(shares[0].value.len(), HEADER_LEN + 251);
+
+        // t + ℓ - 1 = 6 shares in any order suffice; 5 do not.
+        let subset: Vec<Share> = [9, 2, 7, 0, 4, 5].iter().map(|&i| shares[i].clone()).collect();
+        assert_eq!(scheme.reconstruct(&subset, 3).unwrap(), secret);
+        assert_eq!(scheme.reconstruct(&subset[..5], 3), Err(MpcError::InsufficientShares));
+        assert_eq!(ShamirGF256.reconstruct(&subset, 3), Err(MpcError::UnsupportedVersion));
+    }
+
+    #[test]
+    fn test_params_against_plain_shamir() {
+        let mut rng = MockEntropy { state: 12 };
+        let secret: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(29) ^ 0x5A).collect();
+
+        // ℓ = 1 is plain Shamir with k = t: same thresholds, same share size.
+        let single = PackedParams { t: 3, pack: 1 };
+        assert_eq!(single.privacy_threshold(), 2);
+        assert_eq!(single.reconstruction_threshold(), 3);
+        let plain = ShamirGF256.split(&secret, 3, 5, &mut rng).unwrap();
+        let packed1 = split_secret(&secret, single, 5, &mut rng).unwrap();
+        assert_eq!(packed1[0].value.len() - HEADER_LEN, plain[0].value.len());
+        assert_eq!(reconstruct_secret(&packed1[2..], 3).unwrap(), secret);
+
+        // ℓ = 8: same privacy, 8x smaller shares, reconstruction needs ℓ - 1 more shares.
+        let wide = PackedParams { t: 3, pack: 8 };
+        assert_eq!(wide.privacy_threshold(), single.privacy_threshold());
+        assert_eq!(wide.reconstruction_threshold(), 10);
+        assert_eq!(wide.share_len(secret.len()), plain[0].value.len() / 8);
+
+        // The first t - 1 shares are the random anchors: identical for different secrets.
+        let a = split_secret(&secret, wide, 12, &mut MockEntropy { state: 99 }).unwrap();
+        let b = split_secret(&[0u8; 64], wide, 12, &mut MockEntropy { state: 99 }).unwrap();
+        assert_eq!(a[..2], b[..2]);
+        assert!(a[2..].iter().zip(&b[2..]).all(|(x, y)| x != y));
+    }
+
+    #[test]
+    fn test_packed_validation() {
+        let mut rng = MockEntropy { state: 3 };
+        let p = PackedParams { t: 2, pack: 4 };
+        assert_eq!(p.validate(4), Err(MpcError::InvalidThreshold));
+        assert_eq!(p.validate(252), Err(MpcError::InvalidShareIndex));
+        assert_eq!(p.validate(251), Ok(()));
+        assert_eq!(PackedParams { t: 1, pack: 4 }.validate(10), Err(MpcError::InvalidThreshold));
+        assert_eq!(split_secret(&[], p, 8, &mut rng), Err(MpcError::EmptyShare));
+
+        // Odd-length secrets round-trip through the padding header.
+        let shares = split_secret(b"abcdefg", p, 6, &mut rng).unwrap();
+        assert_eq!(shares[0].value[1], 1);
+        assert_eq!(reconstruct_secret(&shares[1..], 2).unwrap(), b"abcdefg");
+        let mut dup = shares[1..6].to_vec();
+        dup[4] = dup[0].clone();
+        assert_eq!(reconstruct_secret(&dup, 2), Err(MpcError::DuplicateShareIndex));
+
+        // A forged header whose padding exceeds the unpacked length is rejected, not subtracted.
+        let mut forged = split_secret(b"ab", PackedParams { t: 2, pack: 2 }, 4, &mut rng).unwrap();
+        for share in forged.iter_mut() {
+            share.value[1] = 200;
+        }
+        assert_eq!(reconstruct_secret(&forged, 2), Err(MpcError::ShareLengthMismatch));
+        // So is a later share too short to hold a header.
+        let mut short = shares[1..6].to_vec();
+        short[3].value.truncate(1);
+        assert_eq!(reconstruct_secret(&short, 2), Err(MpcError::ShareLengthMismatch));
+    }
+}
@@ -3,33 +3,77 @@
 //! This module provides shared polynomial functions used by quorum, aggregate,
 //! and reconstruct modules to avoid code duplication.
 
-use crate::core::gf256::GF256;
+use crate::core::gf256::{GF256, mul_slice};
+use crate::core::xor::xor_inplace;
 
-/// Evaluates a polynomial at a given point x using Horner's method.
+/// Evaluates many polynomials of the same degree at one point x, byte-parallel.
 ///
-/// f(x) = c[0] + c[1]*x + ... + c[k-1]*x^(k-1)
-///
-/// # Arguments
-/// * `coeffs` - Coefficients [c0, c1, ..., ck-1]
-/// * `x` - The point to evaluate at
-///
-/// # Returns
-/// * The value f(x)
-#[inline(always)]
-pub(crate) fn evaluate_polynomial(coeffs: &[GF256], x: GF256) -> GF256 {
-    // Horner's method:
-    // result = c[k-1]
-    // result = result * x + c[k-2]
-    // ...
-    // result = result * x + c[0]
-    
-    if coeffs.is_empty() {
-        return GF256(0);
+/// `rows` holds the coefficient rows c1..c(k-1) back to back, each `out.len()` bytes wide
+/// (row d carries c_{d+1} for every position). `intercept` is c0 (zero if `None`).
+/// Writes f(x) = c0 + c1*x + ... + c(k-1)*x^(k-1) per position into `out`,
+/// using Horner's method on the bulk GF(2^8) kernels.
+pub(crate) fn evaluate_rows(rows: &[u8], intercept: Option<&[u8]>, x: GF256, out: &mut [u8]) {
+    let len = out.len();
+    debug_assert!(len == 0 || rows.len() % len == 0, "evaluate_rows: ragged rows");
+    out.fill(0);
+    if len > 0 {
+        for row in rows.chunks_exact(len).rev() {
+            xor_inplace(out, row);
+            mul_slice(out, x);
+        }
+    }
+    if let Some(c0) = intercept {
+        xor_inplace(out, c0);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Evaluates a polynomial at a given point x using Horner's method (scalar reference
+    /// for `evaluate_rows`).
+    ///
+    /// f(x) = c[0] + c[1]*x + ... + c[k-1]*x^(k-1)
+    ///
+    /// # Arguments
+    /// * `coeffs` - Coefficients [c0, c1, ..., ck-1]
+    /// * `x` - The point to evaluate at
+    ///
+    /// # Returns
+    /// * The value f(x)
+    #[inline(always)]
+    pub(crate) fn evaluate_polynomial(coeffs: &[GF256], x: GF256) -> GF256 {
+        // Horner's method:
+        // result = c[k-1]
+        // result = result * x + c[k-2]
+        // ...
+        // result = result * x + c[0]
+
+        if coeffs.is_empty() {
+            return GF256(0);
+        }
+
+        let mut result = *coeffs.last().unwrap();
+        for coeff in coeffs.iter().rev().skip(1) {
+            result = result * x + *coeff;
+        }
+        result
     }
 
-    let mut result = *coeffs.last().unwrap();
-    for coeff in coeffs.iter().rev().skip(1) {
-        result = result * x + *coeff;
+    #[test]
+    fn test_evaluate_rows_matches_scalar() {
+        let width = 5;
+        let rows: Vec<u8> = (0..3 * width).map(|i| (i as u8).wrapping_mul(29).wrapping_add(7)).collect();
+        let intercept: Vec<u8> = (0..width).map(|i| 0xA0 ^ i as u8).collect();
+        for x in [0u8, 1, 2, 0x53, 0xFF] {
+            let mut out = vec![0u8; width];
+            evaluate_rows(&rows, Some(&intercept), GF256(x), &mut out);
+            for (pos, &got) in out.iter().enumerate() {
+                let mut coeffs = vec![GF256(intercept[pos])];
+                coeffs.extend(rows.chunks_exact(width).map(|row| GF256(row[pos])));
+                assert_eq!(GF256(got), evaluate_polynomial(&coeffs, GF256(x)));
+            }
+        }
     }
-    result
 }
@@ -1,7 +1,19 @@
 //! Quorum logic for Shamir's Secret Sharing over GF(256).
 //!
 //! This module implements the threshold logic and polynomial generation required
-//! to split secrets into shares.
+//! to split secrets into shares, and the quorum planner for large groups.
+//!
+//! # Quorum Partitioning
+//! For n > 50 a `QuorumPlan` splits the members into overlapping committees of size
+//! `c = O(log n)`. The secret is shared among the `m` committees (threshold `top_k`), and
+//! each committee's share among its members (threshold `committee_k`), so sharing and
+//! reconstruction run per committee in parallel and total work is O(n log n). Reconstruction
+//! combines `committee_k`-of-`c` inside committees, then `top_k`-of-`m` across them.
+//!
+//! # Weighted Thresholds
+//! A `WeightedPolicy` gives member `i` `weights[i]` consecutive share identifiers of one
+//! `ShamirGF256` sharing, so a set of members is qualified when its total weight reaches the
+//! threshold (`reconstruct::reconstruct_weighted` checks this before interpolating).
 //!
 //! # Security
 //! - **Constant-Time**: Uses `GF256` arithmetic which is branch-free.
@@ -13,12 +25,19 @@
 //! - Section 2.4: Share Calculation.
 
 extern crate alloc;
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
 use alloc::vec::Vec;
+use core::ops::Range;
 use zeroize::Zeroizing;
 use crate::core::gf256::GF256;
-use crate::mpc::{MpcError, share::Share};
+use crate::mpc::{MpcError, SecretSharingScheme, ShamirGF256, share::Share};
+use crate::mpc::reconstruct::reconstruct_secret;
 use crate::entropy::EntropySource;
-use crate::mpc::polynomial::evaluate_polynomial;
+use crate::mpc::polynomial::evaluate_rows;
+
+/// Bytes of secret evaluated per pass in `split_secret`.
+const SPLIT_CHUNK: usize = 4096;
 
 /// Splits a secret into `n` shares, requiring `k` shares to reconstruct.
 ///
@@ -55,64 +74,287 @@ pub fn split_secret<R: EntropySource + ?Sized>(
     debug_assert!(k >= 1, "k must be at least 1");
 
     // Initialize storage for shares
-    // We process the secret byte-by-byte, but we want to return shares
-    // where each share contains the byte-slices for that share index.
     // share_values[i] corresponds to share with index i+1.
     let mut share_values: Vec<Vec<u8>> = Vec::with_capacity(n as usize);
     for _ in 0..n {
         share_values.push(Vec::with_capacity(secret.len()));
     }
 
-    // Temporary buffer for random coefficients (k-1 coefficients per byte)
-    // We allocate this once and reuse it to minimize allocations.
-    // Wrapped in Zeroizing to ensure cleanup.
-    let mut random_buf = Zeroizing::new(vec![0u8; (k - 1) as usize]);
+    // The secret is processed in chunks: for each chunk we draw k-1 random coefficient
+    // rows (row d holds a_d for every byte of the chunk) and evaluate
+    // f(x) = a0 + a1*x + ... + ak-1*x^(k-1) for all bytes at once via Horner with the
+    // bulk GF(2^8) kernels. Buffers are wrapped in Zeroizing to ensure cleanup.
+    let mut random_rows = Zeroizing::new(vec![0u8; (k - 1) as usize * SPLIT_CHUNK]);
+    let mut eval = Zeroizing::new(vec![0u8; SPLIT_CHUNK]);
 
-    for &byte in secret {
-        // 1. Generate random coefficients for f(x) = a0 + a1*x + ... + ak-1*x^(k-1)
-        // a0 is the secret byte.
-        // a1..ak-1 are random.
-        
-        // Fill random buffer
-        if k > 1 {
-            match rng.fill(&mut random_buf) {
-                Ok(_) => {},
-                Err(_) => return Err(MpcError::RngFailure),
-            }
-        }
+    for chunk in secret.chunks(SPLIT_CHUNK) {
+        let len = chunk.len();
+        let rows = &mut random_rows[..(k - 1) as usize * len];
 
-        // Construct polynomial coefficients
-        // coefficients[0] = secret
-        // coefficients[1..] = random
-        let mut coeffs: Vec<GF256> = Vec::with_capacity(k as usize);
-        coeffs.push(GF256(byte));
-        for &r in random_buf.iter() {
-            coeffs.push(GF256(r));
+        // 1. Generate random coefficients a1..ak-1 (a0 is the secret chunk)
+        if rng.fill(rows).is_err() {
+            return Err(MpcError::RngFailure);
         }
-        // Wrap coefficients in Zeroizing to ensure they are wiped
-        let coeffs = Zeroizing::new(coeffs);
 
         // 2. Evaluate polynomial for each share x = 1..=n
         for i in 0..n {
             let x = GF256(i + 1); // Share indices are 1-based
-            let y = evaluate_polynomial(&coeffs, x);
-            share_values[i as usize].push(y.0);
+            let y = &mut eval[..len];
+            evaluate_rows(rows, Some(chunk), x, y);
+            share_values[i as usize].extend_from_slice(y);
         }
     }
 
     // 3. Construct Share objects
     let mut shares = Vec::with_capacity(n as usize);
     for (i, values) in share_values.into_iter().enumerate() {
-        shares.push(Share::new((i + 1) as u8, values)?);
+        shares.push(Share::new((i + 1) as u16, values)?);
     }
 
     Ok(shares)
 }
 
+/// A member's share inside one committee of a `QuorumPlan`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitteeShare {
+    /// Committee index (0-based; its top-level share identifier is `committee + 1`).
+    pub committee: u16,
+    /// Group-wide member identifier.
+    pub member: u16,
+    /// Share of the committee's value; `identifier` is the member's 1-based seat.
+    pub share: Share,
+}
+
+/// Guaranteed tolerance of a `QuorumPlan`.
+///
+/// Both are lower bounds from the maximum committee multiplicity `r` (committees per
+/// member): an adversary needs `committee_k` seats in each of `top_k` committees, and every
+/// member it controls fills at most `r` seats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CorruptionBound {
+    /// Corrupted members that learn nothing: `⌈top_k · committee_k / r⌉ - 1`.
+    pub privacy: usize,
+    /// Offline members that cannot prevent reconstruction:
+    /// `⌈(m - top_k + 1) · (c - committee_k + 1) / r⌉ - 1`.
+    pub availability: usize,
+}
+
+/// Hierarchical two-level sharing over overlapping committees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumPlan {
+    committees: Vec<Vec<u16>>,
+    committee_k: u8,
+    top_k: u8,
+}
+
+impl QuorumPlan {
+    /// Cyclic committees of `committee_size` consecutive members, each starting
+    /// `committee_size - overlap` after the previous one.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidThreshold` if `overlap >= committee_size`, the group is not larger
+    ///   than one committee, `committee_k` is outside `2..=committee_size`, or `top_k` outside
+    ///   `2..=m`.
+    /// * `MpcError::InvalidShareIndex` if more than 255 committees result.
+    /// * `MpcError::DuplicateShareIndex` on a repeated member.
+    pub fn new(members: &[u16], committee_size: u8, overlap: u8, committee_k: u8, top_k: u8) -> Result<Self, MpcError> {
+        let n = members.len();
+        let c = committee_size as usize;
+        if overlap >= committee_size || n <= c || committee_k < 2 || committee_k > committee_size {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let mut sorted = members.to_vec();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        let stride = c - overlap as usize;
+        let m = n.div_ceil(stride);
+        if m > u8::MAX as usize {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if top_k < 2 || top_k as usize > m {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let committees = (0..m)
+            .map(|j| (0..c).map(|i| members[(j * stride + i) % n]).collect())
+            .collect();
+        Ok(Self { committees, committee_k, top_k })
+    }
+
+    /// Default plan: committees of `4⌈log2 n⌉` (8..=64) members overlapping by half, with
+    /// majority thresholds at both levels.
+    pub fn auto(members: &[u16]) -> Result<Self, MpcError> {
+        let n = members.len().max(2);
+        let log2 = (usize::BITS - (n - 1).leading_zeros()) as usize;
+        let c = (4 * log2).clamp(8, 64).min(n.saturating_sub(1)).max(2);
+        let overlap = c / 2;
+        let m = n.div_ceil(c - overlap);
+        Self::new(members, c as u8, overlap as u8, (c / 2 + 1) as u8, (m / 2 + 1).max(2).min(m) as u8)
+    }
+
+    /// The committees, in top-level identifier order.
+    pub fn committees(&self) -> &[Vec<u16>] {
+        &self.committees
+    }
+
+    /// Thresholds `(committee_k, top_k)`.
+    pub fn thresholds(&self) -> (u8, u8) {
+        (self.committee_k, self.top_k)
+    }
+
+    /// Most committees any single member sits on.
+    pub fn multiplicity(&self) -> usize {
+        let mut seats: BTreeMap<u16, usize> = BTreeMap::new();
+        for m in self.committees.iter().flatten() {
+            *seats.entry(*m).or_default() += 1;
+        }
+        seats.values().copied().max().unwrap_or(0)
+    }
+
+    /// Guaranteed privacy and availability tolerance (see `CorruptionBound`).
+    pub fn tolerance(&self) -> CorruptionBound {
+        let r = self.multiplicity().max(1);
+        let m = self.committees.len();
+        let c = self.committees.first().map_or(0, |c| c.len());
+        let (ck, tk) = (self.committee_k as usize, self.top_k as usize);
+        CorruptionBound {
+            privacy: (tk * ck).div_ceil(r) - 1,
+            availability: ((m - tk + 1) * (c - ck + 1)).div_ceil(r) - 1,
+        }
+    }
+
+    /// Shares `secret` across the committees; every member gets one `CommitteeShare` per seat.
+    pub fn split<R: EntropySource + ?Sized>(&self, secret: &[u8], rng: &mut R) -> Result<Vec<CommitteeShare>, MpcError> {
+        let m = self.committees.len() as u8;
+        let top = split_secret(secret, self.top_k, m, rng)?;
+        let mut out = Vec::with_capacity(self.committees.len() * self.committees[0].len());
+        for (j, (committee, top_share)) in self.committees.iter().zip(&top).enumerate() {
+            let seats = split_secret(&top_share.value, self.committee_k, committee.len() as u8, rng)?;
+            for (&member, share) in committee.iter().zip(seats) {
+                out.push(CommitteeShare { committee: j as u16, member, share });
+            }
+        }
+        Ok(out)
+    }
+
+    /// Reconstructs hierarchically: every committee with `committee_k` seats present opens its
+    /// top-level share, and `top_k` of those open the secret.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidShareIndex` if a share's member does not hold that seat.
+    /// * `MpcError::InsufficientShares` if fewer than `top_k` committees can open.
+    /// * Otherwise as `reconstruct_secret`.
+    pub fn reconstruct(&self, shares: &[CommitteeShare]) -> Result<Vec<u8>, MpcError> {
+        let mut by_committee: BTreeMap<u16, Vec<Share>> = BTreeMap::new();
+        for cs in shares {
+            let seat = self
+                .committees
+                .get(cs.committee as usize)
+                .and_then(|c| c.get((cs.share.identifier as usize).wrapping_sub(1)));
+            if seat != Some(&cs.member) {
+                return Err(MpcError::InvalidShareIndex);
+            }
+            by_committee.entry(cs.committee).or_default().push(cs.share.clone());
+        }
+
+        let mut top = Vec::with_capacity(self.top_k as usize);
+        for (j, seats) in by_committee {
+            if top.len() == self.top_k as usize {
+                break;
+            }
+            if seats.len() >= self.committee_k as usize {
+                let value = Zeroizing::new(reconstruct_secret(&seats, self.committee_k)?);
+                top.push(Share::new(j + 1, value.to_vec())?);
+            }
+        }
+        if top.len() < self.top_k as usize {
+            return Err(MpcError::InsufficientShares);
+        }
+        reconstruct_secret(&top, self.top_k)
+    }
+}
+
+/// Weighted access structure over one GF(256) sharing.
+///
+/// Identifiers are assigned in member order: member 0 holds `1..=weights[0]`, member 1 the
+/// next `weights[1]`, and so on. A member of weight zero holds no share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedPolicy {
+    weights: Vec<u8>,
+    threshold: u8,
+}
+
+impl WeightedPolicy {
+    /// Creates a policy where members reaching total weight `threshold` can reconstruct.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidThreshold` if `threshold < 2` or it exceeds the total weight.
+    /// * `MpcError::InvalidShareIndex` if the total weight exceeds 255 identifiers.
+    pub fn new(weights: Vec<u8>, threshold: u8) -> Result<Self, MpcError> {
+        let total: usize = weights.iter().map(|&w| w as usize).sum();
+        if total > 255 {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if threshold < 2 || threshold as usize > total {
+            return Err(MpcError::InvalidThreshold);
+        }
+        Ok(Self { weights, threshold })
+    }
+
+    /// Per-member weights, in member order.
+    pub fn weights(&self) -> &[u8] {
+        &self.weights
+    }
+
+    /// Total weight required to reconstruct.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Total number of identifiers dealt.
+    pub fn total_weight(&self) -> u8 {
+        // Bounded by `new`.
+        self.weights.iter().map(|&w| w as usize).sum::<usize>() as u8
+    }
+
+    /// Identifiers held by `member`, or `None` if out of range.
+    pub fn identifiers(&self, member: usize) -> Option<Range<u16>> {
+        let w = *self.weights.get(member)? as u16;
+        let start = 1 + self.weights[..member].iter().map(|&w| w as u16).sum::<u16>();
+        Some(start..start + w)
+    }
+
+    /// The member holding `identifier`.
+    pub fn owner(&self, identifier: u16) -> Option<usize> {
+        (0..self.weights.len()).find(|&m| self.identifiers(m).is_some_and(|r| r.contains(&identifier)))
+    }
+
+    /// Total weight of a set of members (repeats and unknown members count once and zero).
+    pub fn weight_of(&self, members: &[usize]) -> usize {
+        let set: BTreeSet<usize> = members.iter().copied().collect();
+        set.iter().filter_map(|&m| self.weights.get(m)).map(|&w| w as usize).sum()
+    }
+
+    /// Whether `members` together reach the threshold.
+    pub fn is_qualified(&self, members: &[usize]) -> bool {
+        self.weight_of(members) >= self.threshold as usize
+    }
+
+    /// Splits `secret` and returns each member's shares, in member order.
+    pub fn split<R: EntropySource + ?Sized>(&self, secret: &[u8], rng: &mut R) -> Result<Vec<Vec<Share>>, MpcError> {
+        let mut shares = ShamirGF256
+            .split(&secret.to_vec(), self.threshold as u16, self.total_weight() as u16, rng)?
+            .into_iter();
+        Ok(self.weights.iter().map(|&w| shares.by_ref().take(w as usize).collect()).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::entropy::{EntropySource, EntropyError};
+    use crate::mpc::polynomial::tests::evaluate_polynomial;
     
     /// Mock entropy source for deterministic testing
     struct MockEntropy {
@@ -142,7 +384,7 @@ mod tests {
 
         assert_eq!(shares.len(), 3);
         for (i, share) in shares.iter().enumerate() {
-            assert_eq!(share.identifier, (i + 1) as u8);
+            assert_eq!(share.identifier, (i + 1) as u16);
             assert_eq!(share.value.len(), 2);
         }
     }
@@ -174,4 +416,101 @@ mod tests {
         assert_eq!(evaluate_polynomial(&coeffs, GF256(2)), GF256(5));
         assert_eq!(evaluate_polynomial(&coeffs, GF256(3)), GF256(7));
     }
+
+    /// Simulates the plan for `n` members: worst-case contiguous failures and corruptions
+    /// at the reported bounds.
+    fn simulate(n: u16) {
+        let mut rng = MockEntropy { fill_val: 0x21 };
+        let members: Vec<u16> = (1..=n).collect();
+        let plan = QuorumPlan::auto(&members).unwrap();
+        let (ck, tk) = plan.thresholds();
+        let bound = plan.tolerance();
+        assert!(bound.privacy >= 1 && bound.availability >= 1);
+
+        let secret = vec![0xC3; 32];
+        let shares = plan.split(&secret, &mut rng).unwrap();
+        let seats: usize = plan.committees().iter().map(|c| c.len()).sum();
+        assert_eq!(shares.len(), seats);
+        assert_eq!(plan.reconstruct(&shares).unwrap(), secret);
+
+        for start in [0u16, n / 3, n - 5] {
+            let window: Vec<u16> = (0..n).map(|i| members[((start + i) % n) as usize]).collect();
+
+            // Availability: the first `availability` members of the window go offline.
+            let offline = &window[..bound.availability];
+            let alive: Vec<CommitteeShare> = shares.iter().filter(|s| !offline.contains(&s.member)).cloned().collect();
+            assert_eq!(plan.reconstruct(&alive).unwrap(), secret);
+
+            // Privacy: `privacy` colluders never fill `committee_k` seats in `top_k` committees.
+            let corrupt = &window[..bound.privacy];
+            let broken = plan
+                .committees()
+                .iter()
+                .filter(|c| c.iter().filter(|m| corrupt.contains(m)).count() >= ck as usize)
+                .count();
+            assert!(broken < tk as usize, "n={} broken={} top_k={}", n, broken, tk);
+            let leaked: Vec<CommitteeShare> = shares.iter().filter(|s| corrupt.contains(&s.member)).cloned().collect();
+            assert_eq!(plan.reconstruct(&leaked), Err(MpcError::InsufficientShares));
+        }
+    }
+
+    #[test]
+    fn test_quorum_plan_n100() {
+        simulate(100);
+    }
+
+    #[test]
+    fn test_quorum_plan_n500() {
+        simulate(500);
+    }
+
+    #[test]
+    fn test_quorum_plan_validation() {
+        let members: Vec<u16> = (1..=60).collect();
+        let plan = QuorumPlan::new(&members, 12, 6, 7, 6).unwrap();
+        assert_eq!(plan.committees().len(), 10);
+        assert_eq!(plan.multiplicity(), 2);
+        // ⌈6·7/2⌉ - 1 and ⌈5·6/2⌉ - 1.
+        assert_eq!(plan.tolerance(), CorruptionBound { privacy: 20, availability: 14 });
+
+        assert_eq!(QuorumPlan::new(&members, 12, 12, 7, 6), Err(MpcError::InvalidThreshold));
+        assert_eq!(QuorumPlan::new(&members[..12], 12, 6, 7, 2), Err(MpcError::InvalidThreshold));
+        assert_eq!(QuorumPlan::new(&members, 12, 6, 7, 11), Err(MpcError::InvalidThreshold));
+        let mut dup = members.clone();
+        dup[3] = 1;
+        assert_eq!(QuorumPlan::new(&dup, 12, 6, 7, 6), Err(MpcError::DuplicateShareIndex));
+
+        // A share presented for someone else's seat is rejected.
+        let mut rng = MockEntropy { fill_val: 3 };
+        let mut shares = plan.split(&[1, 2, 3], &mut rng).unwrap();
+        shares[0].member = 59;
+        assert_eq!(plan.reconstruct(&shares), Err(MpcError::InvalidShareIndex));
+    }
+
+    #[test]
+    fn test_weighted_policy() {
+        // A founder of weight 3, a home server of weight 2 and three members of weight 1.
+        let policy = WeightedPolicy::new(vec![3, 2, 1, 1, 1], 4).unwrap();
+        assert_eq!(policy.total_weight(), 8);
+        assert_eq!(policy.identifiers(0), Some(1..4));
+        assert_eq!(policy.identifiers(1), Some(4..6));
+        assert_eq!(policy.identifiers(4), Some(8..9));
+        assert_eq!(policy.identifiers(5), None);
+        assert_eq!(policy.owner(5), Some(1));
+        assert_eq!(policy.owner(9), None);
+
+        assert!(policy.is_qualified(&[0, 2]));
+        assert!(policy.is_qualified(&[1, 3, 4]));
+        assert!(!policy.is_qualified(&[2, 3, 4]));
+        assert!(!policy.is_qualified(&[0, 0]));
+
+        let mut rng = MockEntropy { fill_val: 7 };
+        let shares = policy.split(b"weighted", &mut rng).unwrap();
+        let counts: Vec<usize> = shares.iter().map(|s| s.len()).collect();
+        assert_eq!(counts, vec![3, 2, 1, 1, 1]);
+        assert_eq!(shares[1][1].identifier, 5);
+
+        assert_eq!(WeightedPolicy::new(vec![1, 1], 3), Err(MpcError::InvalidThreshold));
+        assert_eq!(WeightedPolicy::new(vec![200, 100], 3), Err(MpcError::InvalidShareIndex));
+    }
 }
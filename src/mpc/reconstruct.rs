@@ -1,7 +1,9 @@
 //! Secret reconstruction from shares.
 //!
 //! This module implements Lagrange interpolation over GF(256) to reconstruct
-//! the original secret from a threshold number of shares.
+//! the original secret from a threshold number of shares, and to evaluate the shared
+//! polynomial at other points (`interpolate_at`). `ReconstructionContext` caches the
+//! Lagrange weights for a fixed shareholder set across many blocks.
 //!
 //! # Security
 //! - **Constant-Time**: Uses `GF256` arithmetic.
@@ -11,10 +13,13 @@
 //! - Section 2.5: Secret Reconstruction.
 
 extern crate alloc;
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
-use zeroize::Zeroizing;
-use crate::core::gf256::GF256;
-use crate::mpc::{MpcError, share::Share};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+use crate::core::gf256::{GF256, mul_add_slice};
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF256, SHARE_VERSION_GF65536}};
+use crate::mpc::auth::{reject_tagged, verify_shares, VerifierKeys};
+use crate::mpc::quorum::WeightedPolicy;
 
 /// Reconstructs the secret from a list of shares.
 ///
@@ -27,7 +32,16 @@ use crate::mpc::{MpcError, share::Share};
 /// # Returns
 /// * `Ok(Vec<u8>)` - The reconstructed secret.
 /// * `Err(MpcError)` - If inputs are invalid (mismatch lengths, duplicates, insufficient shares, etc.).
+///
+/// Tags cannot be checked without a verifier's keys, so shares carrying any tag are rejected
+/// with `MpcError::IntegrityFailure`; use `reconstruct_authenticated` for those.
 pub fn reconstruct_secret(shares: &[Share], k: u8) -> Result<Vec<u8>, MpcError> {
+    reject_tagged(shares)?;
+    reconstruct_unchecked(shares, k)
+}
+
+/// `reconstruct_secret` without the tag check, for shares already verified.
+fn reconstruct_unchecked(shares: &[Share], k: u8) -> Result<Vec<u8>, MpcError> {
     if shares.is_empty() {
         return Err(MpcError::InsufficientShares);
     }
@@ -36,68 +50,400 @@ pub fn reconstruct_secret(shares: &[Share], k: u8) -> Result<Vec<u8>, MpcError>
         return Err(MpcError::InsufficientShares);
     }
 
-    let num_shares = shares.len();
+    interpolate_unchecked(shares, 0)
+}
+
+/// Evaluates the polynomial through `shares` at an arbitrary point `x`.
+///
+/// With `x = 0` this is the secret; with `x` a member identifier it is that member's share
+/// (used by `reshare` and for share repair). No threshold is checked: `m` shares determine a
+/// polynomial of degree below `m`.
+///
+/// Like `reconstruct_secret`, shares carrying a tag are refused: at `x = 0` this would
+/// otherwise open unverified shares.
+///
+/// # Errors
+/// * `MpcError::InsufficientShares` if `shares` is empty.
+/// * `MpcError::IntegrityFailure` if any share carries a tag.
+/// * Otherwise as `reconstruct_secret` (mismatched lengths, duplicates, non-GF(2^8) shares).
+pub fn interpolate_at(shares: &[Share], x: u8) -> Result<Vec<u8>, MpcError> {
+    reject_tagged(shares)?;
+    interpolate_unchecked(shares, x)
+}
+
+/// `interpolate_at` without the tag check, for shares already verified.
+fn interpolate_unchecked(shares: &[Share], x: u8) -> Result<Vec<u8>, MpcError> {
+    if shares.is_empty() {
+        return Err(MpcError::InsufficientShares);
+    }
     let share_len = shares[0].value.len();
 
     // 1. Validation
-    // Check all shares have the same length
-    for share in shares {
-        if share.value.len() != share_len {
-            return Err(MpcError::ShareLengthMismatch);
-        }
-    }
+    let xs = gf256_points(shares)?;
 
-    // Check for duplicate indices
-    // Optimized to O(N) using a boolean array
-    let mut seen = [false; 256];
-    for share in shares {
-        let idx = share.identifier as usize;
-        if seen[idx] {
-            return Err(MpcError::DuplicateShareIndex);
-        }
-        seen[idx] = true;
+    // 2. Lagrange basis polynomials at x
+    let lambdas = lagrange_weights(&xs, GF256(x));
+
+    // 3. Interpolate with bulk multiply-accumulate
+    // f(x) = sum_{j} (share_j * lambda_j), whole shares at a time
+    let mut out = vec![0u8; share_len];
+    for (share, &lambda) in shares.iter().zip(lambdas.iter()) {
+        mul_add_slice(&mut out, &share.value, lambda);
     }
 
-    // 2. Precompute Lagrange basis polynomials at x=0
-    // lambda_j = product_{m != j} (x_m / (x_m - x_j))
-    // Note: in GF(2^8), subtraction is addition (XOR).
-    // So lambda_j = product_{m != j} (x_m / (x_m + x_j))
-    
+    Ok(out)
+}
+
+/// Lagrange basis polynomials of the points `xs`, evaluated at `x`.
+///
+/// lambda_j = product_{m != j} ((x - x_m) / (x_j - x_m))
+/// Note: in GF(2^8), subtraction is addition (XOR).
+/// So lambda_j = product_{m != j} ((x + x_m) / (x_j + x_m)); at x = 0 the numerator is
+/// product_{m != j} x_m. `xs` must be distinct.
+pub(crate) fn lagrange_weights(xs: &[GF256], x: GF256) -> Zeroizing<Vec<GF256>> {
     // Zeroizing wrapper to protect sensitive Lagrange coefficients
-    let mut lambdas = Zeroizing::new(Vec::with_capacity(num_shares));
-    for j in 0..num_shares {
-        let xj = GF256(shares[j].identifier);
+    let mut lambdas = Zeroizing::new(Vec::with_capacity(xs.len()));
+    for (j, &xj) in xs.iter().enumerate() {
         let mut numerator = GF256(1);
         let mut denominator = GF256(1);
 
-        for m in 0..num_shares {
+        for (m, &xm) in xs.iter().enumerate() {
             if j == m {
                 continue;
             }
-            let xm = GF256(shares[m].identifier);
-            
-            numerator *= xm;
+            numerator *= x + xm;
             denominator *= xm + xj;
         }
 
         // lambda_j = numerator * (denominator)^-1
         lambdas.push(numerator * denominator.inv());
     }
+    lambdas
+}
+
+/// Result of `reconstruct_robust`: the secret and the shareholders caught cheating.
+#[derive(Debug, Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct RobustSecret {
+    /// The reconstructed secret.
+    pub secret: Vec<u8>,
+    /// Identifiers of shares that disagree with the decoded polynomial, in input order.
+    pub cheaters: Vec<u16>,
+}
+
+/// Reconstructs the secret while correcting up to `⌊(m - k) / 2⌋` faulty shares.
+///
+/// Every byte position is a Reed–Solomon codeword of length `m = shares.len()` and
+/// dimension `k`, decoded with Berlekamp–Welch: solve `Q(x_i) = y_i E(x_i)` for a monic
+/// error locator `E` of degree `e = ⌊(m - k) / 2⌋` and `Q` of degree `< k + e`, then
+/// `P = Q / E`. A share is reported as a cheater if it disagrees with `P` at any position.
+/// With exactly `k` shares nothing can be corrected and this is plain interpolation.
+///
+/// The linear system is solved by Gaussian elimination with mask-selected pivots and the
+/// division runs a fixed number of steps, so the work done is independent of the share
+/// values. Cost is `O(m (k + 2e)^2)` field operations per secret byte.
+///
+/// # Errors
+/// * `MpcError::InsufficientShares` if fewer than `k` shares are given.
+/// * `MpcError::InvalidThreshold` if `k` is zero.
+/// * `MpcError::IntegrityFailure` if some byte position has more than `e` errors, or a
+///   share carries a tag (check those with `auth::verify_shares` and strip them first).
+/// * Otherwise as `reconstruct_secret` (GF(2^8) shares only).
+pub fn reconstruct_robust(shares: &[Share], k: u8) -> Result<RobustSecret, MpcError> {
+    reject_tagged(shares)?;
+    if k == 0 {
+        return Err(MpcError::InvalidThreshold);
+    }
+    if shares.is_empty() || shares.len() < k as usize {
+        return Err(MpcError::InsufficientShares);
+    }
+    let xs = gf256_points(shares)?;
 
-    // 3. Reconstruct secret byte-by-byte
-    // S[p] = sum_{j} (share_j[p] * lambda_j)
-    
-    let mut secret = Vec::with_capacity(share_len);
-    for p in 0..share_len {
-        let mut sum = GF256(0);
-        for j in 0..num_shares {
-            let y = GF256(shares[j].value[p]);
-            sum += y * lambdas[j];
+    let m = shares.len();
+    let k = k as usize;
+    let e = (m - k) / 2;
+    let q_len = k + e;
+    let unknowns = q_len + e;
+    let width = unknowns + 1;
+
+    // pows[i * (q_len + 1) + j] = x_i^j (public).
+    let mut pows = Vec::with_capacity(m * (q_len + 1));
+    for &x in &xs {
+        let mut p = GF256(1);
+        for _ in 0..=q_len {
+            pows.push(p);
+            p *= x;
+        }
+    }
+    let pow = |i: usize, j: usize| pows[i * (q_len + 1) + j];
+
+    let mut secret = Zeroizing::new(vec![0u8; shares[0].value.len()]);
+    let mut bad = vec![0u8; m];
+    let mut failed = 0u8;
+
+    let mut a = Zeroizing::new(vec![GF256(0); m * width]);
+    let mut pivot_row = Zeroizing::new(vec![GF256(0); width]);
+    let mut used = vec![0u8; m];
+    let mut pivot_of = vec![0u8; m * unknowns];
+    let mut sol = Zeroizing::new(vec![GF256(0); unknowns + 1]);
+    let mut p = Zeroizing::new(vec![GF256(0); k]);
+
+    for (pos, out) in secret.iter_mut().enumerate() {
+        // Row i: [x_i^0 .. x_i^(k+e-1) | y_i x_i^0 .. y_i x_i^(e-1) | y_i x_i^e]
+        for (i, share) in shares.iter().enumerate() {
+            let y = GF256(share.value[pos]);
+            let row = &mut a[i * width..(i + 1) * width];
+            let (vander, weighted) = row.split_at_mut(q_len);
+            for (j, v) in vander.iter_mut().enumerate() {
+                *v = pow(i, j);
+            }
+            for (j, v) in weighted.iter_mut().enumerate() {
+                *v = y * pow(i, j);
+            }
+        }
+        used.fill(0);
+        pivot_of.fill(0);
+
+        for c in 0..unknowns {
+            // Select the first unused row with a non-zero entry in column c.
+            let mut found = 0u8;
+            pivot_row.fill(GF256(0));
+            for i in 0..m {
+                let sel = !used[i] & !found & nonzero_mask(a[i * width + c].0);
+                found |= sel;
+                used[i] |= sel;
+                pivot_of[i * unknowns + c] = sel;
+                for (d, &v) in pivot_row.iter_mut().zip(&a[i * width..(i + 1) * width]) {
+                    d.0 |= sel & v.0;
+                }
+            }
+            // Normalize (an all-zero row stays zero: inv(0) = 0).
+            let inv = pivot_row[c].inv();
+            for v in pivot_row.iter_mut() {
+                *v *= inv;
+            }
+            // Eliminate column c from every row; the pivot row becomes the normalized row.
+            for i in 0..m {
+                let row = &mut a[i * width..(i + 1) * width];
+                let f = row[c];
+                let sel = pivot_of[i * unknowns + c];
+                for (d, &r) in row.iter_mut().zip(pivot_row.iter()) {
+                    *d = GF256((d.0 ^ (f * r).0) | (sel & r.0));
+                }
+            }
+        }
+
+        // Free variables are zero; pivot variables read off the right-hand side.
+        for (c, s) in sol[..unknowns].iter_mut().enumerate() {
+            let mut v = 0u8;
+            for i in 0..m {
+                v |= pivot_of[i * unknowns + c] & a[i * width + unknowns].0;
+            }
+            *s = GF256(v);
+        }
+        // sol = [Q_0 .. Q_(k+e-1) | E_0 .. E_(e-1), 1]
+        sol[unknowns] = GF256(1);
+
+        // P = Q / E by long division with a monic divisor.
+        for d in (e..q_len).rev() {
+            let q = sol[d];
+            p[d - e] = q;
+            for j in 0..=e {
+                let t = sol[d - e + j] + q * sol[q_len + j];
+                sol[d - e + j] = t;
+            }
+        }
+        *out = p[0].0;
+
+        // Count disagreements with the decoded polynomial.
+        let mut errors = 0usize;
+        for i in 0..m {
+            let mut v = GF256(0);
+            for &c in p.iter().rev() {
+                v = v * xs[i] + c;
+            }
+            let diff = nonzero_mask(v.0 ^ shares[i].value[pos]);
+            bad[i] |= diff;
+            errors += (diff & 1) as usize;
         }
-        secret.push(sum.0);
+        failed |= (errors > e) as u8;
     }
 
-    Ok(secret)
+    if failed != 0 {
+        return Err(MpcError::IntegrityFailure);
+    }
+    let cheaters = shares.iter().zip(&bad).filter(|(_, &b)| b != 0).map(|(s, _)| s.identifier).collect();
+    Ok(RobustSecret { secret: core::mem::take(&mut *secret), cheaters })
+}
+
+/// 0xFF if `b` is non-zero, 0x00 otherwise, without branching.
+#[inline(always)]
+fn nonzero_mask(b: u8) -> u8 {
+    ((b as u16).wrapping_neg() >> 8) as u8
+}
+
+/// Validates a set of GF(2^8) shares and returns their identifiers as field points.
+///
+/// Checks that all shares have the same length and distinct identifiers.
+/// Optimized to O(N) using a boolean array.
+pub(crate) fn gf256_points(shares: &[Share]) -> Result<Vec<GF256>, MpcError> {
+    let share_len = shares[0].value.len();
+    if shares.iter().any(|s| s.value.len() != share_len) {
+        return Err(MpcError::ShareLengthMismatch);
+    }
+    let mut seen = [false; 256];
+    let mut xs = Vec::with_capacity(shares.len());
+    for share in shares {
+        let x = share.gf256_x()?;
+        if seen[x.0 as usize] {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        seen[x.0 as usize] = true;
+        xs.push(x);
+    }
+    Ok(xs)
+}
+
+/// Precomputed Lagrange weights for a fixed shareholder set.
+///
+/// `reconstruct_secret` inverts one field element per share on every call. When the same
+/// holders reconstruct many blocks (e.g. the pad, one 4096-byte block at a time), build a
+/// context once: identifiers are validated and the weights computed in `new`, and each
+/// block afterwards is only a bulk multiply-accumulate per share.
+///
+/// Weights are zeroized on drop.
+pub struct ReconstructionContext {
+    identifiers: Vec<u16>,
+    lambdas: Zeroizing<Vec<GF256>>,
+}
+
+impl ReconstructionContext {
+    /// Context for shares from `identifiers`, in this order, with threshold `k`.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` if fewer than `k` (or no) identifiers.
+    /// * `MpcError::InvalidShareIndex` if an identifier is zero or above 255.
+    /// * `MpcError::DuplicateShareIndex` on a repeated identifier.
+    pub fn new(identifiers: &[u16], k: u8) -> Result<Self, MpcError> {
+        if identifiers.is_empty() || identifiers.len() < k as usize {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut seen = [false; 256];
+        let mut xs = Vec::with_capacity(identifiers.len());
+        for &id in identifiers {
+            let x = u8::try_from(id).ok().filter(|&x| x != 0).ok_or(MpcError::InvalidShareIndex)?;
+            if seen[x as usize] {
+                return Err(MpcError::DuplicateShareIndex);
+            }
+            seen[x as usize] = true;
+            xs.push(GF256(x));
+        }
+        Ok(Self { identifiers: identifiers.to_vec(), lambdas: lagrange_weights(&xs, GF256(0)) })
+    }
+
+    /// The shareholder identifiers, in block order.
+    pub fn identifiers(&self) -> &[u16] {
+        &self.identifiers
+    }
+
+    /// Reconstructs one block from raw share bytes, `blocks[i]` belonging to `identifiers()[i]`.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` if the block count differs from the identifier count.
+    /// * `MpcError::ShareLengthMismatch` if a block's length differs from `out.len()`.
+    pub fn reconstruct_into(&self, blocks: &[&[u8]], out: &mut [u8]) -> Result<(), MpcError> {
+        if blocks.len() != self.identifiers.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        if blocks.iter().any(|b| b.len() != out.len()) {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        out.fill(0);
+        for (block, &lambda) in blocks.iter().zip(self.lambdas.iter()) {
+            mul_add_slice(out, block, lambda);
+        }
+        Ok(())
+    }
+
+    /// Reconstructs from whole shares, which must come from `identifiers()` in order.
+    ///
+    /// # Errors
+    /// * `MpcError::InvalidShareIndex` if the identifiers (or their order) differ.
+    /// * `MpcError::UnsupportedVersion` for non-GF(2^8) shares.
+    /// * `MpcError::IntegrityFailure` if any share carries a tag (as in `reconstruct_secret`).
+    /// * Otherwise as `reconstruct_into`.
+    pub fn reconstruct(&self, shares: &[Share]) -> Result<Vec<u8>, MpcError> {
+        reject_tagged(shares)?;
+        if shares.len() != self.identifiers.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        for (share, &id) in shares.iter().zip(&self.identifiers) {
+            if share.version != SHARE_VERSION_GF256 {
+                return Err(MpcError::UnsupportedVersion);
+            }
+            if share.identifier != id {
+                return Err(MpcError::InvalidShareIndex);
+            }
+        }
+        let blocks: Vec<&[u8]> = shares.iter().map(|s| s.value.as_slice()).collect();
+        let mut out = vec![0u8; shares[0].value.len()];
+        self.reconstruct_into(&blocks, &mut out)?;
+        Ok(out)
+    }
+
+    /// Lazily reconstructs a stream of share blocks, one `Vec<Share>` per block.
+    ///
+    /// Each item is checked as in `reconstruct`; outputs are zeroized on drop.
+    pub fn stream<'a, I>(&'a self, blocks: I) -> impl Iterator<Item = Result<Zeroizing<Vec<u8>>, MpcError>> + 'a
+    where
+        I: IntoIterator<Item = Vec<Share>>,
+        I::IntoIter: 'a,
+    {
+        blocks.into_iter().map(move |block| self.reconstruct(&block).map(Zeroizing::new))
+    }
+}
+
+/// Reconstructs the secret after checking every share's tag for `keys.verifier()`.
+///
+/// Dispatches on the share version (GF(2^8) or GF(2^16)).
+///
+/// # Errors
+/// * `MpcError::IntegrityFailure` if any share lacks a valid tag for this verifier.
+/// * Otherwise as `reconstruct_secret`.
+pub fn reconstruct_authenticated(shares: &[Share], k: u16, keys: &VerifierKeys) -> Result<Vec<u8>, MpcError> {
+    verify_shares(shares, keys)?;
+    match shares.first().map(|s| s.version) {
+        Some(SHARE_VERSION_GF65536) => crate::mpc::wide::reconstruct_unchecked(shares, k),
+        _ => reconstruct_unchecked(shares, u8::try_from(k).map_err(|_| MpcError::InvalidThreshold)?),
+    }
+}
+
+/// Reconstructs a secret shared under `policy`, checking the holders' weight first.
+///
+/// A member counts for its weight only when it hands in all of its shares, and the members
+/// present must be qualified (`WeightedPolicy::is_qualified`) before any interpolation.
+///
+/// # Errors
+/// * `MpcError::InvalidShareIndex` if a share's identifier is not assigned by `policy`.
+/// * `MpcError::DuplicateShareIndex` on a repeated identifier.
+/// * `MpcError::InsufficientShares` if a member present is missing some of its shares, or
+///   the members present do not reach the threshold.
+/// * Otherwise as `reconstruct_secret`.
+pub fn reconstruct_weighted(shares: &[Share], policy: &WeightedPolicy) -> Result<Vec<u8>, MpcError> {
+    let mut seen = Vec::with_capacity(shares.len());
+    let mut held = BTreeMap::new();
+    for share in shares {
+        let owner = policy.owner(share.identifier).ok_or(MpcError::InvalidShareIndex)?;
+        if seen.contains(&share.identifier) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        seen.push(share.identifier);
+        *held.entry(owner).or_insert(0usize) += 1;
+    }
+    let members: Vec<usize> = held.keys().copied().collect();
+    if held.iter().any(|(&m, &n)| n != policy.weight_of(&[m])) || !policy.is_qualified(&members) {
+        return Err(MpcError::InsufficientShares);
+    }
+    reconstruct_secret(shares, policy.threshold())
 }
 
 #[cfg(test)]
@@ -180,4 +526,111 @@ mod tests {
             Err(MpcError::InsufficientShares)
         );
     }
+
+    #[test]
+    fn test_reconstruct_multi_chunk() {
+        use crate::mpc::aggregate::refresh_shares;
+        let mut rng = MockEntropy { fill_val: 0x77 };
+        let secret: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let mut shares = split_secret(&secret, 4, 6, &mut rng).unwrap();
+        refresh_shares(&mut shares, 4, &mut rng).unwrap();
+        let subset = [shares[5].clone(), shares[0].clone(), shares[3].clone(), shares[2].clone()];
+        assert_eq!(reconstruct_secret(&subset, 4).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_reconstruct_robust_corrects_cheaters() {
+        let mut rng = MockEntropy { fill_val: 0x31 };
+        let secret: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(37)).collect();
+        let mut shares = split_secret(&secret, 3, 7, &mut rng).unwrap();
+
+        // No faults: nobody is accused.
+        let clean = reconstruct_robust(&shares, 3).unwrap();
+        assert_eq!(clean.secret, secret);
+        assert!(clean.cheaters.is_empty());
+
+        // e = (7 - 3) / 2 = 2 faulty shares, corrupted at different positions.
+        shares[1].value[0] ^= 0x5A;
+        shares[5].value[10] ^= 0x01;
+        shares[5].value[63] ^= 0xFF;
+        let out = reconstruct_robust(&shares, 3).unwrap();
+        assert_eq!(out.secret, secret);
+        assert_eq!(out.cheaters, vec![2, 6]);
+        assert_ne!(reconstruct_secret(&shares[..3], 3).unwrap(), secret);
+
+        // A third fault on one position exceeds the decoding radius.
+        shares[3].value[10] ^= 0x77;
+        shares[6].value[10] ^= 0x10;
+        assert_eq!(reconstruct_robust(&shares, 3), Err(MpcError::IntegrityFailure));
+    }
+
+    #[test]
+    fn test_reconstruct_robust_edges() {
+        let mut rng = MockEntropy { fill_val: 0x02 };
+        let secret = vec![0xDE, 0xAD];
+        let shares = split_secret(&secret, 3, 4, &mut rng).unwrap();
+        // m = k and m = k + 1 leave no correction capacity but still reconstruct.
+        assert_eq!(reconstruct_robust(&shares[..3], 3).unwrap().secret, secret);
+        assert_eq!(reconstruct_robust(&shares, 3).unwrap().secret, secret);
+        assert_eq!(reconstruct_robust(&shares[..2], 3), Err(MpcError::InsufficientShares));
+        assert_eq!(reconstruct_robust(&shares, 0), Err(MpcError::InvalidThreshold));
+    }
+
+    #[test]
+    fn test_reconstruction_context_blocks() {
+        let mut rng = MockEntropy { fill_val: 0x0B };
+        let pad: Vec<u8> = (0..3 * 4096u32).map(|i| (i * 13 % 251) as u8).collect();
+        let blocks: Vec<Vec<Share>> = pad.chunks(4096).map(|b| split_secret(b, 3, 5, &mut rng).unwrap()).collect();
+
+        let ctx = ReconstructionContext::new(&[5, 2, 4], 3).unwrap();
+        let pick = |shares: &Vec<Share>| vec![shares[4].clone(), shares[1].clone(), shares[3].clone()];
+        let mut out = Vec::new();
+        for block in ctx.stream(blocks.iter().map(pick)) {
+            out.extend_from_slice(&block.unwrap());
+        }
+        assert_eq!(out, pad);
+
+        // Raw slices reuse the same weights.
+        let raw: Vec<&[u8]> = [4, 1, 3].iter().map(|&i| blocks[1][i].value()).collect();
+        let mut buf = vec![0u8; 4096];
+        ctx.reconstruct_into(&raw, &mut buf).unwrap();
+        assert_eq!(&buf[..], &pad[4096..8192]);
+
+        // A block from a different holder set (or order) is refused.
+        let swapped = vec![blocks[0][1].clone(), blocks[0][4].clone(), blocks[0][3].clone()];
+        assert_eq!(ctx.reconstruct(&swapped), Err(MpcError::InvalidShareIndex));
+        assert_eq!(ctx.reconstruct(&blocks[0][..2]), Err(MpcError::InsufficientShares));
+        assert_eq!(ctx.reconstruct_into(&raw, &mut [0u8; 10]), Err(MpcError::ShareLengthMismatch));
+        assert_eq!(ReconstructionContext::new(&[1, 1, 2], 3).map(|_| ()), Err(MpcError::DuplicateShareIndex));
+        assert_eq!(ReconstructionContext::new(&[1, 2], 3).map(|_| ()), Err(MpcError::InsufficientShares));
+    }
+
+    #[test]
+    fn test_reconstruct_weighted() {
+        let mut rng = MockEntropy { fill_val: 0x31 };
+        let policy = WeightedPolicy::new(vec![3, 1, 1, 1], 4).unwrap();
+        let secret = b"founder".to_vec();
+        let held = policy.split(&secret, &mut rng).unwrap();
+
+        // The founder plus any one member reaches weight 4.
+        let mut shares = held[0].clone();
+        shares.extend(held[2].iter().cloned());
+        assert_eq!(reconstruct_weighted(&shares, &policy).unwrap(), secret);
+
+        // The three light members only reach weight 3.
+        let light: Vec<Share> = held[1..].iter().flatten().cloned().collect();
+        assert_eq!(reconstruct_weighted(&light, &policy), Err(MpcError::InsufficientShares));
+
+        // Identifiers outside the policy, or a share presented twice, are rejected.
+        let mut stray = shares.clone();
+        stray[0].identifier = 9;
+        assert_eq!(reconstruct_weighted(&stray, &policy), Err(MpcError::InvalidShareIndex));
+        let doubled = [held[0][0].clone(), held[0][0].clone(), held[1][0].clone(), held[2][0].clone()];
+        assert_eq!(reconstruct_weighted(&doubled, &policy), Err(MpcError::DuplicateShareIndex));
+
+        // The founder counts for weight 3 only with all three of its shares: two of them plus
+        // two members are four shares, but not weight 4.
+        let partial = [held[0][0].clone(), held[0][1].clone(), held[1][0].clone(), held[2][0].clone()];
+        assert_eq!(reconstruct_weighted(&partial, &policy), Err(MpcError::InsufficientShares));
+    }
 }
@@ -0,0 +1,390 @@
+//! Distributed proactive share refresh.
+//!
+//! `aggregate::refresh_shares` needs every share in one place. Here each party deals its own
+//! random sharing of zero, sends one sub-share to every other party over a pairwise channel,
+//! and adds the sub-shares it receives to its own share. The secret is unchanged (the sum of
+//! zero sharings is a zero sharing) while old shares no longer combine with new ones, and no
+//! party ever holds more than its own share.
+//!
+//! # Protocol
+//! 1. Every party opens a `RefreshRound` with its share, the threshold and the dealer set.
+//! 2. Every dealer calls `deal_zero` and sends sub-share `i` to party `i`.
+//! 3. Every party feeds each sub-share to `RefreshRound::receive`.
+//! 4. Once every dealer has contributed, the parties fix a public `challenge` (see below)
+//!    and every party broadcasts `RefreshRound::opening`.
+//! 5. With all openings in, `RefreshRound::finish` checks them and yields the new share.
+//!
+//! # Verification
+//! Tags only stop tampering in transit; they do not show that a dealer's polynomial is a
+//! sharing of zero of degree below `k`. A dealing of `d != 0` would shift the secret to
+//! `secret + d`, and a higher-degree one would break reconstruction. So each dealing also
+//! carries `BLIND_LEN` bytes of a second, blinding zero sharing. A party's opening is its
+//! blind sum plus a Toeplitz fold of its summed dealing under the challenge; `finish` checks
+//! that the openings of all parties, together with zero at `x = 0`, lie on one polynomial
+//! of degree below `k`. The blind hides the summed dealing, so the openings reveal nothing
+//! about the new shares.
+//!
+//! The challenge is `len + BLIND_LEN - 1` public bytes that no dealer can predict before
+//! every sub-share is delivered (e.g. agreed randomness from an earlier `joint` round). If
+//! at least `k` parties open honestly, a bad sum of dealings then passes with probability
+//! `2^-(8 * BLIND_LEN)`. The check does not say which dealer cheated.
+//!
+//! # Aborts
+//! `receive` rejects (and the round must be abandoned) on:
+//! - `MpcError::InvalidShareIndex`: unknown dealer, or a sub-share addressed to another party.
+//! - `MpcError::DuplicateShareIndex`: a second contribution from the same dealer.
+//! - `MpcError::UnsupportedVersion` / `MpcError::ShareLengthMismatch`: format does not match
+//!   the party's share.
+//! - `MpcError::IntegrityFailure`: the polynomial MAC tag under the pairwise key (see `auth`)
+//!   does not verify.
+//!
+//! `opening` and `finish` fail with `MpcError::InsufficientShares` while any dealer (or, for
+//! `finish`, any opening) is missing, and `finish` fails with `MpcError::IntegrityFailure`
+//! if the openings are inconsistent or its own opening was altered.
+//!
+//! # Security
+//! - **Pairwise Keys**: Party `p`'s `VerifierKeys` hold, per peer `q`, the one-time key it
+//!   shares with `q` (so `keys_p[q] == keys_q[p]`); each direction uses its own mask. A key
+//!   tags one sub-share, so fresh keys are needed for every round.
+//! - **Zeroization**: Coefficients and the running sum are zeroized.
+//! - **GF(2^8) only**: Version 1 shares.
+
+extern crate alloc;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+use crate::core::gf256::{GF256, mul_add_slice};
+use crate::core::xor::xor_inplace;
+use crate::entropy::EntropySource;
+use crate::mpc::auth::{tag_pairwise, verify_pairwise, VerifierKeys};
+use crate::mpc::polynomial::evaluate_rows;
+use crate::mpc::reconstruct::{gf256_points, lagrange_weights};
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF256}};
+
+/// Bytes of blinding zero sharing appended to every sub-share (see module docs).
+pub const BLIND_LEN: usize = 8;
+
+/// Dealer side: a fresh sharing of zero for `recipients`, each sub-share tagged for its recipient.
+///
+/// Sub-shares hold `len + BLIND_LEN` bytes: the dealing for a `len`-byte share, then the
+/// blind used by the check. The dealer is `keys.verifier()`; its own sub-share is left
+/// untagged. Each key in `keys` is spent by the sub-share it tags.
+///
+/// # Errors
+/// * `MpcError::InvalidThreshold` if `k < 2` or `k` exceeds the number of recipients.
+/// * `MpcError::InvalidShareIndex` on an out-of-range recipient or a missing or spent
+///   pairwise key.
+/// * `MpcError::DuplicateShareIndex` on a repeated recipient.
+/// * `MpcError::EmptyShare` if `len` is zero; `MpcError::RngFailure` on rng failure.
+pub fn deal_zero<R: EntropySource + ?Sized>(
+    recipients: &[u16],
+    k: u8,
+    len: usize,
+    keys: &mut VerifierKeys,
+    rng: &mut R
+) -> Result<Vec<Share>, MpcError> {
+    if k < 2 || k as usize > recipients.len() {
+        return Err(MpcError::InvalidThreshold);
+    }
+    if len == 0 {
+        return Err(MpcError::EmptyShare);
+    }
+    let mut seen = BTreeSet::new();
+    if !recipients.iter().all(|&r| seen.insert(r)) {
+        return Err(MpcError::DuplicateShareIndex);
+    }
+
+    let width = len + BLIND_LEN;
+    let mut rows = Zeroizing::new(vec![0u8; (k - 1) as usize * width]);
+    rng.fill(&mut rows[..]).map_err(|_| MpcError::RngFailure)?;
+
+    let dealer = keys.verifier();
+    let mut out = Vec::with_capacity(recipients.len());
+    for &r in recipients {
+        let x = u8::try_from(r).ok().filter(|&x| x != 0).ok_or(MpcError::InvalidShareIndex)?;
+        let mut value = vec![0u8; width];
+        evaluate_rows(&rows, None, GF256(x), &mut value);
+        let mut sub = Share::new(r, value)?;
+        if r != dealer {
+            tag_pairwise(&mut sub, r, keys)?;
+        }
+        out.push(sub);
+    }
+    Ok(out)
+}
+
+/// One party's state during a distributed refresh.
+pub struct RefreshRound {
+    share: Share,
+    k: u8,
+    dealers: BTreeSet<u16>,
+    received: BTreeSet<u16>,
+    sum: Zeroizing<Vec<u8>>,
+}
+
+impl RefreshRound {
+    /// Opens a round for `share`, expecting one contribution from every identifier in `dealers`.
+    ///
+    /// # Errors
+    /// * `MpcError::UnsupportedVersion` unless `share` is a GF(2^8) share.
+    /// * `MpcError::InvalidThreshold` if fewer than `k` dealers (or `k < 2`).
+    /// * `MpcError::InvalidShareIndex` if the party itself is not a dealer.
+    pub fn new(share: Share, k: u8, dealers: &[u16]) -> Result<Self, MpcError> {
+        share.gf256_x()?;
+        let dealers: BTreeSet<u16> = dealers.iter().copied().collect();
+        if k < 2 || dealers.len() < k as usize {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if !dealers.contains(&share.identifier) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        let sum = Zeroizing::new(vec![0u8; share.value.len() + BLIND_LEN]);
+        Ok(Self { share, k, dealers, received: BTreeSet::new(), sum })
+    }
+
+    /// Verifies and absorbs the sub-share dealt by `dealer` (see module docs for aborts).
+    ///
+    /// `keys` are this party's pairwise keys, indexed by dealer.
+    pub fn receive(&mut self, dealer: u16, sub: &Share, keys: &VerifierKeys) -> Result<(), MpcError> {
+        let me = self.share.identifier;
+        if !self.dealers.contains(&dealer) || sub.identifier != me || keys.verifier() != me {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if self.received.contains(&dealer) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        if sub.version != SHARE_VERSION_GF256 {
+            return Err(MpcError::UnsupportedVersion);
+        }
+        if sub.value.len() != self.sum.len() {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        if dealer != me {
+            verify_pairwise(sub, dealer, keys)?;
+        }
+        xor_inplace(&mut self.sum, &sub.value);
+        self.received.insert(dealer);
+        Ok(())
+    }
+
+    /// Dealers whose contribution has not arrived yet.
+    pub fn missing(&self) -> Vec<u16> {
+        self.dealers.difference(&self.received).copied().collect()
+    }
+
+    /// This party's blinded opening under `challenge`, to be broadcast to every party.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` while any dealer is missing.
+    /// * `MpcError::ShareLengthMismatch` unless `challenge` is `len + BLIND_LEN - 1` bytes.
+    pub fn opening(&self, challenge: &[u8]) -> Result<Share, MpcError> {
+        if self.received != self.dealers {
+            return Err(MpcError::InsufficientShares);
+        }
+        let len = self.share.value.len();
+        if challenge.len() != len + BLIND_LEN - 1 {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        Share::new(self.share.identifier, fold(&self.sum, challenge))
+    }
+
+    /// Checks every party's `opened` value against `challenge` and, if the dealings sum to a
+    /// zero sharing of degree below `k`, adds it to the share and returns it, untagged.
+    ///
+    /// `opened` must hold exactly one opening from every dealer, this party's included.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` while any dealer or opening is missing.
+    /// * `MpcError::InvalidShareIndex` / `MpcError::DuplicateShareIndex` on openings from
+    ///   unknown or repeated parties.
+    /// * `MpcError::IntegrityFailure` if the openings are inconsistent or this party's own
+    ///   opening differs from the one it computed.
+    /// * Otherwise as `opening`.
+    pub fn finish(mut self, challenge: &[u8], opened: &[Share]) -> Result<Share, MpcError> {
+        let own = self.opening(challenge)?;
+        if opened.len() != self.dealers.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        gf256_points(opened)?;
+        if opened.iter().any(|o| !self.dealers.contains(&o.identifier)) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if opened.iter().any(|o| o.identifier == own.identifier && o.value != own.value) {
+            return Err(MpcError::IntegrityFailure);
+        }
+        check_zero_sharing(opened, self.k)?;
+        let len = self.share.value.len();
+        xor_inplace(&mut self.share.value, &self.sum[..len]);
+        // Old tags cover the old value; re-tag with fresh keys (see `auth`).
+        self.share.tags.clear();
+        Ok(self.share)
+    }
+}
+
+/// Blinded fold of `value = dealt || blind` (`BLIND_LEN` blind bytes) under `challenge`
+/// (`dealt.len() + BLIND_LEN - 1` bytes): `blind[r] + sum_j challenge[r + j] * dealt[j]`.
+///
+/// The map is the same for every party, so folding each share of a sharing gives a
+/// sharing of the folded secret.
+pub(crate) fn fold(value: &[u8], challenge: &[u8]) -> Vec<u8> {
+    let (dealt, blind) = value.split_at(value.len() - BLIND_LEN);
+    let mut out = blind.to_vec();
+    for (r, o) in out.iter_mut().enumerate() {
+        let mut acc = GF256(*o);
+        for (&c, &v) in challenge[r..r + dealt.len()].iter().zip(dealt) {
+            acc += GF256(c) * GF256(v);
+        }
+        *o = acc.0;
+    }
+    out
+}
+
+/// Checks that `opened`, together with zero at `x = 0`, lie on one polynomial of degree
+/// below `k`: the first `k - 1` openings and the origin fix it, and every other opening
+/// must agree.
+fn check_zero_sharing(opened: &[Share], k: u8) -> Result<(), MpcError> {
+    let xs = gf256_points(opened)?;
+    let base = k as usize - 1;
+    if opened.len() <= base {
+        return Err(MpcError::InsufficientShares);
+    }
+    let mut points = Vec::with_capacity(k as usize);
+    points.push(GF256(0));
+    points.extend_from_slice(&xs[..base]);
+    let mut diff = 0u8;
+    for (share, &x) in opened[base..].iter().zip(&xs[base..]) {
+        let weights = lagrange_weights(&points, x);
+        let mut expected = vec![0u8; share.value.len()];
+        for (o, &w) in opened[..base].iter().zip(&weights[1..]) {
+            mul_add_slice(&mut expected, &o.value, w);
+        }
+        diff |= expected.iter().zip(&share.value).fold(0u8, |d, (a, b)| d | (a ^ b));
+    }
+    if diff == 0 { Ok(()) } else { Err(MpcError::IntegrityFailure) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::{MockEntropy, pairwise};
+    use crate::mpc::quorum::split_secret;
+    use crate::mpc::reconstruct::reconstruct_secret;
+
+    /// Every party absorbs its sub-share from every dealer, opens, and finishes.
+    fn run(shares: &[Share], k: u8, dealings: &[Vec<Share>], keys: &[VerifierKeys], rng: &mut MockEntropy) -> Vec<Result<Share, MpcError>> {
+        let ids: Vec<u16> = shares.iter().map(|s| s.identifier).collect();
+        let mut rounds = Vec::new();
+        for (i, share) in shares.iter().enumerate() {
+            let mut round = RefreshRound::new(share.clone(), k, &ids).unwrap();
+            for (d, dealing) in dealings.iter().enumerate() {
+                assert!(round.missing().contains(&ids[d]));
+                round.receive(ids[d], &dealing[i], &keys[i]).unwrap();
+            }
+            rounds.push(round);
+        }
+        // Fixed only once every sub-share is in.
+        let mut challenge = vec![0u8; shares[0].value.len() + BLIND_LEN - 1];
+        rng.fill(&mut challenge).unwrap();
+        let opened: Vec<Share> = rounds.iter().map(|r| r.opening(&challenge).unwrap()).collect();
+        rounds.into_iter().map(|r| r.finish(&challenge, &opened)).collect()
+    }
+
+    #[test]
+    fn test_distributed_refresh_preserves_secret() {
+        let mut rng = MockEntropy { state: 5 };
+        let secret = b"pad seed material".to_vec();
+        let shares = split_secret(&secret, 3, 4, &mut rng).unwrap();
+        let ids: Vec<u16> = shares.iter().map(|s| s.identifier).collect();
+        let mut keys = pairwise(&ids, &mut rng);
+
+        let dealings: Vec<Vec<Share>> = keys
+            .iter_mut()
+            .map(|k| deal_zero(&ids, 3, secret.len(), k, &mut rng).unwrap())
+            .collect();
+        let refreshed: Vec<Share> = run(&shares, 3, &dealings, &keys, &mut rng).into_iter().map(|r| r.unwrap()).collect();
+
+        assert_ne!(refreshed[0].value, shares[0].value);
+        assert_eq!(reconstruct_secret(&refreshed[1..], 3).unwrap(), secret);
+        // Old and new shares no longer combine.
+        let mixed = [shares[0].clone(), refreshed[1].clone(), refreshed[2].clone()];
+        assert_ne!(reconstruct_secret(&mixed, 3).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_malformed_contributions_abort() {
+        let mut rng = MockEntropy { state: 9 };
+        let shares = split_secret(b"k", 2, 3, &mut rng).unwrap();
+        let ids = [1u16, 2, 3];
+        let mut keys = pairwise(&ids, &mut rng);
+        let from2 = deal_zero(&ids, 2, 1, &mut keys[1], &mut rng).unwrap();
+        assert_eq!(from2[0].value.len(), 1 + BLIND_LEN);
+        let mut round = RefreshRound::new(shares[0].clone(), 2, &ids).unwrap();
+
+        // Tampered in transit.
+        let mut bad = from2[0].clone();
+        bad.value[0] ^= 1;
+        assert_eq!(round.receive(2, &bad, &keys[0]), Err(MpcError::IntegrityFailure));
+        // Addressed to another party, or from an unknown dealer.
+        assert_eq!(round.receive(2, &from2[1], &keys[0]), Err(MpcError::InvalidShareIndex));
+        assert_eq!(round.receive(7, &from2[0], &keys[0]), Err(MpcError::InvalidShareIndex));
+        // Claimed by the wrong dealer: the pairwise key does not match.
+        assert_eq!(round.receive(3, &from2[0], &keys[0]), Err(MpcError::IntegrityFailure));
+
+        round.receive(2, &from2[0], &keys[0]).unwrap();
+        assert_eq!(round.receive(2, &from2[0], &keys[0]), Err(MpcError::DuplicateShareIndex));
+        assert_eq!(round.missing(), vec![1, 3]);
+        let challenge = [0u8; BLIND_LEN];
+        assert_eq!(round.opening(&challenge), Err(MpcError::InsufficientShares));
+        assert_eq!(round.finish(&challenge, &[]).map(|_| ()), Err(MpcError::InsufficientShares));
+
+        assert_eq!(RefreshRound::new(shares[0].clone(), 2, &[2, 3]).map(|_| ()), Err(MpcError::InvalidShareIndex));
+        assert_eq!(deal_zero(&[1, 1], 2, 1, &mut keys[0], &mut rng), Err(MpcError::DuplicateShareIndex));
+        // Keys are one-time: the same dealer cannot tag a second round with them.
+        assert_eq!(deal_zero(&ids, 2, 1, &mut keys[1], &mut rng), Err(MpcError::InvalidShareIndex));
+    }
+
+    #[test]
+    fn test_bad_dealings_abort() {
+        let mut rng = MockEntropy { state: 13 };
+        let shares = split_secret(b"s", 2, 3, &mut rng).unwrap();
+        let ids = [1u16, 2, 3];
+        let width = 1 + BLIND_LEN;
+
+        // A sharing of a non-zero value, and a zero sharing of too high a degree.
+        let mut shifted = vec![0u8; width];
+        shifted[0] = 0x01;
+        for (evil, threshold) in [(shifted, 2), (vec![0u8; width], 3)] {
+            let mut keys = pairwise(&ids, &mut rng);
+            let mut dealings: Vec<Vec<Share>> = keys[..2]
+                .iter_mut()
+                .map(|k| deal_zero(&ids, 2, 1, k, &mut rng).unwrap())
+                .collect();
+            let mut bad = split_secret(&evil, threshold, 3, &mut rng).unwrap();
+            for (sub, &r) in bad.iter_mut().zip(&ids) {
+                if r != 3 {
+                    tag_pairwise(sub, r, &mut keys[2]).unwrap();
+                }
+            }
+            dealings.push(bad);
+            for result in run(&shares, 2, &dealings, &keys, &mut rng) {
+                assert_eq!(result, Err(MpcError::IntegrityFailure));
+            }
+        }
+
+        // A party whose own opening was altered in the broadcast aborts as well.
+        let mut keys = pairwise(&ids, &mut rng);
+        let dealings: Vec<Vec<Share>> = keys.iter_mut().map(|k| deal_zero(&ids, 2, 1, k, &mut rng).unwrap()).collect();
+        let mut rounds: Vec<RefreshRound> = shares.iter().map(|s| RefreshRound::new(s.clone(), 2, &ids).unwrap()).collect();
+        for (i, round) in rounds.iter_mut().enumerate() {
+            for (d, dealing) in dealings.iter().enumerate() {
+                round.receive(ids[d], &dealing[i], &keys[i]).unwrap();
+            }
+        }
+        let challenge = [0x5Au8; BLIND_LEN];
+        let mut opened: Vec<Share> = rounds.iter().map(|r| r.opening(&challenge).unwrap()).collect();
+        opened[0].value[0] ^= 1;
+        let first = rounds.remove(0);
+        assert_eq!(first.finish(&challenge, &opened).map(|_| ()), Err(MpcError::IntegrityFailure));
+        assert_eq!(rounds.remove(0).finish(&challenge, &opened[1..]).map(|_| ()), Err(MpcError::InsufficientShares));
+    }
+}
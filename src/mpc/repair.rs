@@ -0,0 +1,379 @@
+//! Share repair for a member who lost their device.
+//!
+//! A replacement device rejoins with its original identifier `r` without a re-bootstrap:
+//! `k` helpers jointly evaluate the sharing polynomial at `x = r`. Helper `i` holds the
+//! contribution `c_i = lambda_i(r) * y_i`, with `lambda_i(r)` the Lagrange weight of the helper
+//! set at `r`, so that `sum_i c_i` is the lost share. Each helper splits `c_i` into random
+//! additive masks, one per helper, and each helper forwards only the sum of the masks it
+//! received. The new device adds the forwarded sums; no helper sees another's contribution.
+//!
+//! # Protocol
+//! 1. The replacement device enters `State::Recovery` and opens an `Enrollment` for its
+//!    identifier and the agreed helper set.
+//! 2. Every helper calls `repair_contribute` and sends mask `j` to helper `j`.
+//! 3. Every helper feeds each mask to `RepairRound::receive`, then sends
+//!    `RepairRound::finish` to the replacement device.
+//! 4. The device feeds each partial to `Enrollment::receive`; once all have arrived,
+//!    `StateMachine::complete_repair` yields the share and returns the node to `Active`.
+//!
+//! # Aborts
+//! `receive` on either side aborts with the same errors as `refresh::RefreshRound::receive`.
+//! `finish` fails with `MpcError::InsufficientShares` while any helper is missing.
+//!
+//! # Security
+//! - **Pairwise Keys**: Masks and partials carry one-time polynomial MAC tags (see `auth`),
+//!   masks under helper-to-helper keys and partials under the keys between each helper and
+//!   the replacement device, which must be established during recovery before repair
+//!   starts. Each key tags one mask or partial per direction, so fresh keys are needed for
+//!   every round.
+//! - **Privacy**: A helper sees only uniformly random masks from the others; the device sees
+//!   only partial sums of masks, which add up to its share.
+//! - **Scope**: Like `refresh`, tags do not prove that a helper used its real share.
+//! - **Zeroization**: Contributions, weights and running sums are zeroized.
+//! - **GF(2^8) only**: Version 1 shares.
+
+extern crate alloc;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+use crate::core::gf256::{GF256, mul_slice};
+use crate::core::xor::xor_inplace;
+use crate::entropy::EntropySource;
+use crate::mpc::auth::{tag_pairwise, verify_pairwise, VerifierKeys};
+use crate::mpc::reconstruct::lagrange_weights;
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF256}};
+
+/// Validates a helper set for repairing `lost` and returns it ordered.
+fn helper_set(helpers: &[u16], k: u8, lost: u16) -> Result<BTreeSet<u16>, MpcError> {
+    if k < 2 || helpers.len() < k as usize {
+        return Err(MpcError::InvalidThreshold);
+    }
+    let set: BTreeSet<u16> = helpers.iter().copied().collect();
+    if set.len() != helpers.len() {
+        return Err(MpcError::DuplicateShareIndex);
+    }
+    if lost == 0 || lost > 255 || set.contains(&lost) || set.iter().any(|&h| h == 0 || h > 255) {
+        return Err(MpcError::InvalidShareIndex);
+    }
+    Ok(set)
+}
+
+/// Helper side: masks this helper's contribution to the share at `lost`.
+///
+/// Returns one mask per helper, in `helpers` order, each tagged for its recipient (the
+/// helper's own mask is untagged). `keys.verifier()` must be `share.identifier`.
+///
+/// # Errors
+/// * `MpcError::InvalidThreshold` if `k < 2` or fewer than `k` helpers.
+/// * `MpcError::InvalidShareIndex` on an out-of-range identifier, `lost` among the helpers,
+///   a share or key not belonging to a helper, or a missing or spent pairwise key.
+/// * `MpcError::DuplicateShareIndex` on a repeated helper.
+/// * `MpcError::UnsupportedVersion` unless `share` is a GF(2^8) share.
+/// * `MpcError::RngFailure` on rng failure.
+pub fn repair_contribute<R: EntropySource + ?Sized>(
+    share: &Share,
+    helpers: &[u16],
+    k: u8,
+    lost: u16,
+    keys: &mut VerifierKeys,
+    rng: &mut R
+) -> Result<Vec<Share>, MpcError> {
+    share.gf256_x()?;
+    let set = helper_set(helpers, k, lost)?;
+    let me = share.identifier;
+    if keys.verifier() != me || !set.contains(&me) {
+        return Err(MpcError::InvalidShareIndex);
+    }
+
+    let xs: Vec<GF256> = set.iter().map(|&h| GF256(h as u8)).collect();
+    let weights = lagrange_weights(&xs, GF256(lost as u8));
+    let pos = set.iter().position(|&h| h == me).ok_or(MpcError::InvalidShareIndex)?;
+    let mut own = Zeroizing::new(share.value.clone());
+    mul_slice(&mut own, weights[pos]);
+
+    let mut out = Vec::with_capacity(helpers.len());
+    for &h in helpers {
+        if h == me {
+            // Placeholder; the own mask is whatever the others leave over.
+            out.push(Share::new(h, vec![0u8; own.len()])?);
+            continue;
+        }
+        let mut value = vec![0u8; own.len()];
+        rng.fill(&mut value).map_err(|_| MpcError::RngFailure)?;
+        xor_inplace(&mut own, &value);
+        let mut mask = Share::new(h, value)?;
+        tag_pairwise(&mut mask, h, keys)?;
+        out.push(mask);
+    }
+    if let Some(slot) = out.iter_mut().find(|s| s.identifier == me) {
+        slot.value.copy_from_slice(&own);
+    }
+    Ok(out)
+}
+
+/// Running sum of tagged contributions addressed to `me`, one per expected sender.
+struct Accumulator {
+    me: u16,
+    senders: BTreeSet<u16>,
+    received: BTreeSet<u16>,
+    sum: Zeroizing<Vec<u8>>,
+}
+
+impl Accumulator {
+    fn receive(&mut self, from: u16, part: &Share, keys: &VerifierKeys, untagged: Option<u16>) -> Result<(), MpcError> {
+        if !self.senders.contains(&from) || part.identifier != self.me || keys.verifier() != self.me {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if self.received.contains(&from) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        if part.version != SHARE_VERSION_GF256 {
+            return Err(MpcError::UnsupportedVersion);
+        }
+        if part.value.len() != self.sum.len() {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        if untagged != Some(from) {
+            verify_pairwise(part, from, keys)?;
+        }
+        xor_inplace(&mut self.sum, &part.value);
+        self.received.insert(from);
+        Ok(())
+    }
+
+    fn missing(&self) -> Vec<u16> {
+        self.senders.difference(&self.received).copied().collect()
+    }
+
+    fn complete(&self) -> Result<(), MpcError> {
+        if self.received != self.senders {
+            return Err(MpcError::InsufficientShares);
+        }
+        Ok(())
+    }
+}
+
+/// One helper's state during a repair: collects the masks addressed to it.
+pub struct RepairRound {
+    acc: Accumulator,
+    lost: u16,
+}
+
+impl RepairRound {
+    /// Opens a round for helper `me`, repairing `lost` from the masks of every helper.
+    ///
+    /// # Errors
+    /// Same as `repair_contribute`, plus `MpcError::EmptyShare` if `len` is zero.
+    pub fn new(me: u16, helpers: &[u16], k: u8, lost: u16, len: usize) -> Result<Self, MpcError> {
+        let senders = helper_set(helpers, k, lost)?;
+        if !senders.contains(&me) {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if len == 0 {
+            return Err(MpcError::EmptyShare);
+        }
+        let acc = Accumulator { me, senders, received: BTreeSet::new(), sum: Zeroizing::new(vec![0u8; len]) };
+        Ok(Self { acc, lost })
+    }
+
+    /// Verifies and absorbs the mask sent by helper `from` (see module docs for aborts).
+    ///
+    /// `keys` are this helper's pairwise keys with the other helpers.
+    pub fn receive(&mut self, from: u16, mask: &Share, keys: &VerifierKeys) -> Result<(), MpcError> {
+        let me = self.acc.me;
+        self.acc.receive(from, mask, keys, Some(me))
+    }
+
+    /// Helpers whose mask has not arrived yet.
+    pub fn missing(&self) -> Vec<u16> {
+        self.acc.missing()
+    }
+
+    /// Returns the masked partial for the replacement device, tagged under the key `keys`
+    /// holds for it.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` while any helper is missing.
+    /// * `MpcError::InvalidShareIndex` if `keys` hold no unused key for the replacement device.
+    pub fn finish(self, keys: &mut VerifierKeys) -> Result<Share, MpcError> {
+        self.acc.complete()?;
+        let mut partial = Share::new(self.lost, self.acc.sum.to_vec())?;
+        tag_pairwise(&mut partial, self.lost, keys)?;
+        Ok(partial)
+    }
+}
+
+/// The replacement device's state during a repair: collects the helpers' partials.
+pub struct Enrollment {
+    acc: Accumulator,
+}
+
+impl Enrollment {
+    /// Opens an enrollment restoring the share at `identifier` from the given helpers.
+    ///
+    /// # Errors
+    /// Same as `RepairRound::new`.
+    pub fn new(identifier: u16, helpers: &[u16], k: u8, len: usize) -> Result<Self, MpcError> {
+        let senders = helper_set(helpers, k, identifier)?;
+        if len == 0 {
+            return Err(MpcError::EmptyShare);
+        }
+        Ok(Self { acc: Accumulator { me: identifier, senders, received: BTreeSet::new(), sum: Zeroizing::new(vec![0u8; len]) } })
+    }
+
+    /// The identifier being restored.
+    pub fn identifier(&self) -> u16 {
+        self.acc.me
+    }
+
+    /// Verifies and absorbs the partial sent by helper `from` (see module docs for aborts).
+    ///
+    /// `keys` are the device's pairwise keys with the helpers.
+    pub fn receive(&mut self, from: u16, partial: &Share, keys: &VerifierKeys) -> Result<(), MpcError> {
+        self.acc.receive(from, partial, keys, None)
+    }
+
+    /// Helpers whose partial has not arrived yet.
+    pub fn missing(&self) -> Vec<u16> {
+        self.acc.missing()
+    }
+
+    /// Returns the repaired share, untagged.
+    ///
+    /// Prefer `StateMachine::complete_repair`, which also leaves `State::Recovery`.
+    ///
+    /// Can be retried: a call while any helper is missing leaves the enrollment intact.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` while any helper is missing.
+    pub fn finish(&self) -> Result<Share, MpcError> {
+        self.acc.complete()?;
+        Share::new(self.acc.me, self.acc.sum.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::{MockEntropy, pairwise};
+    use crate::mpc::quorum::split_secret;
+    use crate::mpc::reconstruct::reconstruct_secret;
+
+    /// Runs a repair of `lost` from `helpers` (indices into `shares`) and returns the
+    /// device's enrollment ready to finish.
+    fn run_repair(shares: &[Share], helpers: &[usize], lost: u16, k: u8, rng: &mut MockEntropy) -> Enrollment {
+        let mut ids: Vec<u16> = helpers.iter().map(|&h| shares[h].identifier).collect();
+        ids.push(lost);
+        let mut keys = pairwise(&ids, rng);
+        let helper_ids = &ids[..helpers.len()];
+        let len = shares[0].value.len();
+
+        let masks: Vec<Vec<Share>> = helpers
+            .iter()
+            .enumerate()
+            .map(|(i, &h)| repair_contribute(&shares[h], helper_ids, k, lost, &mut keys[i], rng).unwrap())
+            .collect();
+
+        let mut enrollment = Enrollment::new(lost, helper_ids, k, len).unwrap();
+        for (j, &id) in helper_ids.iter().enumerate() {
+            let mut round = RepairRound::new(id, helper_ids, k, lost, len).unwrap();
+            for (i, from) in masks.iter().enumerate() {
+                round.receive(helper_ids[i], &from[j], &keys[j]).unwrap();
+            }
+            assert!(round.missing().is_empty());
+            let partial = round.finish(&mut keys[j]).unwrap();
+            enrollment.receive(id, &partial, &keys[helpers.len()]).unwrap();
+        }
+        enrollment
+    }
+
+    #[test]
+    fn test_repair_restores_lost_share() {
+        let mut rng = MockEntropy { state: 22 };
+        let secret = b"lost device share".to_vec();
+        let shares = split_secret(&secret, 3, 5, &mut rng).unwrap();
+
+        // Member 2 lost its device; members 1, 4 and 5 help.
+        let enrollment = run_repair(&shares, &[0, 3, 4], 2, 3, &mut rng);
+        assert_eq!(enrollment.identifier(), 2);
+        let repaired = enrollment.finish().unwrap();
+        assert_eq!(repaired.identifier, 2);
+        assert_eq!(repaired.value, shares[1].value);
+
+        // More helpers than the threshold give the same share.
+        let repaired = run_repair(&shares, &[0, 2, 3, 4], 2, 3, &mut rng).finish().unwrap();
+        assert_eq!(repaired.value, shares[1].value);
+        assert_eq!(reconstruct_secret(&[repaired, shares[4].clone(), shares[0].clone()], 3).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_masks_hide_contributions() {
+        let mut rng = MockEntropy { state: 3 };
+        let shares = split_secret(&[0x5A; 8], 2, 3, &mut rng).unwrap();
+        let mut keys = pairwise(&[1, 2], &mut rng);
+        let masks = repair_contribute(&shares[0], &[1, 2], 2, 3, &mut keys[0], &mut rng).unwrap();
+        let mut weighted = shares[0].value.clone();
+        mul_slice(&mut weighted, lagrange_weights(&[GF256(1), GF256(2)], GF256(3))[0]);
+
+        // The mask sent away is not the contribution; the two masks add up to it.
+        assert_ne!(masks[1].value, weighted);
+        let mut sum = masks[0].value.clone();
+        xor_inplace(&mut sum, &masks[1].value);
+        assert_eq!(sum, weighted);
+        assert!(masks[0].tags.is_empty());
+        assert_eq!(masks[1].tags[0].verifier, 2);
+    }
+
+    #[test]
+    fn test_repair_aborts() {
+        let mut rng = MockEntropy { state: 8 };
+        let shares = split_secret(b"abc", 2, 4, &mut rng).unwrap();
+        let ids = [1u16, 3, 2];
+        let mut keys = pairwise(&ids, &mut rng);
+        let from3 = repair_contribute(&shares[2], &ids[..2], 2, 2, &mut keys[1], &mut rng).unwrap();
+
+        let mut round = RepairRound::new(1, &ids[..2], 2, 2, 3).unwrap();
+        let mut bad = from3[0].clone();
+        bad.value[1] ^= 0x80;
+        assert_eq!(round.receive(3, &bad, &keys[0]), Err(MpcError::IntegrityFailure));
+        assert_eq!(round.receive(3, &from3[1], &keys[0]), Err(MpcError::InvalidShareIndex));
+        round.receive(3, &from3[0], &keys[0]).unwrap();
+        assert_eq!(round.receive(3, &from3[0], &keys[0]), Err(MpcError::DuplicateShareIndex));
+        assert_eq!(round.missing(), vec![1]);
+        assert_eq!(round.finish(&mut keys[0]).map(|_| ()), Err(MpcError::InsufficientShares));
+
+        // The lost identifier cannot help, helpers must reach the threshold.
+        assert_eq!(repair_contribute(&shares[0], &[1, 2], 2, 2, &mut keys[0], &mut rng), Err(MpcError::InvalidShareIndex));
+        assert_eq!(repair_contribute(&shares[0], &[1, 3], 3, 2, &mut keys[0], &mut rng), Err(MpcError::InvalidThreshold));
+        assert_eq!(repair_contribute(&shares[0], &[1, 1], 2, 2, &mut keys[0], &mut rng), Err(MpcError::DuplicateShareIndex));
+        assert_eq!(Enrollment::new(2, &[1, 3], 2, 0).map(|_| ()), Err(MpcError::EmptyShare));
+    }
+
+    #[test]
+    fn test_tampered_partials_rejected() {
+        let mut rng = MockEntropy { state: 41 };
+        let shares = split_secret(&[0x17u8; 300], 2, 3, &mut rng).unwrap();
+        let ids = [1u16, 3, 2];
+        let mut keys = pairwise(&ids, &mut rng);
+        let from1 = repair_contribute(&shares[0], &ids[..2], 2, 2, &mut keys[0], &mut rng).unwrap();
+        let from3 = repair_contribute(&shares[2], &ids[..2], 2, 2, &mut keys[1], &mut rng).unwrap();
+        // Masks are one-time: a second contribution under the same keys is refused.
+        assert_eq!(repair_contribute(&shares[0], &ids[..2], 2, 2, &mut keys[0], &mut rng), Err(MpcError::InvalidShareIndex));
+
+        let mut round = RepairRound::new(1, &ids[..2], 2, 2, 300).unwrap();
+        round.receive(1, &from1[0], &keys[0]).unwrap();
+        round.receive(3, &from3[0], &keys[0]).unwrap();
+        let partial = round.finish(&mut keys[0]).unwrap();
+
+        // Equal changes 255 bytes apart, which SIP-64 tags did not cover, and tag-only changes.
+        let mut enrollment = Enrollment::new(2, &ids[..2], 2, 300).unwrap();
+        let mut bad = partial.clone();
+        bad.value[10] ^= 0x44;
+        bad.value[265] ^= 0x44;
+        assert_eq!(enrollment.receive(1, &bad, &keys[2]), Err(MpcError::IntegrityFailure));
+        let mut bad = partial.clone();
+        bad.tags[0].tag[31] ^= 0x80;
+        assert_eq!(enrollment.receive(1, &bad, &keys[2]), Err(MpcError::IntegrityFailure));
+        enrollment.receive(1, &partial, &keys[2]).unwrap();
+    }
+}
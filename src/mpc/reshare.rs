@@ -0,0 +1,336 @@
+//! Share redistribution to a new access structure.
+//!
+//! Moves a secret shared `(k, n)` among old holders to `(k', n')` among new holders without
+//! reconstructing it: every participating old holder `i` shares its own value `y_i` with a
+//! fresh degree `k' - 1` polynomial and sends sub-share `g_i(x_j)` to new holder `j`, who sets
+//! its share to `sum_i lambda_i * g_i(x_j)` with `lambda_i` the Lagrange weights of the old
+//! holder set at zero. This is the share-side step that should follow a
+//! `GapOperation::AddMember`, `RemoveMember`, `UpdatePolicy` or `SetWeight`.
+//!
+//! # Protocol
+//! 1. At least `k` old holders agree on the dealer set and on the new holders and `k'`.
+//! 2. Every dealer calls `reshare_deal` and sends sub-share `j` to new holder `j`.
+//! 3. Every new holder feeds each sub-share to `ReshareRound::receive`, then `finish`es.
+//! 4. The new holders run the consistency check below before the old holders erase.
+//!
+//! # Verification
+//! Sub-shares carry a one-time polynomial MAC tag (see `auth`) under the pairwise key between
+//! dealer and new holder, so a new holder rejects anything altered in transit or injected by
+//! a non-dealer. `receive` aborts with the same errors as `refresh::RefreshRound::receive`.
+//!
+//! # Consistency
+//! Tags do not show that a dealer's sub-shares lie on one polynomial of degree below `k'`.
+//! After `finish` the new holders check it jointly without opening the secret: each draws a
+//! random value and reshares it among the new holders (a second `reshare_deal` /
+//! `ReshareRound` over fresh keys), adds the resulting blind share to its new share and
+//! publishes the sum. The published shares are a sharing of `secret + blind` for a blind no
+//! single holder knows, and `check_consistency` confirms that any `k'` of them agree.
+//!
+//! The check needs more than `k'` new holders. It does not catch a dealer that reshares a
+//! wrong value on a consistent polynomial, which shifts the secret (unlike `refresh`, where
+//! every dealing must share the known value zero).
+//!
+//! # Security
+//! - **No Reconstruction**: No party ever holds more than its own share and sub-shares.
+//! - **Old Shares**: Old holders must erase their shares once resharing completes; the old
+//!   sharing still opens the secret.
+//! - **Zeroization**: Coefficients, weights and the running sum are zeroized.
+//! - **GF(2^8) only**: Version 1 shares, identifiers 1..=255 on both sides.
+
+extern crate alloc;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+use crate::core::gf256::{GF256, mul_add_slice};
+use crate::entropy::EntropySource;
+use crate::mpc::auth::{tag_pairwise, verify_pairwise, VerifierKeys};
+use crate::mpc::polynomial::evaluate_rows;
+use crate::mpc::reconstruct::{gf256_points, interpolate_at, lagrange_weights};
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF256}};
+
+/// Old holder side: shares `share.value` among `new_holders` with threshold `new_k`.
+///
+/// `keys` are the dealer's pairwise keys with the new holders (`keys.verifier()` must be
+/// `share.identifier`); every sub-share is tagged for its recipient, spending its key.
+///
+/// # Errors
+/// * `MpcError::InvalidThreshold` if `new_k < 2` or `new_k` exceeds the number of new holders.
+/// * `MpcError::InvalidShareIndex` on an out-of-range identifier, a dealer/key mismatch or a
+///   missing or spent pairwise key.
+/// * `MpcError::DuplicateShareIndex` on a repeated new holder.
+/// * `MpcError::RngFailure` on rng failure.
+pub fn reshare_deal<R: EntropySource + ?Sized>(
+    share: &Share,
+    new_holders: &[u16],
+    new_k: u8,
+    keys: &mut VerifierKeys,
+    rng: &mut R
+) -> Result<Vec<Share>, MpcError> {
+    share.gf256_x()?;
+    if keys.verifier() != share.identifier {
+        return Err(MpcError::InvalidShareIndex);
+    }
+    if new_k < 2 || new_k as usize > new_holders.len() {
+        return Err(MpcError::InvalidThreshold);
+    }
+    let mut seen = BTreeSet::new();
+    if !new_holders.iter().all(|&h| seen.insert(h)) {
+        return Err(MpcError::DuplicateShareIndex);
+    }
+
+    let len = share.value.len();
+    let mut rows = Zeroizing::new(vec![0u8; (new_k - 1) as usize * len]);
+    rng.fill(&mut rows[..]).map_err(|_| MpcError::RngFailure)?;
+
+    let mut out = Vec::with_capacity(new_holders.len());
+    for &h in new_holders {
+        let x = u8::try_from(h).ok().filter(|&x| x != 0).ok_or(MpcError::InvalidShareIndex)?;
+        let mut value = vec![0u8; len];
+        evaluate_rows(&rows, Some(&share.value), GF256(x), &mut value);
+        let mut sub = Share::new(h, value)?;
+        tag_pairwise(&mut sub, h, keys)?;
+        out.push(sub);
+    }
+    Ok(out)
+}
+
+/// A new holder's state during resharing.
+pub struct ReshareRound {
+    identifier: u16,
+    dealers: Vec<u16>,
+    weights: Zeroizing<Vec<GF256>>,
+    received: BTreeSet<u16>,
+    sum: Zeroizing<Vec<u8>>,
+}
+
+impl ReshareRound {
+    /// Opens a round for new holder `identifier`, expecting `len`-byte sub-shares from every
+    /// old holder in `dealers` (at least `old_k` of them).
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` if fewer than `old_k` dealers.
+    /// * `MpcError::InvalidShareIndex` / `MpcError::DuplicateShareIndex` on bad identifiers.
+    /// * `MpcError::EmptyShare` if `len` is zero.
+    pub fn new(identifier: u16, dealers: &[u16], old_k: u8, len: usize) -> Result<Self, MpcError> {
+        if identifier == 0 || identifier > u8::MAX as u16 {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if dealers.is_empty() || dealers.len() < old_k as usize {
+            return Err(MpcError::InsufficientShares);
+        }
+        if len == 0 {
+            return Err(MpcError::EmptyShare);
+        }
+        let mut seen = BTreeSet::new();
+        let mut xs = Vec::with_capacity(dealers.len());
+        for &d in dealers {
+            let x = u8::try_from(d).ok().filter(|&x| x != 0).ok_or(MpcError::InvalidShareIndex)?;
+            if !seen.insert(d) {
+                return Err(MpcError::DuplicateShareIndex);
+            }
+            xs.push(GF256(x));
+        }
+        Ok(Self {
+            identifier,
+            dealers: dealers.to_vec(),
+            weights: lagrange_weights(&xs, GF256(0)),
+            received: BTreeSet::new(),
+            sum: Zeroizing::new(vec![0u8; len]),
+        })
+    }
+
+    /// Verifies and absorbs the sub-share dealt by old holder `dealer`.
+    ///
+    /// `keys` are this new holder's pairwise keys, indexed by dealer.
+    pub fn receive(&mut self, dealer: u16, sub: &Share, keys: &VerifierKeys) -> Result<(), MpcError> {
+        let me = self.identifier;
+        let pos = self.dealers.iter().position(|&d| d == dealer);
+        let Some(pos) = pos.filter(|_| sub.identifier == me && keys.verifier() == me) else {
+            return Err(MpcError::InvalidShareIndex);
+        };
+        if self.received.contains(&dealer) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+        if sub.version != SHARE_VERSION_GF256 {
+            return Err(MpcError::UnsupportedVersion);
+        }
+        if sub.value.len() != self.sum.len() {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        verify_pairwise(sub, dealer, keys)?;
+        mul_add_slice(&mut self.sum, &sub.value, self.weights[pos]);
+        self.received.insert(dealer);
+        Ok(())
+    }
+
+    /// Old holders whose sub-share has not arrived yet.
+    pub fn missing(&self) -> Vec<u16> {
+        self.dealers.iter().copied().filter(|d| !self.received.contains(d)).collect()
+    }
+
+    /// The new share, untagged (tag it for the new verifiers with `auth`).
+    ///
+    /// Run `check_consistency` before relying on it.
+    ///
+    /// # Errors
+    /// * `MpcError::InsufficientShares` while any dealer is missing.
+    pub fn finish(mut self) -> Result<Share, MpcError> {
+        if self.received.len() != self.dealers.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        Share::new(self.identifier, core::mem::take(&mut *self.sum))
+    }
+}
+
+/// Checks that the published (blinded) new shares lie on one polynomial of degree below
+/// `new_k`, i.e. that every `new_k` of them agree (see module docs).
+///
+/// # Errors
+/// * `MpcError::InvalidThreshold` if `new_k < 2`.
+/// * `MpcError::InsufficientShares` unless more than `new_k` shares are given.
+/// * `MpcError::IntegrityFailure` if some share is off the polynomial through the others.
+/// * Otherwise as `reconstruct::interpolate_at` (lengths, duplicates, GF(2^8) shares only).
+pub fn check_consistency(opened: &[Share], new_k: u8) -> Result<(), MpcError> {
+    if new_k < 2 {
+        return Err(MpcError::InvalidThreshold);
+    }
+    if opened.len() <= new_k as usize {
+        return Err(MpcError::InsufficientShares);
+    }
+    gf256_points(opened)?;
+    let (base, rest) = opened.split_at(new_k as usize);
+    let mut diff = 0u8;
+    for share in rest {
+        let expected = Zeroizing::new(interpolate_at(base, share.identifier as u8)?);
+        diff |= expected.iter().zip(&share.value).fold(0u8, |d, (a, b)| d | (a ^ b));
+    }
+    if diff == 0 { Ok(()) } else { Err(MpcError::IntegrityFailure) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::auth::KEY_LEN;
+    use crate::mpc::test_util::{MockEntropy, pairwise_between};
+    use crate::mpc::quorum::split_secret;
+    use crate::mpc::reconstruct::reconstruct_secret;
+
+    #[test]
+    fn test_interpolate_at() {
+        let mut rng = MockEntropy { state: 1 };
+        let shares = split_secret(b"abc", 3, 5, &mut rng).unwrap();
+        assert_eq!(interpolate_at(&shares[..3], 0).unwrap(), b"abc");
+        // Any three shares determine the others.
+        assert_eq!(interpolate_at(&shares[1..4], 5).unwrap(), shares[4].value);
+        assert_eq!(interpolate_at(&[], 1), Err(MpcError::InsufficientShares));
+    }
+
+    /// Reshares `sources` among `holders`; returns the dealings and both sides' keys.
+    fn deal_all(sources: &[Share], holders: &[u16], new_k: u8, rng: &mut MockEntropy) -> (Vec<Vec<Share>>, Vec<VerifierKeys>, Vec<VerifierKeys>) {
+        let dealers: Vec<u16> = sources.iter().map(|s| s.identifier).collect();
+        let (mut dealer_keys, holder_keys) = pairwise_between(&dealers, holders, rng);
+        let dealings = sources
+            .iter()
+            .zip(&mut dealer_keys)
+            .map(|(s, keys)| reshare_deal(s, holders, new_k, keys, rng).unwrap())
+            .collect();
+        (dealings, dealer_keys, holder_keys)
+    }
+
+    /// Every holder absorbs its sub-share from every dealer and finishes.
+    fn collect(dealings: &[Vec<Share>], dealers: &[u16], holders: &[u16], old_k: u8, keys: &[VerifierKeys]) -> Vec<Share> {
+        let len = dealings[0][0].value.len();
+        let mut fresh = Vec::new();
+        for (j, &h) in holders.iter().enumerate() {
+            let mut round = ReshareRound::new(h, dealers, old_k, len).unwrap();
+            for (i, &d) in dealers.iter().enumerate() {
+                round.receive(d, &dealings[i][j], &keys[j]).unwrap();
+            }
+            fresh.push(round.finish().unwrap());
+        }
+        fresh
+    }
+
+    /// Adds a joint random resharing among the new holders to `fresh` (see module docs).
+    fn blinded(fresh: &[Share], new_k: u8, rng: &mut MockEntropy) -> Vec<Share> {
+        let holders: Vec<u16> = fresh.iter().map(|s| s.identifier).collect();
+        let mut randoms = Vec::new();
+        for &h in &holders {
+            let mut value = vec![0u8; fresh[0].value.len()];
+            rng.fill(&mut value).unwrap();
+            randoms.push(Share::new(h, value).unwrap());
+        }
+        let (dealings, _, keys) = deal_all(&randoms, &holders, new_k, rng);
+        let blinds = collect(&dealings, &holders, &holders, new_k, &keys);
+        let mut opened = fresh.to_vec();
+        for (o, b) in opened.iter_mut().zip(&blinds) {
+            o.value.iter_mut().zip(&b.value).for_each(|(x, y)| *x ^= y);
+        }
+        opened
+    }
+
+    #[test]
+    fn test_reshare_to_new_structure() {
+        let mut rng = MockEntropy { state: 21 };
+        let secret = b"master pad seed".to_vec();
+        // (3, 5) -> (4, 6) among identifiers 10..=15, using old holders 1, 3, 4, 5.
+        let old = split_secret(&secret, 3, 5, &mut rng).unwrap();
+        let dealers = [1u16, 3, 4, 5];
+        let holders: Vec<u16> = (10..16).collect();
+        let sources: Vec<Share> = dealers.iter().map(|&d| old[d as usize - 1].clone()).collect();
+        let (dealings, _, holder_keys) = deal_all(&sources, &holders, 4, &mut rng);
+        let fresh = collect(&dealings, &dealers, &holders, 3, &holder_keys);
+
+        assert_eq!(reconstruct_secret(&fresh[2..], 4).unwrap(), secret);
+        assert_ne!(interpolate_at(&fresh[..3], 0).unwrap(), secret);
+
+        // The blinded opening is consistent and does not reveal the secret.
+        let opened = blinded(&fresh, 4, &mut rng);
+        assert_eq!(check_consistency(&opened, 4), Ok(()));
+        assert_ne!(interpolate_at(&opened[..4], 0).unwrap(), secret);
+        assert_eq!(check_consistency(&opened[..4], 4), Err(MpcError::InsufficientShares));
+    }
+
+    #[test]
+    fn test_consistency_check_catches_bad_dealer() {
+        let mut rng = MockEntropy { state: 31 };
+        let old = split_secret(b"seed", 2, 3, &mut rng).unwrap();
+        let dealers = [1u16, 2];
+        let holders = [4u16, 5, 6, 7];
+        let (mut dealings, mut dealer_keys, mut holder_keys) = deal_all(&old[..2], &holders, 2, &mut rng);
+
+        // Dealer 2 sends holder 6 a well-tagged sub-share off its polynomial.
+        let sub = &mut dealings[1][2];
+        sub.value[0] ^= 0x10;
+        sub.tags.clear();
+        dealer_keys[1].insert(6, [0x3C; KEY_LEN]);
+        holder_keys[2].insert(2, [0x3C; KEY_LEN]);
+        tag_pairwise(sub, 6, &mut dealer_keys[1]).unwrap();
+
+        let fresh = collect(&dealings, &dealers, &holders, 2, &holder_keys);
+        let opened = blinded(&fresh, 2, &mut rng);
+        assert_eq!(check_consistency(&opened, 2), Err(MpcError::IntegrityFailure));
+    }
+
+    #[test]
+    fn test_reshare_rejects_bad_sub_shares() {
+        let mut rng = MockEntropy { state: 8 };
+        let old = split_secret(b"s", 2, 3, &mut rng).unwrap();
+        let (mut dealer_keys, holder_keys) = pairwise_between(&[1, 2], &[7, 8], &mut rng);
+        let from1 = reshare_deal(&old[0], &[7, 8], 2, &mut dealer_keys[0], &mut rng).unwrap();
+        let mut round = ReshareRound::new(7, &[1, 2], 2, 1).unwrap();
+
+        let mut bad = from1[0].clone();
+        bad.value[0] ^= 0x80;
+        assert_eq!(round.receive(1, &bad, &holder_keys[0]), Err(MpcError::IntegrityFailure));
+        assert_eq!(round.receive(1, &from1[1], &holder_keys[0]), Err(MpcError::InvalidShareIndex));
+        assert_eq!(round.receive(2, &from1[0], &holder_keys[0]), Err(MpcError::IntegrityFailure));
+        round.receive(1, &from1[0], &holder_keys[0]).unwrap();
+        assert_eq!(round.receive(1, &from1[0], &holder_keys[0]), Err(MpcError::DuplicateShareIndex));
+        assert_eq!(round.missing(), vec![2]);
+        assert_eq!(round.finish().map(|_| ()), Err(MpcError::InsufficientShares));
+
+        assert_eq!(ReshareRound::new(7, &[1], 2, 1).map(|_| ()), Err(MpcError::InsufficientShares));
+        assert_eq!(reshare_deal(&old[1], &[7, 8], 2, &mut dealer_keys[0], &mut rng), Err(MpcError::InvalidShareIndex));
+    }
+}
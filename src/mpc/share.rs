@@ -1,11 +1,21 @@
 //! Secret Share Definition.
 //!
 //! A share is a point $(x, y)$ on the polynomial used to hide the secret.
-//! - $x$ (identifier): A non-zero byte unique to each participant.
+//! - $x$ (identifier): A non-zero field element unique to each participant.
 //! - $y$ (value): The evaluation of the polynomial at $x$.
 //!
+//! # Versions
+//! `version` selects the field and the layout of `value`:
+//! - `1` (`SHARE_VERSION_GF256`): GF(2^8), one byte per secret byte, identifiers 1..=255.
+//! - `2` (`SHARE_VERSION_GF65536`): GF(2^16), identifiers 1..=65535. `value[0]` is the number
+//!   of padding bytes (0 or 1) appended to the secret, followed by little-endian 16-bit symbols.
+//! - `3` (`SHARE_VERSION_PACKED`): packed GF(2^8) (see `packed`). `value[0]` is the pack size ℓ,
+//!   `value[1]` the padding bytes (< ℓ) appended to the secret, then one byte per ℓ secret
+//!   bytes. Identifiers are 1..=255-ℓ (the top ℓ points carry the secret).
+//!
 //! # Security
 //! - Implements `Zeroize` and `ZeroizeOnDrop` to wipe sensitive data from memory.
+//! - `tags` carries one polynomial MAC tag per verifier (see `auth`); tags are public.
 //! - `Debug` implementation redacts the actual value.
 
 extern crate alloc;
@@ -13,26 +23,44 @@ use alloc::vec::Vec;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 use core::fmt;
 use super::MpcError;
+use crate::core::gf256::GF256;
+use crate::mpc::auth::TAG_LEN;
+
+/// Share format over GF(2^8).
+pub const SHARE_VERSION_GF256: u8 = 1;
+/// Share format over GF(2^16).
+pub const SHARE_VERSION_GF65536: u8 = 2;
+/// Packed share format over GF(2^8).
+pub const SHARE_VERSION_PACKED: u8 = 3;
 
 /// A share of a secret.
 ///
 /// Contains the x-coordinate (identifier) and the y-coordinate (value).
 #[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
 pub struct Share {
-    /// Protocol version.
+    /// Share format version; selects the field (see module docs).
     pub version: u8,
 
-    /// The x-coordinate (1..=255).
+    /// The x-coordinate (1..=255 for version 1, 1..=65535 for version 2, 1..=255-ℓ for version 3).
     /// Public information (who owns the share).
-    pub identifier: u8,
+    pub identifier: u16,
     
     /// The y-coordinates (one per byte of the secret).
     /// Highly sensitive information.
     pub value: Vec<u8>,
 
-    /// Optional integrity check (HMAC-SHA256).
-    /// If present, verifies the integrity of the identifier and value.
-    pub mac: Option<[u8; 32]>,
+    /// Information-theoretic tags, one per verifier (see `mpc::auth`).
+    /// Each binds the version, identifier and value under a dealer/verifier pairwise key.
+    pub tags: Vec<ShareTag>,
+}
+
+/// A one-time polynomial MAC tag on a share for one verifier.
+#[derive(Clone, Debug, PartialEq, Eq, Zeroize)]
+pub struct ShareTag {
+    /// Identifier of the member holding the verification key.
+    pub verifier: u16,
+    /// Polynomial MAC tag.
+    pub tag: [u8; TAG_LEN],
 }
 
 impl fmt::Debug for Share {
@@ -41,39 +69,89 @@ impl fmt::Debug for Share {
             .field("version", &self.version)
             .field("identifier", &self.identifier)
             .field("length", &self.value.len())
-            .field("mac", &self.mac.is_some())
+            .field("tags", &self.tags.len())
             .field("value", &"***SENSITIVE***")
             .finish()
     }
 }
 
 impl Share {
-    /// Creates a new share with validation.
+    /// Creates a new GF(2^8) share with validation.
     ///
-    /// Sets version to 1 and mac to None by default.
+    /// Sets version to 1 and no tags by default.
     ///
     /// # Arguments
-    /// * `identifier` - The x-coordinate (must be non-zero).
+    /// * `identifier` - The x-coordinate (must be in 1..=255).
     /// * `value` - The y-coordinate vector (must not be empty).
     ///
     /// # Returns
     /// * `Ok(Share)` if valid.
     /// * `Err(MpcError)` if invalid.
-    pub fn new(identifier: u8, value: Vec<u8>) -> Result<Self, MpcError> {
-        if identifier == 0 {
-            return Err(MpcError::InvalidShareIndex);
-        }
-        if value.is_empty() {
-            return Err(MpcError::EmptyShare);
+    pub fn new(identifier: u16, value: Vec<u8>) -> Result<Self, MpcError> {
+        Self::with_version(SHARE_VERSION_GF256, identifier, value)
+    }
+
+    /// Creates a share of the given format version with validation.
+    ///
+    /// # Errors
+    /// * `MpcError::UnsupportedVersion` for an unknown version.
+    /// * `MpcError::InvalidShareIndex` if the identifier is zero or outside the field.
+    /// * `MpcError::EmptyShare` / `MpcError::ShareLengthMismatch` if `value` does not fit the layout.
+    pub fn with_version(version: u8, identifier: u16, value: Vec<u8>) -> Result<Self, MpcError> {
+        match version {
+            SHARE_VERSION_GF256 => {
+                if identifier == 0 || identifier > u8::MAX as u16 {
+                    return Err(MpcError::InvalidShareIndex);
+                }
+                if value.is_empty() {
+                    return Err(MpcError::EmptyShare);
+                }
+            }
+            SHARE_VERSION_GF65536 => {
+                if identifier == 0 {
+                    return Err(MpcError::InvalidShareIndex);
+                }
+                if value.len() < 3 {
+                    return Err(MpcError::EmptyShare);
+                }
+                if value.len() % 2 == 0 || value[0] > 1 {
+                    return Err(MpcError::ShareLengthMismatch);
+                }
+            }
+            SHARE_VERSION_PACKED => {
+                if value.len() < 3 {
+                    return Err(MpcError::EmptyShare);
+                }
+                if value[0] == 0 || value[1] >= value[0] {
+                    return Err(MpcError::ShareLengthMismatch);
+                }
+                if identifier == 0 || identifier as usize + value[0] as usize > u8::MAX as usize {
+                    return Err(MpcError::InvalidShareIndex);
+                }
+            }
+            _ => return Err(MpcError::UnsupportedVersion),
         }
         Ok(Self { 
-            version: 1,
+            version,
             identifier, 
             value,
-            mac: None 
+            tags: Vec::new(),
         })
     }
 
+    /// The tag for `verifier`, if the dealer attached one.
+    pub fn tag_for(&self, verifier: u16) -> Option<&ShareTag> {
+        self.tags.iter().find(|t| t.verifier == verifier)
+    }
+
+    /// The identifier as a GF(2^8) point, for version 1 shares.
+    pub(crate) fn gf256_x(&self) -> Result<GF256, MpcError> {
+        if self.version != SHARE_VERSION_GF256 {
+            return Err(MpcError::UnsupportedVersion);
+        }
+        u8::try_from(self.identifier).map(GF256).map_err(|_| MpcError::InvalidShareIndex)
+    }
+
     /// Returns a reference to the value bytes.
     pub fn value(&self) -> &[u8] {
         &self.value
@@ -95,6 +173,22 @@ mod tests {
     fn test_share_validation() {
         assert_eq!(Share::new(0, alloc::vec![1]), Err(MpcError::InvalidShareIndex));
         assert_eq!(Share::new(1, alloc::vec![]), Err(MpcError::EmptyShare));
+        assert_eq!(Share::new(256, alloc::vec![1]), Err(MpcError::InvalidShareIndex));
+    }
+
+    #[test]
+    fn test_share_versions() {
+        let wide = Share::with_version(SHARE_VERSION_GF65536, 300, alloc::vec![1, 0xAA, 0x00]).unwrap();
+        assert_eq!(wide.gf256_x(), Err(MpcError::UnsupportedVersion));
+        assert_eq!(Share::new(7, alloc::vec![1]).unwrap().gf256_x(), Ok(GF256(7)));
+        // Symbols must be whole and the padding count 0 or 1.
+        assert_eq!(Share::with_version(2, 1, alloc::vec![0, 1, 2, 3]), Err(MpcError::ShareLengthMismatch));
+        assert_eq!(Share::with_version(2, 1, alloc::vec![2, 1, 2]), Err(MpcError::ShareLengthMismatch));
+        assert_eq!(Share::with_version(9, 1, alloc::vec![1]), Err(MpcError::UnsupportedVersion));
+        // Packed: identifiers must stay below the ℓ secret points.
+        assert!(Share::with_version(SHARE_VERSION_PACKED, 251, alloc::vec![4, 3, 9]).is_ok());
+        assert_eq!(Share::with_version(3, 252, alloc::vec![4, 3, 9]), Err(MpcError::InvalidShareIndex));
+        assert_eq!(Share::with_version(3, 1, alloc::vec![4, 4, 9]), Err(MpcError::ShareLengthMismatch));
     }
 
     #[test]
@@ -0,0 +1,299 @@
+//! Streaming split and reconstruction for large secrets.
+//!
+//! `quorum::split_secret` holds the secret and all `n` shares in memory. Here the secret is
+//! read from a `Read` and each share written to its own `Write` in fixed-size chunks, so a
+//! 1 GB pad can be shared with a few chunk buffers of memory. Reconstruction reads `k` or
+//! more share streams back into one `Write`, with the Lagrange weights computed once
+//! (`ReconstructionContext`).
+//!
+//! # Share Stream Format
+//! - Header: `[magic "TGSS" (4)] [format (1)] [identifier u16 LE] [k (1)] [chunk_len u32 LE]`
+//! - Chunks: `[flags (1)] [len u32 LE] [data (len)] [trailer (16)]`
+//!
+//! `chunk_len` is at most `STREAM_CHUNK` and is checked before any buffer is allocated.
+//! The last chunk is an empty chunk with `flags = FINAL`, and nothing may follow it. The
+//! trailer is BLAKE3 (derived key `TOGM_SHARE_STREAM_CHUNK`) over the identifier, `k`, the
+//! chunk index, flags, length and data, so a truncated, reordered or corrupted share stream
+//! is detected.
+//!
+//! # Security
+//! - **Zeroization**: Secret, coefficient and share chunk buffers are zeroized.
+//! - **Integrity**: Trailers catch accidents and truncation, not an adversary (they are
+//!   unkeyed); use `auth` tags for that.
+//! - **Partial Output**: Reconstruction writes as it goes; on error, discard what was written.
+
+extern crate alloc;
+extern crate std;
+use alloc::vec::Vec;
+use std::io::{ErrorKind, Read, Write};
+use zeroize::Zeroizing;
+use crate::core::gf256::GF256;
+use crate::entropy::EntropySource;
+use crate::mpc::polynomial::evaluate_rows;
+use crate::mpc::reconstruct::ReconstructionContext;
+use crate::mpc::MpcError;
+
+/// Secret bytes per chunk written by `split_stream`.
+pub const STREAM_CHUNK: usize = 64 * 1024;
+
+const MAGIC: &[u8; 4] = b"TGSS";
+const FORMAT: u8 = 1;
+const HEADER_LEN: usize = 12;
+const TRAILER_LEN: usize = 16;
+const FINAL: u8 = 1;
+
+/// Splits the secret read from `secret` into `sinks.len()` share streams with threshold `k`.
+///
+/// Sink `i` receives the share with identifier `i + 1`.
+///
+/// # Returns
+/// * `Ok(bytes)` - the secret length.
+/// * `Err(MpcError)` on invalid parameters, an empty secret, rng or I/O failure.
+pub fn split_stream<R: Read, W: Write, E: EntropySource + ?Sized>(
+    secret: &mut R,
+    sinks: &mut [W],
+    k: u8,
+    rng: &mut E
+) -> Result<u64, MpcError> {
+    let n = u8::try_from(sinks.len()).map_err(|_| MpcError::InvalidShareIndex)?;
+    if k < 2 || k > n {
+        return Err(MpcError::InvalidThreshold);
+    }
+
+    let mut chunk = Zeroizing::new(vec![0u8; STREAM_CHUNK]);
+    let mut rows = Zeroizing::new(vec![0u8; (k - 1) as usize * STREAM_CHUNK]);
+    let mut eval = Zeroizing::new(vec![0u8; STREAM_CHUNK]);
+
+    let mut len = read_full(secret, &mut chunk)?;
+    if len == 0 {
+        return Err(MpcError::EmptyShare);
+    }
+    for (i, sink) in sinks.iter_mut().enumerate() {
+        write_header(sink, i as u16 + 1, k)?;
+    }
+
+    let mut total = 0u64;
+    let mut index = 0u64;
+    while len > 0 {
+        let rows = &mut rows[..(k - 1) as usize * len];
+        rng.fill(rows).map_err(|_| MpcError::RngFailure)?;
+        for (i, sink) in sinks.iter_mut().enumerate() {
+            let y = &mut eval[..len];
+            evaluate_rows(rows, Some(&chunk[..len]), GF256(i as u8 + 1), y);
+            write_chunk(sink, i as u16 + 1, k, index, 0, y)?;
+        }
+        total += len as u64;
+        index += 1;
+        len = read_full(secret, &mut chunk)?;
+    }
+    for (i, sink) in sinks.iter_mut().enumerate() {
+        write_chunk(sink, i as u16 + 1, k, index, FINAL, &[])?;
+        sink.flush().map_err(|_| MpcError::Io)?;
+    }
+    Ok(total)
+}
+
+/// Reconstructs the secret from `k` or more share streams into `out`.
+///
+/// # Returns
+/// * `Ok(bytes)` - the secret length.
+///
+/// # Errors
+/// * `MpcError::IntegrityFailure` on a bad header (including `chunk_len` above
+///   `STREAM_CHUNK`), trailer, truncation, bytes after the final chunk or misaligned streams.
+/// * `MpcError::InsufficientShares`, `DuplicateShareIndex` as for `reconstruct_secret`.
+/// * `MpcError::Io` on I/O failure.
+pub fn reconstruct_stream<R: Read, W: Write>(
+    shares: &mut [R],
+    out: &mut W
+) -> Result<u64, MpcError> {
+    if shares.is_empty() {
+        return Err(MpcError::InsufficientShares);
+    }
+    let mut identifiers = Vec::with_capacity(shares.len());
+    let mut params = None;
+    for r in shares.iter_mut() {
+        let (id, k, chunk_len) = read_header(r)?;
+        if *params.get_or_insert((k, chunk_len)) != (k, chunk_len) {
+            return Err(MpcError::IntegrityFailure);
+        }
+        identifiers.push(id);
+    }
+    let (k, chunk_len) = params.ok_or(MpcError::InsufficientShares)?;
+    if k < 2 {
+        return Err(MpcError::IntegrityFailure);
+    }
+    let ctx = ReconstructionContext::new(&identifiers, k)?;
+
+    let mut bufs: Vec<Zeroizing<Vec<u8>>> = (0..shares.len()).map(|_| Zeroizing::new(vec![0u8; chunk_len])).collect();
+    let mut secret = Zeroizing::new(vec![0u8; chunk_len]);
+    let mut total = 0u64;
+    let mut index = 0u64;
+    loop {
+        let mut frame = None;
+        for ((r, buf), &id) in shares.iter_mut().zip(bufs.iter_mut()).zip(&identifiers) {
+            let (flags, len) = read_chunk(r, id, k, index, buf)?;
+            if *frame.get_or_insert((flags, len)) != (flags, len) {
+                return Err(MpcError::IntegrityFailure);
+            }
+        }
+        let (flags, len) = frame.ok_or(MpcError::InsufficientShares)?;
+        if flags == FINAL {
+            for r in shares.iter_mut() {
+                if read_full(r, &mut [0u8; 1])? != 0 {
+                    return Err(MpcError::IntegrityFailure);
+                }
+            }
+            return Ok(total);
+        }
+        let blocks: Vec<&[u8]> = bufs.iter().map(|b| &b[..len]).collect();
+        ctx.reconstruct_into(&blocks, &mut secret[..len])?;
+        out.write_all(&secret[..len]).map_err(|_| MpcError::Io)?;
+        total += len as u64;
+        index += 1;
+    }
+}
+
+fn write_header<W: Write>(w: &mut W, id: u16, k: u8) -> Result<(), MpcError> {
+    let mut h = [0u8; HEADER_LEN];
+    h[..4].copy_from_slice(MAGIC);
+    h[4] = FORMAT;
+    h[5..7].copy_from_slice(&id.to_le_bytes());
+    h[7] = k;
+    h[8..].copy_from_slice(&(STREAM_CHUNK as u32).to_le_bytes());
+    w.write_all(&h).map_err(|_| MpcError::Io)
+}
+
+fn read_header<R: Read>(r: &mut R) -> Result<(u16, u8, usize), MpcError> {
+    let mut h = [0u8; HEADER_LEN];
+    if read_full(r, &mut h)? != HEADER_LEN || &h[..4] != MAGIC || h[4] != FORMAT {
+        return Err(MpcError::IntegrityFailure);
+    }
+    let chunk_len = u32::from_le_bytes([h[8], h[9], h[10], h[11]]) as usize;
+    if chunk_len == 0 || chunk_len > STREAM_CHUNK {
+        return Err(MpcError::IntegrityFailure);
+    }
+    Ok((u16::from_le_bytes([h[5], h[6]]), h[7], chunk_len))
+}
+
+fn write_chunk<W: Write>(w: &mut W, id: u16, k: u8, index: u64, flags: u8, data: &[u8]) -> Result<(), MpcError> {
+    let mut head = [0u8; 5];
+    head[0] = flags;
+    head[1..].copy_from_slice(&(data.len() as u32).to_le_bytes());
+    let trailer = chunk_trailer(id, k, index, &head, data);
+    w.write_all(&head).map_err(|_| MpcError::Io)?;
+    w.write_all(data).map_err(|_| MpcError::Io)?;
+    w.write_all(&trailer).map_err(|_| MpcError::Io)
+}
+
+/// Reads chunk `index` into `buf`, returning its flags and length.
+fn read_chunk<R: Read>(r: &mut R, id: u16, k: u8, index: u64, buf: &mut [u8]) -> Result<(u8, usize), MpcError> {
+    let mut head = [0u8; 5];
+    if read_full(r, &mut head)? != head.len() {
+        return Err(MpcError::IntegrityFailure);
+    }
+    let len = u32::from_le_bytes([head[1], head[2], head[3], head[4]]) as usize;
+    if len > buf.len() || head[0] & !FINAL != 0 || (head[0] == FINAL) != (len == 0) {
+        return Err(MpcError::IntegrityFailure);
+    }
+    let mut trailer = [0u8; TRAILER_LEN];
+    if read_full(r, &mut buf[..len])? != len || read_full(r, &mut trailer)? != TRAILER_LEN {
+        return Err(MpcError::IntegrityFailure);
+    }
+    let expected = chunk_trailer(id, k, index, &head, &buf[..len]);
+    if expected.iter().zip(trailer.iter()).fold(0u8, |d, (x, y)| d | (x ^ y)) != 0 {
+        return Err(MpcError::IntegrityFailure);
+    }
+    Ok((head[0], len))
+}
+
+fn chunk_trailer(id: u16, k: u8, index: u64, head: &[u8; 5], data: &[u8]) -> [u8; TRAILER_LEN] {
+    let mut h = blake3::Hasher::new_derive_key("TOGM_SHARE_STREAM_CHUNK");
+    h.update(&id.to_le_bytes());
+    h.update(&[k]);
+    h.update(&index.to_le_bytes());
+    h.update(head);
+    h.update(data);
+    let mut out = [0u8; TRAILER_LEN];
+    out.copy_from_slice(&h.finalize().as_bytes()[..TRAILER_LEN]);
+    out
+}
+
+/// Reads until `buf` is full or the stream ends; returns the bytes read.
+fn read_full<R: Read>(r: &mut R, buf: &mut [u8]) -> Result<usize, MpcError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match r.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(m) => filled += m,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(_) => return Err(MpcError::Io),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::MockEntropy;
+    use std::io::Cursor;
+
+    fn split(secret: &[u8], k: u8, n: usize) -> Vec<Vec<u8>> {
+        let mut rng = MockEntropy { state: 42 };
+        let mut sinks: Vec<Vec<u8>> = vec![Vec::new(); n];
+        let len = split_stream(&mut Cursor::new(secret), &mut sinks, k, &mut rng).unwrap();
+        assert_eq!(len, secret.len() as u64);
+        sinks
+    }
+
+    #[test]
+    fn test_stream_roundtrip_multi_chunk() {
+        let secret: Vec<u8> = (0..(2 * STREAM_CHUNK + 1234) as u32).map(|i| (i % 253) as u8).collect();
+        let files = split(&secret, 3, 5);
+        // Three data chunks plus the final marker.
+        assert_eq!(files[0].len(), HEADER_LEN + secret.len() + 4 * (5 + TRAILER_LEN));
+
+        let mut readers: Vec<Cursor<&[u8]>> = [4, 0, 2].iter().map(|&i| Cursor::new(&files[i][..])).collect();
+        let mut out = Vec::new();
+        assert_eq!(reconstruct_stream(&mut readers, &mut out).unwrap(), secret.len() as u64);
+        assert_eq!(out, secret);
+    }
+
+    #[test]
+    fn test_stream_detects_truncation_and_corruption() {
+        let secret = vec![0x3Cu8; 5000];
+        let files = split(&secret, 2, 3);
+        let open = |a: &[u8], b: &[u8]| {
+            let mut readers = [Cursor::new(a.to_vec()), Cursor::new(b.to_vec())];
+            reconstruct_stream(&mut readers, &mut Vec::new())
+        };
+        assert_eq!(open(&files[0], &files[1]), Ok(5000));
+
+        // Missing final marker, cut mid-chunk, flipped bit.
+        let cut = files[1].len() - (5 + TRAILER_LEN);
+        assert_eq!(open(&files[0], &files[1][..cut]), Err(MpcError::IntegrityFailure));
+        assert_eq!(open(&files[0], &files[1][..100]), Err(MpcError::IntegrityFailure));
+        let mut flipped = files[1].clone();
+        flipped[HEADER_LEN + 40] ^= 1;
+        assert_eq!(open(&files[0], &flipped), Err(MpcError::IntegrityFailure));
+
+        // Trailing bytes after the final marker.
+        let mut trailing = files[1].clone();
+        trailing.push(0);
+        assert_eq!(open(&files[0], &trailing), Err(MpcError::IntegrityFailure));
+
+        // Oversized chunk length in the header, refused before allocating.
+        let mut huge = files[1].clone();
+        huge[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(open(&files[0], &huge), Err(MpcError::IntegrityFailure));
+
+        // Same share twice, too few shares, empty secret.
+        assert_eq!(open(&files[2], &files[2]), Err(MpcError::DuplicateShareIndex));
+        let mut one = [Cursor::new(files[0].clone())];
+        assert_eq!(reconstruct_stream(&mut one, &mut Vec::new()), Err(MpcError::InsufficientShares));
+        let mut sinks = vec![Vec::new(); 2];
+        let mut rng = MockEntropy { state: 1 };
+        assert_eq!(split_stream(&mut Cursor::new(&[][..]), &mut sinks, 2, &mut rng), Err(MpcError::EmptyShare));
+    }
+}
@@ -0,0 +1,52 @@
+//! Shared test fixtures: a deterministic entropy source and pairwise `auth` keys.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use crate::entropy::{EntropyError, EntropySource};
+use crate::mpc::auth::{VerifierKeys, KEY_LEN};
+
+/// LCG-driven entropy, reproducible from `state`.
+pub(crate) struct MockEntropy {
+    pub(crate) state: u64,
+}
+
+impl EntropySource for MockEntropy {
+    fn name(&self) -> &'static str { "Mock" }
+    fn fill(&mut self, dest: &mut [u8]) -> Result<(), EntropyError> {
+        for b in dest.iter_mut() {
+            self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            *b = (self.state >> 56) as u8;
+        }
+        Ok(())
+    }
+    fn entropy_estimate(&self) -> f64 { 8.0 }
+}
+
+/// Symmetric pairwise keys: `keys[p][q] == keys[q][p]`.
+pub(crate) fn pairwise(ids: &[u16], rng: &mut MockEntropy) -> Vec<VerifierKeys> {
+    let mut keys: Vec<VerifierKeys> = ids.iter().map(|&p| VerifierKeys::new(p)).collect();
+    for a in 0..ids.len() {
+        for b in a + 1..ids.len() {
+            let mut key = [0u8; KEY_LEN];
+            rng.fill(&mut key).unwrap();
+            keys[a].insert(ids[b], key);
+            keys[b].insert(ids[a], key);
+        }
+    }
+    keys
+}
+
+/// Keys between two disjoint roles: `left[i][r] == right[j][l]`.
+pub(crate) fn pairwise_between(left: &[u16], right: &[u16], rng: &mut MockEntropy) -> (Vec<VerifierKeys>, Vec<VerifierKeys>) {
+    let mut lk: Vec<VerifierKeys> = left.iter().map(|&l| VerifierKeys::new(l)).collect();
+    let mut rk: Vec<VerifierKeys> = right.iter().map(|&r| VerifierKeys::new(r)).collect();
+    for (i, &l) in left.iter().enumerate() {
+        for (j, &r) in right.iter().enumerate() {
+            let mut key = [0u8; KEY_LEN];
+            rng.fill(&mut key).unwrap();
+            lk[i].insert(r, key);
+            rk[j].insert(l, key);
+        }
+    }
+    (lk, rk)
+}
@@ -0,0 +1,203 @@
+//! Shamir's Secret Sharing over GF(2^16).
+//!
+//! Lifts the 255-member cap of the GF(256) scheme: identifiers are 16-bit, so up to
+//! 65535 shares can be issued. The secret is zero-padded to an even length and read as
+//! little-endian 16-bit symbols, each shared with its own random polynomial. Shares use
+//! format version 2 (see `share`); the padding count travels in `value[0]`.
+//!
+//! # Security
+//! - **Constant-Time**: Uses `GF65536` arithmetic which is branch-free.
+//! - **Zeroization**: Coefficients, symbols and Lagrange weights are zeroized after use.
+//! - **Validation**: Same checks as the GF(256) path ($2 \le k \le n$, duplicates, lengths).
+//!
+//! # Whitepaper Compliance
+//! - Section 2.3/2.4: Polynomial Generation and Share Calculation.
+//! - Section 2.5: Secret Reconstruction.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use alloc::collections::BTreeSet;
+use zeroize::Zeroizing;
+use crate::core::gf65536::GF65536;
+use crate::mpc::{MpcError, share::{Share, SHARE_VERSION_GF65536}};
+use crate::mpc::auth::reject_tagged;
+use crate::entropy::EntropySource;
+
+/// Symbols of secret evaluated per pass in `split_secret`.
+const SPLIT_CHUNK: usize = 2048;
+
+/// Splits a secret into `n` version 2 shares, requiring `k` shares to reconstruct.
+///
+/// # Returns
+/// * `Ok(Vec<Share>)` containing `n` shares with identifiers `1..=n`.
+/// * `Err(MpcError)` on invalid parameters or rng failure.
+pub fn split_secret<R: EntropySource + ?Sized>(
+    secret: &[u8],
+    k: u16,
+    n: u16,
+    rng: &mut R
+) -> Result<Vec<Share>, MpcError> {
+    if secret.is_empty() {
+        return Err(MpcError::EmptyShare);
+    }
+    if k < 2 || k > n {
+        return Err(MpcError::InvalidThreshold);
+    }
+
+    let padding = (secret.len() % 2) as u8;
+    let symbols = to_symbols(secret);
+    let degree = (k - 1) as usize;
+
+    let mut share_values: Vec<Vec<u8>> = Vec::with_capacity(n as usize);
+    for _ in 0..n {
+        let mut v = Vec::with_capacity(1 + 2 * symbols.len());
+        v.push(padding);
+        share_values.push(v);
+    }
+
+    // coeffs[d * len + j] is a_{d+1} for symbol j of the chunk.
+    let mut random_bytes = Zeroizing::new(vec![0u8; degree * SPLIT_CHUNK * 2]);
+    let mut coeffs = Zeroizing::new(vec![GF65536(0); degree * SPLIT_CHUNK]);
+
+    for chunk in symbols.chunks(SPLIT_CHUNK) {
+        let len = chunk.len();
+        let bytes = &mut random_bytes[..degree * len * 2];
+        if rng.fill(bytes).is_err() {
+            return Err(MpcError::RngFailure);
+        }
+        for (c, b) in coeffs.iter_mut().zip(bytes.chunks_exact(2)) {
+            *c = GF65536(u16::from_le_bytes([b[0], b[1]]));
+        }
+
+        for (i, values) in share_values.iter_mut().enumerate() {
+            let x = GF65536(i as u16 + 1);
+            for (j, &s) in chunk.iter().enumerate() {
+                // Horner: f(x) = a0 + x(a1 + x(a2 + ...))
+                let mut y = GF65536(0);
+                for d in (0..degree).rev() {
+                    y = (y + coeffs[d * len + j]) * x;
+                }
+                y += GF65536(s);
+                values.extend_from_slice(&y.0.to_le_bytes());
+            }
+        }
+    }
+
+    let mut shares = Vec::with_capacity(n as usize);
+    for (i, values) in share_values.into_iter().enumerate() {
+        shares.push(Share::with_version(SHARE_VERSION_GF65536, (i + 1) as u16, values)?);
+    }
+    Ok(shares)
+}
+
+/// Reconstructs the secret from version 2 shares by Lagrange interpolation at x=0.
+///
+/// # Errors
+/// * `MpcError::UnsupportedVersion` if any share is not a GF(2^16) share.
+/// * `MpcError::InsufficientShares`, `DuplicateShareIndex`, `ShareLengthMismatch` as for GF(256).
+/// * `MpcError::IntegrityFailure` if any share carries a tag (see `reconstruct_authenticated`).
+pub fn reconstruct_secret(shares: &[Share], k: u16) -> Result<Vec<u8>, MpcError> {
+    reject_tagged(shares)?;
+    reconstruct_unchecked(shares, k)
+}
+
+/// `reconstruct_secret` without the tag check, for shares already verified.
+pub(crate) fn reconstruct_unchecked(shares: &[Share], k: u16) -> Result<Vec<u8>, MpcError> {
+    if shares.is_empty() || shares.len() < k as usize {
+        return Err(MpcError::InsufficientShares);
+    }
+    let layout = (shares[0].value.len(), shares[0].value[0]);
+    let mut seen = BTreeSet::new();
+    for share in shares {
+        if share.version != SHARE_VERSION_GF65536 {
+            return Err(MpcError::UnsupportedVersion);
+        }
+        if (share.value.len(), share.value[0]) != layout {
+            return Err(MpcError::ShareLengthMismatch);
+        }
+        if share.identifier == 0 {
+            return Err(MpcError::InvalidShareIndex);
+        }
+        if !seen.insert(share.identifier) {
+            return Err(MpcError::DuplicateShareIndex);
+        }
+    }
+
+    // lambda_j = prod_{m != j} x_m / (x_m + x_j)
+    let mut lambdas = Zeroizing::new(Vec::with_capacity(shares.len()));
+    for (j, sj) in shares.iter().enumerate() {
+        let xj = GF65536(sj.identifier);
+        let mut numerator = GF65536(1);
+        let mut denominator = GF65536(1);
+        for (m, sm) in shares.iter().enumerate() {
+            if j == m {
+                continue;
+            }
+            let xm = GF65536(sm.identifier);
+            numerator *= xm;
+            denominator *= xm + xj;
+        }
+        lambdas.push(numerator * denominator.inv());
+    }
+
+    let num_symbols = (layout.0 - 1) / 2;
+    let mut acc = Zeroizing::new(vec![GF65536(0); num_symbols]);
+    for (share, &lambda) in shares.iter().zip(lambdas.iter()) {
+        for (a, y) in acc.iter_mut().zip(share.value[1..].chunks_exact(2)) {
+            *a += GF65536(u16::from_le_bytes([y[0], y[1]])) * lambda;
+        }
+    }
+
+    let mut secret = Vec::with_capacity(2 * num_symbols);
+    for a in acc.iter() {
+        secret.extend_from_slice(&a.0.to_le_bytes());
+    }
+    secret.truncate(2 * num_symbols - layout.1 as usize);
+    Ok(secret)
+}
+
+// Little-endian 16-bit symbols of `secret`, zero-padded to an even length.
+fn to_symbols(secret: &[u8]) -> Zeroizing<Vec<u16>> {
+    let mut symbols = Zeroizing::new(Vec::with_capacity(secret.len().div_ceil(2)));
+    for pair in secret.chunks(2) {
+        let hi = pair.get(1).copied().unwrap_or(0);
+        symbols.push(u16::from_le_bytes([pair[0], hi]));
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::test_util::MockEntropy;
+    use crate::mpc::{SecretSharingScheme, ShamirGF65536, ShamirGF256};
+
+    #[test]
+    fn test_split_beyond_255_members() {
+        let mut rng = MockEntropy { state: 1 };
+        let secret = b"odd-length group key".to_vec();
+        let shares = ShamirGF65536.split(&secret, 3, 500, &mut rng).unwrap();
+        assert_eq!(shares.len(), 500);
+        assert_eq!(shares[499].identifier, 500);
+        assert!(shares.iter().all(|s| s.version == SHARE_VERSION_GF65536));
+
+        let subset = [shares[499].clone(), shares[0].clone(), shares[255].clone()];
+        assert_eq!(ShamirGF65536.reconstruct(&subset, 3).unwrap(), secret);
+        // Two shares reveal nothing useful: interpolation gives a different value.
+        assert_ne!(reconstruct_secret(&subset[..2], 2).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_reconstruct_errors() {
+        let mut rng = MockEntropy { state: 7 };
+        let shares = split_secret(&[1, 2, 3, 4], 2, 4, &mut rng).unwrap();
+        assert_eq!(reconstruct_secret(&shares[..1], 2), Err(MpcError::InsufficientShares));
+        assert_eq!(reconstruct_secret(&[shares[1].clone(), shares[1].clone()], 2), Err(MpcError::DuplicateShareIndex));
+        let narrow = Share::new(3, vec![1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(reconstruct_secret(&[shares[0].clone(), narrow.clone()], 2), Err(MpcError::UnsupportedVersion));
+        // And the GF(256) path refuses wide shares and more than 255 members.
+        assert_eq!(ShamirGF256.reconstruct(&shares, 2), Err(MpcError::UnsupportedVersion));
+        assert_eq!(ShamirGF256.split(&vec![1], 2, 256, &mut rng), Err(MpcError::InvalidShareIndex));
+        assert_eq!(split_secret(&[1], 5, 4, &mut rng), Err(MpcError::InvalidThreshold));
+    }
+}
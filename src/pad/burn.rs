@@ -9,10 +9,14 @@
 //! - **Memory Barriers**: Ensures writes are committed (best effort in portable Rust).
 //! - **Paranoid Mode**: Optional multi-pass overwriting (0x00 -> 0xFF -> Random -> 0x00)
 //!   when the `paranoid` feature is enabled.
+//! - **Burn After Use**: `BurnGuard` hands out a pad region and burns it when dropped,
+//!   so consumed keystream does not outlive its single use.
 //!
 //! # Whitepaper Compliance
 //! - Section 8: Emergency Destruction.
 
+use core::fmt;
+use core::ops::Deref;
 use zeroize::Zeroize;
 
 /// Irreversibly destroys the provided memory slice.
@@ -51,6 +55,60 @@ pub fn burn_slice(slice: &mut [u8]) {
     core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
 }
 
+/// Borrowed pad region that is burned with `burn_slice` when dropped.
+///
+/// Dereferences to the bytes for the duration of their single use; copies made by the
+/// caller are the caller's responsibility.
+pub struct BurnGuard<'a> {
+    slice: &'a mut [u8],
+}
+
+impl<'a> BurnGuard<'a> {
+    /// Wraps a region that must be burned once the guard goes out of scope.
+    pub fn new(slice: &'a mut [u8]) -> Self {
+        Self { slice }
+    }
+}
+
+impl Deref for BurnGuard<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.slice
+    }
+}
+
+impl Drop for BurnGuard<'_> {
+    fn drop(&mut self) {
+        burn_slice(self.slice);
+    }
+}
+
+impl PartialEq for BurnGuard<'_> {
+    /// Constant-time over the contents; lengths are public.
+    fn eq(&self, other: &Self) -> bool {
+        if self.slice.len() != other.slice.len() {
+            return false;
+        }
+        let mut diff = 0u8;
+        for (a, b) in self.slice.iter().zip(other.slice.iter()) {
+            diff |= a ^ b;
+        }
+        diff == 0
+    }
+}
+
+impl Eq for BurnGuard<'_> {}
+
+impl fmt::Debug for BurnGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BurnGuard")
+            .field("length", &self.slice.len())
+            .field("value", &"***SENSITIVE***")
+            .finish()
+    }
+}
+
 /// Trait for objects that can be burned.
 pub trait Burnable {
     /// Destroys the object's sensitive contents.
@@ -83,4 +141,15 @@ mod tests {
         burn_slice(&mut secret);
         assert_eq!(secret, vec![0, 0, 0, 0]);
     }
+
+    #[test]
+    fn test_burn_guard_burns_on_drop() {
+        let mut pad = vec![0xAAu8; 8];
+        {
+            let guard = BurnGuard::new(&mut pad[2..6]);
+            assert_eq!(&*guard, &[0xAA; 4]);
+            assert!(!alloc::format!("{:?}", guard).contains("170"));
+        }
+        assert_eq!(pad, vec![0xAA, 0xAA, 0, 0, 0, 0, 0xAA, 0xAA]);
+    }
 }
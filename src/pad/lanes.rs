@@ -0,0 +1,500 @@
+//! Per-Sender Pad Lanes.
+//!
+//! Splits the shared Master Pad deterministically into disjoint per-member regions so
+//! that concurrent senders never draw the same keystream. Every member derives the same
+//! layout from the same parameters; each member only advances its own lane.
+//!
+//! # Layouts
+//! - `Static`: lane `i` owns one contiguous slice of the pad.
+//! - `Interleaved`: fixed-size stripes are dealt round-robin, so lanes drain evenly
+//!   across the whole pad. A reservation must fit in one stripe, so the stripe has to hold
+//!   the largest block any member will draw (padded payload plus MAC material).
+//!
+//! # Rebalancing
+//! Once the group agrees (via `GapOperation::RebalanceLanes`) on how much of every lane
+//! has been consumed, the unused remainders are pooled in pad order and dealt out again
+//! with the same layout under a new epoch. A rebalance may also add lanes for members
+//! that joined since the last split.
+//!
+//! # Persistence
+//! `to_bytes` / `from_bytes` carry the lane segments and per-lane usage across restarts,
+//! so a reloaded pad resumes every lane where it stopped instead of re-dealing used bytes.
+//!
+//! # Security
+//! - **Disjointness**: Lanes never overlap, before or after rebalancing.
+//! - **Usage Enforcement**: Each lane has its own monotonic `UsageTracker`; a rebalance that
+//!   would hand back bytes a lane already used is rejected.
+//! - **Contiguity**: Reservations never straddle a segment; the tail of a segment that is too
+//!   short is skipped (and counted as used).
+//!
+//! # Whitepaper Compliance
+//! - Section 3: Usage Tracking.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use alloc::vec;
+use super::{PadError, usage_stats::UsageTracker};
+
+/// How the pad is divided between lanes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaneLayout {
+    /// Lane `i` owns the `i`-th of `n` (near-)equal contiguous regions.
+    Static,
+    /// Stripes of `stripe` bytes; stripe `j` belongs to lane `j % n`.
+    Interleaved { stripe: u64 },
+}
+
+/// A physical pad region `[start, start + len)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    start: u64,
+    len: u64,
+}
+
+/// One member's share of the pad.
+#[derive(Debug, Clone)]
+struct Lane {
+    /// Physical regions in increasing pad order.
+    segments: Vec<Segment>,
+    /// Position within the concatenation of `segments`.
+    usage: UsageTracker,
+}
+
+/// Allocates pad offsets from independent per-member lanes.
+#[derive(Debug, Clone)]
+pub struct LaneAllocator {
+    layout: LaneLayout,
+    epoch: u64,
+    lanes: Vec<Lane>,
+}
+
+impl LaneAllocator {
+    /// Divides `[base, base + len)` of the pad among `lanes` members.
+    ///
+    /// `max_block` is the largest block any member will reserve (padded length plus MAC
+    /// material, as drawn by `MasterPad::get_padded_block`).
+    ///
+    /// # Errors
+    /// * `PadError::OutOfBounds` if `lanes` is zero, the stripe is zero or smaller than
+    ///   `max_block`, or the range overflows.
+    pub fn new(base: u64, len: u64, lanes: usize, layout: LaneLayout, max_block: u64) -> Result<Self, PadError> {
+        if lanes == 0 {
+            return Err(PadError::OutOfBounds);
+        }
+        if let LaneLayout::Interleaved { stripe } = layout {
+            if stripe == 0 || stripe < max_block {
+                return Err(PadError::OutOfBounds);
+            }
+        }
+        base.checked_add(len).ok_or(PadError::OutOfBounds)?;
+        let dealt = deal(&[Segment { start: base, len }], lanes, layout);
+        Ok(Self {
+            layout,
+            epoch: 0,
+            lanes: dealt.into_iter().map(Lane::new).collect(),
+        })
+    }
+
+    /// Number of lanes.
+    pub fn lane_count(&self) -> usize {
+        self.lanes.len()
+    }
+
+    /// Current rebalance epoch (0 for the initial split).
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The layout used for the initial split and every rebalance.
+    pub fn layout(&self) -> LaneLayout {
+        self.layout
+    }
+
+    /// Usage of a single lane (bytes consumed, including skipped segment tails).
+    pub fn usage(&self, lane: usize) -> Option<&UsageTracker> {
+        self.lanes.get(lane).map(|l| &l.usage)
+    }
+
+    /// Bytes still unused across all lanes.
+    pub fn available(&self) -> u64 {
+        self.lanes.iter().map(|l| l.usage.available()).sum()
+    }
+
+    /// Physical pad ranges `(start, end)` consumed so far in any lane.
+    pub(crate) fn used_ranges(&self) -> Vec<(u64, u64)> {
+        let mut used = Vec::new();
+        for lane in &self.lanes {
+            Cursor::new(&lane.segments).take(lane.usage.used_bytes, &mut used);
+        }
+        used.into_iter().map(|s| (s.start, s.start + s.len)).collect()
+    }
+
+    /// Reserves `len` contiguous pad bytes from `lane` and returns their pad offset.
+    ///
+    /// # Errors
+    /// * `PadError::OutOfBounds` if the lane does not exist.
+    /// * `PadError::Exhausted` if no remaining segment of the lane can hold `len` bytes.
+    pub fn reserve(&mut self, lane: usize, len: u64) -> Result<u64, PadError> {
+        let lane = self.lanes.get_mut(lane).ok_or(PadError::OutOfBounds)?;
+        lane.reserve(len)
+    }
+
+    /// Re-deals every lane's unused remainder under epoch `epoch`.
+    ///
+    /// `consumed[i]` is the agreed number of bytes lane `i` has used; it must cover what
+    /// this allocator has already handed out for that lane. Entries past the current lane
+    /// count add new (so far empty) lanes and must be zero.
+    ///
+    /// # Errors
+    /// * `PadError::IntegrityFailure` on a stale epoch, or if an agreed value is below the
+    ///   local usage or above the lane capacity.
+    /// * `PadError::OutOfBounds` if `consumed` has fewer entries than there are lanes.
+    pub fn rebalance(&mut self, epoch: u64, consumed: &[u64]) -> Result<(), PadError> {
+        if epoch != self.epoch.wrapping_add(1) {
+            return Err(PadError::IntegrityFailure);
+        }
+        if consumed.len() < self.lanes.len() {
+            return Err(PadError::OutOfBounds);
+        }
+        for (lane, &c) in self.lanes.iter().zip(consumed.iter()) {
+            if c < lane.usage.used_bytes || c > lane.usage.total_capacity {
+                return Err(PadError::IntegrityFailure);
+            }
+        }
+        if consumed[self.lanes.len()..].iter().any(|&c| c != 0) {
+            return Err(PadError::IntegrityFailure);
+        }
+
+        let mut free: Vec<Segment> = Vec::new();
+        for (lane, &c) in self.lanes.iter().zip(consumed.iter()) {
+            let mut cursor = Cursor::new(&lane.segments);
+            cursor.skip(c);
+            cursor.take(lane.usage.total_capacity, &mut free);
+        }
+        free.sort_by_key(|s| s.start);
+        let mut merged: Vec<Segment> = Vec::with_capacity(free.len());
+        for s in free {
+            push_segment(&mut merged, s);
+        }
+
+        let dealt = deal(&merged, consumed.len(), self.layout);
+        self.lanes = dealt.into_iter().map(Lane::new).collect();
+        self.epoch = epoch;
+        Ok(())
+    }
+
+    /// Serializes the layout, epoch, segments and per-lane usage.
+    ///
+    /// Format (LE): `[layout u8][stripe u64][epoch u64][lanes u32]` followed per lane by
+    /// `[used u64][segments u32]([start u64][len u64])*`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let (tag, stripe) = match self.layout {
+            LaneLayout::Static => (0u8, 0u64),
+            LaneLayout::Interleaved { stripe } => (1u8, stripe),
+        };
+        out.push(tag);
+        out.extend_from_slice(&stripe.to_le_bytes());
+        out.extend_from_slice(&self.epoch.to_le_bytes());
+        out.extend_from_slice(&(self.lanes.len() as u32).to_le_bytes());
+        for lane in &self.lanes {
+            out.extend_from_slice(&lane.usage.used_bytes.to_le_bytes());
+            out.extend_from_slice(&(lane.segments.len() as u32).to_le_bytes());
+            for seg in &lane.segments {
+                out.extend_from_slice(&seg.start.to_le_bytes());
+                out.extend_from_slice(&seg.len.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// Restores an allocator written by `to_bytes`.
+    ///
+    /// Every segment must lie within `[base, end)` and no two may overlap.
+    ///
+    /// # Errors
+    /// * `PadError::IntegrityFailure` if the record is truncated, has trailing bytes, or
+    ///   describes segments or usage that do not fit.
+    pub fn from_bytes(bytes: &[u8], base: u64, end: u64) -> Result<Self, PadError> {
+        let mut r = Reader { bytes };
+        let layout = match (r.u8()?, r.u64()?) {
+            (0, 0) => LaneLayout::Static,
+            (1, stripe) if stripe > 0 => LaneLayout::Interleaved { stripe },
+            _ => return Err(PadError::IntegrityFailure),
+        };
+        let epoch = r.u64()?;
+        let count = r.u32()? as usize;
+        if count == 0 {
+            return Err(PadError::IntegrityFailure);
+        }
+        let mut lanes = Vec::new();
+        let mut all: Vec<Segment> = Vec::new();
+        for _ in 0..count {
+            let used = r.u64()?;
+            let segs = r.u32()? as usize;
+            // Each segment needs 16 bytes; reject counts the record cannot hold.
+            if segs > r.bytes.len() / 16 {
+                return Err(PadError::IntegrityFailure);
+            }
+            let mut segments = Vec::with_capacity(segs);
+            for _ in 0..segs {
+                let seg = Segment { start: r.u64()?, len: r.u64()? };
+                let seg_end = seg.start.checked_add(seg.len).ok_or(PadError::IntegrityFailure)?;
+                if seg.start < base || seg_end > end {
+                    return Err(PadError::IntegrityFailure);
+                }
+                segments.push(seg);
+                all.push(seg);
+            }
+            let mut lane = Lane::new(segments);
+            if used > lane.usage.total_capacity {
+                return Err(PadError::IntegrityFailure);
+            }
+            lane.usage.used_bytes = used;
+            lanes.push(lane);
+        }
+        if !r.bytes.is_empty() {
+            return Err(PadError::IntegrityFailure);
+        }
+        all.sort_by_key(|s| s.start);
+        if all.windows(2).any(|w| w[0].start + w[0].len > w[1].start) {
+            return Err(PadError::IntegrityFailure);
+        }
+        Ok(Self { layout, epoch, lanes })
+    }
+}
+
+/// Little-endian reader over a lane record.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], PadError> {
+        if self.bytes.len() < N {
+            return Err(PadError::IntegrityFailure);
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        let mut out = [0u8; N];
+        out.copy_from_slice(head);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, PadError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, PadError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, PadError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+impl Lane {
+    fn new(segments: Vec<Segment>) -> Self {
+        let capacity = segments.iter().map(|s| s.len).sum();
+        Self { segments, usage: UsageTracker::new(capacity) }
+    }
+
+    fn reserve(&mut self, len: u64) -> Result<u64, PadError> {
+        // Find the first segment at or after the current position with room for `len`.
+        let pos = self.usage.used_bytes;
+        let mut logical = 0u64;
+        for seg in &self.segments {
+            let seg_end = logical + seg.len;
+            if seg_end > pos || (len == 0 && seg_end == pos) {
+                let from = core::cmp::max(pos, logical);
+                if seg_end - from >= len {
+                    // Skip the unusable tail of earlier segments, then take the block.
+                    self.usage.consume(from - pos)?;
+                    self.usage.consume(len)?;
+                    return Ok(seg.start + (from - logical));
+                }
+            }
+            logical = seg_end;
+        }
+        Err(PadError::Exhausted)
+    }
+}
+
+/// Walks a segment list in logical order.
+struct Cursor<'a> {
+    segments: &'a [Segment],
+    idx: usize,
+    off: u64,
+    logical: u64,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(segments: &'a [Segment]) -> Self {
+        Self { segments, idx: 0, off: 0, logical: 0 }
+    }
+
+    /// Advances to logical position `to` without collecting.
+    fn skip(&mut self, to: u64) {
+        let mut sink = Vec::new();
+        self.take(to, &mut sink);
+    }
+
+    /// Appends the physical pieces of `[logical, to)` to `out`.
+    fn take(&mut self, to: u64, out: &mut Vec<Segment>) {
+        while self.logical < to && self.idx < self.segments.len() {
+            let seg = self.segments[self.idx];
+            let n = core::cmp::min(seg.len - self.off, to - self.logical);
+            if n > 0 {
+                push_segment(out, Segment { start: seg.start + self.off, len: n });
+            }
+            self.off += n;
+            self.logical += n;
+            if self.off == seg.len {
+                self.idx += 1;
+                self.off = 0;
+            }
+        }
+    }
+}
+
+/// Appends a segment, merging it into the previous one when physically adjacent.
+fn push_segment(out: &mut Vec<Segment>, seg: Segment) {
+    if let Some(last) = out.last_mut() {
+        if last.start + last.len == seg.start {
+            last.len += seg.len;
+            return;
+        }
+    }
+    out.push(seg);
+}
+
+/// Deals the concatenation of `free` among `lanes` according to `layout`.
+fn deal(free: &[Segment], lanes: usize, layout: LaneLayout) -> Vec<Vec<Segment>> {
+    let total: u64 = free.iter().map(|s| s.len).sum();
+    let mut out = vec![Vec::new(); lanes];
+    let mut cursor = Cursor::new(free);
+    match layout {
+        LaneLayout::Static => {
+            for (i, lane) in out.iter_mut().enumerate() {
+                let end = (total as u128 * (i as u128 + 1) / lanes as u128) as u64;
+                cursor.take(end, lane);
+            }
+        }
+        LaneLayout::Interleaved { stripe } => {
+            let mut end = 0u64;
+            let mut j = 0usize;
+            while end < total {
+                end = core::cmp::min(end.saturating_add(stripe), total);
+                cursor.take(end, &mut out[j % lanes]);
+                j += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn covered(alloc: &LaneAllocator) -> Vec<(u64, u64)> {
+        let mut all: Vec<(u64, u64)> = alloc.lanes.iter()
+            .flat_map(|l| l.segments.iter().map(|s| (s.start, s.start + s.len)))
+            .collect();
+        all.sort();
+        all
+    }
+
+    #[test]
+    fn test_lanes_disjoint() {
+        for layout in [LaneLayout::Static, LaneLayout::Interleaved { stripe: 64 }] {
+            let alloc = LaneAllocator::new(100, 1000, 3, layout, 64).unwrap();
+            let ranges = covered(&alloc);
+            // Sorted ranges tile [100, 1100) exactly.
+            let mut next = 100;
+            for (s, e) in ranges {
+                assert_eq!(s, next);
+                next = e;
+            }
+            assert_eq!(next, 1100);
+        }
+    }
+
+    #[test]
+    fn test_concurrent_reservations_never_collide() {
+        let mut alloc = LaneAllocator::new(0, 4096, 4, LaneLayout::Interleaved { stripe: 256 }, 256).unwrap();
+        let mut taken: Vec<(u64, u64)> = Vec::new();
+        for round in 0..10 {
+            for lane in 0..4 {
+                let len = 40 + round * 3;
+                let off = alloc.reserve(lane, len).unwrap();
+                for &(s, e) in &taken {
+                    assert!(off + len <= s || off >= e, "overlap");
+                }
+                taken.push((off, off + len));
+            }
+        }
+        // Stripes that cannot hold the largest block are refused up front.
+        let striped = LaneLayout::Interleaved { stripe: 256 };
+        assert_eq!(LaneAllocator::new(0, 4096, 4, striped, 257).map(|_| ()), Err(PadError::OutOfBounds));
+        assert_eq!(alloc.reserve(9, 1), Err(PadError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_rebalance_redistributes_unused() {
+        let mut alloc = LaneAllocator::new(0, 1200, 3, LaneLayout::Static, 1200).unwrap();
+        // Lane 0 is busy, the others idle.
+        let a = alloc.reserve(0, 350).unwrap();
+        assert_eq!(alloc.reserve(0, 100), Err(PadError::Exhausted));
+
+        // Stale epoch and under-reported usage are rejected.
+        assert_eq!(alloc.rebalance(2, &[350, 0, 0]), Err(PadError::IntegrityFailure));
+        assert_eq!(alloc.rebalance(1, &[100, 0, 0]), Err(PadError::IntegrityFailure));
+
+        alloc.rebalance(1, &[350, 20, 0]).unwrap();
+        assert_eq!(alloc.epoch(), 1);
+        let free = 1200 - 350 - 20;
+        for lane in 0..3u64 {
+            let expected = free * (lane + 1) / 3 - free * lane / 3;
+            assert_eq!(alloc.usage(lane as usize).unwrap().total_capacity, expected);
+        }
+        // Nothing handed out before the rebalance is handed out again.
+        let b = alloc.reserve(0, 200).unwrap();
+        assert!(b >= a + 350);
+        for (s, e) in covered(&alloc) {
+            assert!(e <= a || s >= a + 350);
+            assert!(e <= 400 || s >= 420);
+        }
+
+        // A rebalance can add a lane for a new member, but not with usage of its own.
+        let used = alloc.usage(0).unwrap().used_bytes;
+        assert_eq!(alloc.rebalance(2, &[used, 0, 0, 5]), Err(PadError::IntegrityFailure));
+        alloc.rebalance(2, &[used, 0, 0, 0]).unwrap();
+        assert_eq!(alloc.lane_count(), 4);
+        assert!(alloc.reserve(3, 10).is_ok());
+    }
+
+    #[test]
+    fn test_lanes_roundtrip_bytes() {
+        let mut alloc = LaneAllocator::new(16, 1000, 3, LaneLayout::Interleaved { stripe: 100 }, 100).unwrap();
+        alloc.reserve(0, 60).unwrap();
+        alloc.reserve(1, 90).unwrap();
+        alloc.reserve(1, 20).unwrap();
+        let bytes = alloc.to_bytes();
+
+        let mut restored = LaneAllocator::from_bytes(&bytes, 16, 1016).unwrap();
+        assert_eq!(restored.to_bytes(), bytes);
+        assert_eq!(restored.used_ranges(), alloc.used_ranges());
+        assert_eq!(restored.available(), alloc.available());
+        assert_eq!(restored.reserve(1, 10), alloc.reserve(1, 10));
+
+        // Truncated, trailing or out-of-range records are rejected.
+        assert!(LaneAllocator::from_bytes(&bytes[..bytes.len() - 1], 16, 1016).is_err());
+        let mut long = bytes.clone();
+        long.push(0);
+        assert!(LaneAllocator::from_bytes(&long, 16, 1016).is_err());
+        assert!(LaneAllocator::from_bytes(&bytes, 20, 1016).is_err());
+        assert!(LaneAllocator::from_bytes(&bytes, 16, 1000).is_err());
+    }
+}
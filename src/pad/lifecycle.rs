@@ -7,13 +7,22 @@
 //! - **Zeroization**: All key material is stored in `Zeroizing` containers.
 //! - **Usage Enforcement**: All access is mediated by `UsageTracker`.
 //! - **Rotation**: Securely burns old material before replacement.
+//! - **Burn After Use**: Slices handed out by `get_slice` are burned when released; the
+//!   persisted used-bytes counter acts as a low-water mark so burned regions are never reloaded.
+//! - **Lanes**: Per-sender lane usage is persisted with the pad and restored on load, so lane
+//!   offsets are never handed out twice across restarts.
 
 extern crate alloc;
 use alloc::vec::Vec;
 use alloc::vec;
 use zeroize::Zeroizing;
+use crate::core::padding::PaddingPolicy;
 use crate::entropy::EntropySource;
-use super::{PadError, usage_stats::UsageTracker, burn::burn_slice};
+use super::{PadError, usage_stats::UsageTracker, burn::{burn_slice, BurnGuard}, lanes::{LaneAllocator, LaneLayout}};
+
+/// Set in the stored Used Bytes field when a lane record trails the pad data.
+#[cfg(feature = "std")]
+const LANES_FLAG: u64 = 1 << 63;
 
 /// Represents the Master Pad containing the OTP key material.
 pub struct MasterPad {
@@ -23,6 +32,8 @@ pub struct MasterPad {
     usage: UsageTracker,
     /// Unique identifier for this pad instance (for auditing/integrity).
     id: [u8; 16],
+    /// Per-sender lanes over the unused region, once enabled.
+    lanes: Option<LaneAllocator>,
 }
 
 impl MasterPad {
@@ -46,16 +57,38 @@ impl MasterPad {
             data,
             usage: UsageTracker::new(size as u64),
             id,
+            lanes: None,
         })
     }
 
+    /// Wraps pad material produced elsewhere, such as the jointly generated
+    /// `XOR R_i` from `mpc::joint::JointRandom::output`.
+    ///
+    /// # Arguments
+    /// * `data` - The key material; its length is the pad size.
+    /// * `id` - The pad identifier agreed by the group.
+    pub fn from_material(data: Zeroizing<Vec<u8>>, id: [u8; 16]) -> Self {
+        let size = data.len() as u64;
+        Self {
+            data,
+            usage: UsageTracker::new(size),
+            id,
+            lanes: None,
+        }
+    }
+
     /// Reserves and returns a slice of the pad for encryption.
     ///
-    /// This updates the internal usage counter.
+    /// This updates the internal usage counter. The slice is burned in the pad when the
+    /// returned guard is dropped.
     ///
     /// # Errors
     /// * `PadError::Exhausted` if there are not enough bytes remaining.
-    pub fn get_slice(&mut self, len: usize) -> Result<&[u8], PadError> {
+    /// * `PadError::NotReady` if the pad has been split into lanes (use `get_lane_slice`).
+    pub fn get_slice(&mut self, len: usize) -> Result<BurnGuard<'_>, PadError> {
+        if self.lanes.is_some() {
+            return Err(PadError::NotReady);
+        }
         let offset = self.usage.consume(len as u64)?;
         let start = offset as usize;
         let end = start + len;
@@ -65,9 +98,107 @@ impl MasterPad {
              return Err(PadError::OutOfBounds);
         }
 
-        Ok(&self.data[start..end])
+        Ok(BurnGuard::new(&mut self.data[start..end]))
     }
     
+    /// Splits the unused part of the pad into `members` disjoint sender lanes.
+    ///
+    /// Every member must call this with the same parameters on the same pad state so
+    /// that all derive the same layout. Afterwards only `get_lane_slice` hands out bytes.
+    /// `members` is `GroupState::lane_count`; lane `i` belongs to the member whose
+    /// `GroupState::lane_index` is `i`. `max_block` is the largest block `get_padded_block`
+    /// will be asked for (padded length plus `mac_cost`); an interleaved stripe must hold it.
+    ///
+    /// # Errors
+    /// * `PadError::NotReady` if lanes are already enabled (grow them via `rebalance_lanes`).
+    /// * `PadError::OutOfBounds` if an interleaved stripe is smaller than `max_block`.
+    pub fn enable_lanes(&mut self, members: usize, layout: LaneLayout, max_block: usize) -> Result<(), PadError> {
+        if self.lanes.is_some() {
+            return Err(PadError::NotReady);
+        }
+        let base = self.usage.used_bytes;
+        let alloc = LaneAllocator::new(base, self.usage.available(), members, layout, max_block as u64)?;
+        self.lanes = Some(alloc);
+        Ok(())
+    }
+
+    /// Reserves `len` bytes from sender lane `lane`.
+    ///
+    /// Returns the pad offset (to be sent alongside the ciphertext) and the slice, which is
+    /// burned when the guard is dropped.
+    ///
+    /// # Errors
+    /// * `PadError::NotReady` if lanes are not enabled.
+    /// * `PadError::Exhausted` if the lane cannot serve `len` contiguous bytes.
+    pub fn get_lane_slice(&mut self, lane: usize, len: usize) -> Result<(u64, BurnGuard<'_>), PadError> {
+        let lanes = self.lanes.as_mut().ok_or(PadError::NotReady)?;
+        let offset = lanes.reserve(lane, len as u64)?;
+        let start = offset as usize;
+        let end = start.checked_add(len).ok_or(PadError::OutOfBounds)?;
+
+        // Defense-in-depth bounds check
+        if end > self.data.len() {
+            return Err(PadError::OutOfBounds);
+        }
+
+        Ok((offset, BurnGuard::new(&mut self.data[start..end])))
+    }
+
+    /// Reserves the block for a `payload_len`-byte message padded under `policy`: keystream
+    /// for the padded length followed by `mac_cost` bytes (see `MacMode::pad_cost`).
+    ///
+    /// Draws from sender lane `lane` once lanes are enabled, otherwise from the shared pad
+    /// (`lane` must then be `None`). The padding overhead is recorded in the usage statistics.
+    /// Returns the pad offset and the block, which is burned when the guard is dropped.
+    ///
+    /// # Errors
+    /// * `PadError::OutOfBounds` if the policy cannot pad `payload_len` bytes.
+    /// * `PadError::NotReady` if `lane` does not match whether lanes are enabled.
+    /// * `PadError::Exhausted` if not enough bytes remain.
+    pub fn get_padded_block(
+        &mut self,
+        lane: Option<usize>,
+        payload_len: usize,
+        policy: &PaddingPolicy,
+        mac_cost: usize,
+    ) -> Result<(u64, BurnGuard<'_>), PadError> {
+        let padded = policy.padded_len(payload_len).map_err(|_| PadError::OutOfBounds)?;
+        let need = padded.checked_add(mac_cost).ok_or(PadError::OutOfBounds)?;
+        let offset = match (lane, self.lanes.as_mut()) {
+            (None, None) => self.usage.consume_padded((payload_len + mac_cost) as u64, need as u64)?,
+            (Some(lane), Some(lanes)) => {
+                let offset = lanes.reserve(lane, need as u64)?;
+                self.usage.padding_bytes = self.usage.padding_bytes.saturating_add((padded - payload_len) as u64);
+                offset
+            }
+            _ => return Err(PadError::NotReady),
+        };
+        let start = offset as usize;
+        let end = start.checked_add(need).ok_or(PadError::OutOfBounds)?;
+
+        // Defense-in-depth bounds check
+        if end > self.data.len() {
+            return Err(PadError::OutOfBounds);
+        }
+
+        Ok((offset, BurnGuard::new(&mut self.data[start..end])))
+    }
+
+    /// Returns the pad bytes spent on length-hiding padding so far.
+    pub fn padding_overhead(&self) -> u64 {
+        self.usage.padding_bytes
+    }
+
+    /// Applies an agreed lane rebalance (see `GapOperation::RebalanceLanes`).
+    pub fn rebalance_lanes(&mut self, epoch: u64, consumed: &[u64]) -> Result<(), PadError> {
+        self.lanes.as_mut().ok_or(PadError::NotReady)?.rebalance(epoch, consumed)
+    }
+
+    /// Returns the lane allocator, if lanes are enabled.
+    pub fn lanes(&self) -> Option<&LaneAllocator> {
+        self.lanes.as_ref()
+    }
+
     /// Rotates the pad by overwriting it with new random data.
     ///
     /// This securely burns the old data and resets the usage counter.
@@ -83,6 +214,7 @@ impl MasterPad {
         
         // Reset usage tracker
         self.usage.reset(self.data.len() as u64);
+        self.lanes = None;
         
         Ok(())
     }
@@ -92,9 +224,12 @@ impl MasterPad {
         &self.id
     }
     
-    /// Returns the remaining capacity in bytes.
+    /// Returns the remaining capacity in bytes (across all lanes once they are enabled).
     pub fn remaining(&self) -> u64 {
-        self.usage.available()
+        match &self.lanes {
+            Some(lanes) => lanes.available(),
+            None => self.usage.available(),
+        }
     }
     
     /// Returns the total capacity in bytes.
@@ -107,14 +242,19 @@ impl MasterPad {
 impl MasterPad {
     /// Loads a pad from a file.
     ///
-    /// The file format is: `[ID (16 bytes)] [Used Bytes (8 bytes, LE)] [Data (...)]`
+    /// The file format is: `[ID (16 bytes)] [Used Bytes (8 bytes, LE)] [Data (...)]`,
+    /// followed by `[Lane Record] [Record Length (4 bytes, LE)]` when the top bit of Used
+    /// Bytes is set (see `LaneAllocator::to_bytes`).
+    ///
+    /// Used Bytes is the low-water mark: the consumed region below it is never read back
+    /// and stays zeroed in memory, as do the ranges the restored lanes already used.
     ///
     /// # Security Warning
     /// This method assumes the file is trusted or stored on an encrypted filesystem.
     /// For untrusted storage, use `share_encrypt` to encrypt shares instead.
     pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, PadError> {
         use std::fs::File;
-        use std::io::Read;
+        use std::io::{Read, Seek, SeekFrom};
         
         let mut file = File::open(path).map_err(|_| PadError::StorageError)?;
         let metadata = file.metadata().map_err(|_| PadError::StorageError)?;
@@ -132,11 +272,25 @@ impl MasterPad {
         
         let mut used_bytes_buf = [0u8; 8];
         file.read_exact(&mut used_bytes_buf).map_err(|_| PadError::StorageError)?;
-        let used_bytes = u64::from_le_bytes(used_bytes_buf);
-        
-        let mut data = Zeroizing::new(vec![0u8; data_len]);
-        file.read_exact(&mut data).map_err(|_| PadError::StorageError)?;
-        
+        let stored = u64::from_le_bytes(used_bytes_buf);
+        let used_bytes = stored & !LANES_FLAG;
+
+        // Lane record and its length trail the data
+        let mut record = Vec::new();
+        let data_len = if stored & LANES_FLAG != 0 {
+            let mut record_len_buf = [0u8; 4];
+            file.seek(SeekFrom::End(-4)).map_err(|_| PadError::IntegrityFailure)?;
+            file.read_exact(&mut record_len_buf).map_err(|_| PadError::StorageError)?;
+            let record_len = u32::from_le_bytes(record_len_buf) as usize;
+            let data_len = data_len.checked_sub(4 + record_len).ok_or(PadError::IntegrityFailure)?;
+            record.resize(record_len, 0);
+            file.seek(SeekFrom::Start(24 + data_len as u64)).map_err(|_| PadError::StorageError)?;
+            file.read_exact(&mut record).map_err(|_| PadError::StorageError)?;
+            data_len
+        } else {
+            data_len
+        };
+
         let mut usage = UsageTracker::new(data_len as u64);
         usage.used_bytes = used_bytes;
         
@@ -145,16 +299,56 @@ impl MasterPad {
             return Err(PadError::IntegrityFailure);
         }
 
+        let lanes = if record.is_empty() {
+            None
+        } else {
+            Some(LaneAllocator::from_bytes(&record, used_bytes, data_len as u64)?)
+        };
+
+        // Skip the burned region below the low-water mark
+        let mut data = Zeroizing::new(vec![0u8; data_len]);
+        let used = used_bytes as usize;
+        file.seek(SeekFrom::Start(24 + used_bytes)).map_err(|_| PadError::StorageError)?;
+        file.read_exact(&mut data[used..]).map_err(|_| PadError::StorageError)?;
+        if let Some(lanes) = &lanes {
+            for (start, end) in lanes.used_ranges() {
+                burn_slice(&mut data[start as usize..end as usize]);
+            }
+        }
+
         Ok(Self {
             data,
             usage,
             id,
+            lanes,
         })
     }
 
+    /// Stored Used Bytes field: the low-water mark, flagged when a lane record follows.
+    fn stored_mark(&self) -> u64 {
+        match self.lanes {
+            Some(_) => self.usage.used_bytes | LANES_FLAG,
+            None => self.usage.used_bytes,
+        }
+    }
+
+    /// Writes the lane record and its length after the data, if lanes are enabled.
+    fn write_lane_record(&self, file: &mut std::fs::File) -> Result<(), PadError> {
+        use std::io::Write;
+
+        if let Some(lanes) = &self.lanes {
+            let record = lanes.to_bytes();
+            let record_len = u32::try_from(record.len()).map_err(|_| PadError::StorageError)?;
+            file.write_all(&record).map_err(|_| PadError::StorageError)?;
+            file.write_all(&record_len.to_le_bytes()).map_err(|_| PadError::StorageError)?;
+        }
+        Ok(())
+    }
+
     /// Saves the pad to a file.
     ///
-    /// The file format is: `[ID (16 bytes)] [Used Bytes (8 bytes, LE)] [Data (...)]`
+    /// The file format is: `[ID (16 bytes)] [Used Bytes (8 bytes, LE)] [Data (...)]`,
+    /// plus the lane record when lanes are enabled (see `load_from_file`).
     pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), PadError> {
         use std::fs::File;
         use std::io::Write;
@@ -162,13 +356,67 @@ impl MasterPad {
         let mut file = File::create(path).map_err(|_| PadError::StorageError)?;
         
         file.write_all(&self.id).map_err(|_| PadError::StorageError)?;
-        file.write_all(&self.usage.used_bytes.to_le_bytes()).map_err(|_| PadError::StorageError)?;
+        file.write_all(&self.stored_mark().to_le_bytes()).map_err(|_| PadError::StorageError)?;
         file.write_all(&self.data).map_err(|_| PadError::StorageError)?;
+        self.write_lane_record(&mut file)?;
         
         file.sync_all().map_err(|_| PadError::StorageError)?;
         
         Ok(())
     }
+
+    /// Persists the low-water mark to an existing pad file.
+    ///
+    /// Burns the file's data region between its stored mark and the current usage, then
+    /// updates the stored Used Bytes. The mark never moves backwards. With lanes enabled,
+    /// the ranges used in every lane are burned as well and the lane record is rewritten.
+    pub fn sync_low_water_mark<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), PadError> {
+        use std::fs::OpenOptions;
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let mut file = OpenOptions::new().read(true).write(true).open(path).map_err(|_| PadError::StorageError)?;
+
+        let mut id = [0u8; 16];
+        file.read_exact(&mut id).map_err(|_| PadError::StorageError)?;
+        if id != self.id {
+            return Err(PadError::IntegrityFailure);
+        }
+        let mut used_bytes_buf = [0u8; 8];
+        file.read_exact(&mut used_bytes_buf).map_err(|_| PadError::StorageError)?;
+        let stored = u64::from_le_bytes(used_bytes_buf) & !LANES_FLAG;
+
+        let zeros = vec![0u8; 64 * 1024];
+        let mut burn = |start: u64, end: u64| -> Result<(), PadError> {
+            file.seek(SeekFrom::Start(24 + start)).map_err(|_| PadError::StorageError)?;
+            let mut remaining = end - start;
+            while remaining > 0 {
+                let n = core::cmp::min(remaining, zeros.len() as u64) as usize;
+                file.write_all(&zeros[..n]).map_err(|_| PadError::StorageError)?;
+                remaining -= n as u64;
+            }
+            Ok(())
+        };
+
+        let mark = self.usage.used_bytes;
+        if mark > stored {
+            burn(stored, mark)?;
+        }
+        if let Some(lanes) = &self.lanes {
+            for (start, end) in lanes.used_ranges() {
+                burn(start, end)?;
+            }
+            file.set_len(24 + self.data.len() as u64).map_err(|_| PadError::StorageError)?;
+            file.seek(SeekFrom::End(0)).map_err(|_| PadError::StorageError)?;
+            self.write_lane_record(&mut file)?;
+        }
+        if mark > stored || self.lanes.is_some() {
+            file.seek(SeekFrom::Start(16)).map_err(|_| PadError::StorageError)?;
+            file.write_all(&self.stored_mark().to_le_bytes()).map_err(|_| PadError::StorageError)?;
+            file.sync_all().map_err(|_| PadError::StorageError)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -281,4 +529,108 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_file(path);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_low_water_mark() {
+        let mut entropy = MockEntropy::new();
+        let mut pad = MasterPad::new(64, &mut entropy).unwrap();
+        let path = std::env::temp_dir().join(format!("test_pad_lwm_{}.bin", std::process::id()));
+        pad.save_to_file(&path).unwrap();
+
+        let first = pad.get_slice(16).unwrap().to_vec();
+        assert!(first.iter().any(|&b| b != 0));
+        assert!(pad.data[..16].iter().all(|&b| b == 0));
+        pad.sync_low_water_mark(&path).unwrap();
+
+        let raw = std::fs::read(&path).unwrap();
+        assert_eq!(u64::from_le_bytes(raw[16..24].try_into().unwrap()), 16);
+        assert!(raw[24..40].iter().all(|&b| b == 0));
+
+        let loaded = MasterPad::load_from_file(&path).unwrap();
+        assert_eq!(loaded.remaining(), 48);
+        assert!(loaded.data[..16].iter().all(|&b| b == 0));
+        assert_eq!(&loaded.data[16..], &pad.data[16..]);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_lane_slices() {
+        let mut entropy = MockEntropy::new();
+        let mut pad = MasterPad::new(100, &mut entropy).unwrap();
+        pad.get_slice(10).unwrap();
+        pad.enable_lanes(3, LaneLayout::Static, 48).unwrap();
+        assert_eq!(pad.get_slice(1), Err(PadError::NotReady));
+
+        let (off0, s0) = pad.get_lane_slice(0, 20).map(|(o, s)| (o, s.to_vec())).unwrap();
+        let (off1, s1) = pad.get_lane_slice(1, 20).map(|(o, s)| (o, s.to_vec())).unwrap();
+        assert_eq!(off0, 10);
+        assert_eq!(off1, 40);
+        assert_ne!(s0, s1);
+        assert!(pad.data[10..30].iter().all(|&b| b == 0));
+        assert_eq!(pad.get_lane_slice(0, 20).map(|(o, _)| o), Err(PadError::Exhausted));
+
+        pad.rebalance_lanes(1, &[20, 20, 0]).unwrap();
+        assert_eq!(pad.lanes().unwrap().epoch(), 1);
+        // Freed tails [30, 40) and [60, 100) are re-dealt; lane 0 now starts at 30.
+        assert_eq!(pad.get_lane_slice(0, 10).map(|(o, _)| o), Ok(30));
+        assert_eq!(pad.enable_lanes(3, LaneLayout::Static, 48), Err(PadError::NotReady));
+    }
+
+    #[test]
+    fn test_padded_blocks() {
+        let mut entropy = MockEntropy::new();
+        let mut pad = MasterPad::new(200, &mut entropy).unwrap();
+        let policy = PaddingPolicy::Fixed { block: 32 };
+
+        let (off, block) = pad.get_padded_block(None, 3, &policy, 16).map(|(o, b)| (o, b.len())).unwrap();
+        assert_eq!((off, block), (0, 48));
+        assert_eq!(pad.remaining(), 152);
+        assert_eq!(pad.padding_overhead(), 29);
+        assert_eq!(pad.get_padded_block(Some(0), 3, &policy, 16).map(|(o, _)| o), Err(PadError::NotReady));
+
+        // A 32-byte stripe could never serve a 48-byte block.
+        assert_eq!(pad.enable_lanes(2, LaneLayout::Interleaved { stripe: 32 }, 48), Err(PadError::OutOfBounds));
+        pad.enable_lanes(2, LaneLayout::Static, 48).unwrap();
+        assert_eq!(pad.get_padded_block(None, 3, &policy, 16).map(|(o, _)| o), Err(PadError::NotReady));
+        assert_eq!(pad.get_padded_block(Some(1), 20, &policy, 16).map(|(o, b)| (o, b.len())), Ok((124, 48)));
+        assert_eq!(pad.padding_overhead(), 29 + 12);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lane_usage_persisted() {
+        let mut entropy = MockEntropy::new();
+        let mut pad = MasterPad::new(120, &mut entropy).unwrap();
+        let path = std::env::temp_dir().join(format!("test_pad_lanes_{}.bin", std::process::id()));
+        pad.save_to_file(&path).unwrap();
+
+        pad.enable_lanes(2, LaneLayout::Static, 48).unwrap();
+        let (off, _) = pad.get_lane_slice(1, 25).map(|(o, s)| (o, s.to_vec())).unwrap();
+        assert_eq!(off, 60);
+        assert_eq!(pad.remaining(), 95);
+        pad.sync_low_water_mark(&path).unwrap();
+
+        let raw = std::fs::read(&path).unwrap();
+        assert!(raw[24 + 60..24 + 85].iter().all(|&b| b == 0));
+
+        // Reloading resumes the lane instead of re-dealing its used bytes.
+        let mut loaded = MasterPad::load_from_file(&path).unwrap();
+        assert_eq!(loaded.total_capacity(), 120);
+        assert_eq!(loaded.remaining(), 95);
+        assert_eq!(loaded.get_lane_slice(1, 5).map(|(o, _)| o), Ok(85));
+        assert_eq!(loaded.enable_lanes(2, LaneLayout::Static, 48), Err(PadError::NotReady));
+
+        // Further use is recorded on the next sync, and a full save carries the lanes too.
+        loaded.sync_low_water_mark(&path).unwrap();
+        let mut again = MasterPad::load_from_file(&path).unwrap();
+        assert_eq!(again.get_lane_slice(1, 5).map(|(o, _)| o), Ok(90));
+        again.save_to_file(&path).unwrap();
+        let mut saved = MasterPad::load_from_file(&path).unwrap();
+        assert_eq!(saved.get_lane_slice(1, 5).map(|(o, _)| o), Ok(95));
+        assert_eq!(&saved.data[..60], &pad.data[..60]);
+
+        let _ = std::fs::remove_file(path);
+    }
 }
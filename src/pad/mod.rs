@@ -7,6 +7,7 @@
 //! # Components
 //! - `lifecycle`: Manages initialization, rotation, and access to the pad.
 //! - `usage_stats`: Tracks consumed bytes to strictly enforce "One-Time" usage.
+//! - `lanes`: Splits the pad into disjoint per-sender lanes for concurrent senders.
 //! - `burn`: Implements secure, irreversible deletion of key material.
 //! - `monitor`: Integrates with the Watchdog system for anomaly detection.
 //! - `share_encrypt`: Encrypts/decrypts local pad shares (Scrypt-based).
@@ -24,6 +25,7 @@
 
 pub mod lifecycle;
 pub mod usage_stats;
+pub mod lanes;
 pub mod burn;
 pub mod monitor;
 pub mod share_encrypt;
@@ -20,6 +20,8 @@ pub struct UsageTracker {
     pub total_capacity: u64,
     /// Number of bytes already consumed.
     pub used_bytes: u64,
+    /// Portion of `used_bytes` spent on length-hiding padding.
+    pub padding_bytes: u64,
 }
 
 impl UsageTracker {
@@ -28,6 +30,7 @@ impl UsageTracker {
         Self {
             total_capacity,
             used_bytes: 0,
+            padding_bytes: 0,
         }
     }
 
@@ -60,6 +63,21 @@ impl UsageTracker {
         Ok(start_offset)
     }
 
+    /// Reserves `padded_len` bytes for a `payload_len`-byte message and records the
+    /// difference as padding overhead.
+    ///
+    /// # Errors
+    /// Returns `PadError::OutOfBounds` if `payload_len > padded_len`, `PadError::Exhausted`
+    /// if there are not enough bytes remaining.
+    pub fn consume_padded(&mut self, payload_len: u64, padded_len: u64) -> Result<u64, PadError> {
+        if payload_len > padded_len {
+            return Err(PadError::OutOfBounds);
+        }
+        let start_offset = self.consume(padded_len)?;
+        self.padding_bytes = self.padding_bytes.saturating_add(padded_len - payload_len);
+        Ok(start_offset)
+    }
+
     /// Resets usage (e.g., after pad rotation).
     ///
     /// # Safety
@@ -68,6 +86,7 @@ impl UsageTracker {
     pub fn reset(&mut self, new_capacity: u64) {
         self.total_capacity = new_capacity;
         self.used_bytes = 0;
+        self.padding_bytes = 0;
     }
 }
 
@@ -116,4 +135,15 @@ mod tests {
         // State should remain unchanged
         assert_eq!(tracker.used_bytes, 40);
     }
+
+    #[test]
+    fn test_tracker_padding_accounting() {
+        let mut tracker = UsageTracker::new(8192);
+        assert_eq!(tracker.consume_padded(3, 4096), Ok(0));
+        assert_eq!(tracker.used_bytes, 4096);
+        assert_eq!(tracker.padding_bytes, 4093);
+        assert_eq!(tracker.consume_padded(5000, 4096), Err(PadError::OutOfBounds));
+        assert_eq!(tracker.consume_padded(1, 8192), Err(PadError::Exhausted));
+        assert_eq!(tracker.padding_bytes, 4093);
+    }
 }
@@ -5,12 +5,30 @@
 //!
 //! # Whitepaper Compliance
 //! - Section 6: Bootstrap Orchestration (Async n-t startup, 3-8min).
+//! - Section 3.1: H∞ ≥ 0.8 bits/byte on raw entropy or abort & burn (`check_entropy`, which
+//!   `seed_pad` runs before raw samples are extracted into pad material).
 
 use super::stages::BootstrapStage;
 use crate::protocol::ProtocolError;
 
 #[cfg(feature = "std")]
 use std::time::{Instant, Duration};
+#[cfg(feature = "std")]
+use crate::entropy::sp800_90b::{assess_non_iid, NonIidAssessment, BOOTSTRAP_MIN_ENTROPY};
+#[cfg(feature = "std")]
+use crate::pad::burn::burn_slice;
+#[cfg(feature = "std")]
+use crate::entropy::EntropySource;
+#[cfg(feature = "std")]
+use crate::pad::lifecycle::MasterPad;
+#[cfg(feature = "std")]
+use crate::core::universal_hash::ToeplitzExtractor;
+#[cfg(feature = "std")]
+use zeroize::Zeroizing;
+
+/// Bytes of assessed min-entropy `seed_pad` leaves unextracted (128 bits, for a statistical
+/// distance of at most `2^-64` from uniform by the leftover hash lemma).
+pub const EXTRACT_MARGIN: usize = 16;
 
 /// Orchestrates the bootstrap process.
 pub struct BootstrapOrchestrator {
@@ -98,6 +116,71 @@ impl BootstrapOrchestrator {
         Ok(())
     }
     
+    /// Assesses raw 8-bit entropy samples before they seed key material.
+    ///
+    /// Below `BOOTSTRAP_MIN_ENTROPY` bits per byte (or if the samples cannot be assessed)
+    /// the samples are burned, the orchestrator is reset and the bootstrap aborts.
+    ///
+    /// # Errors
+    /// Returns `ProtocolError::BootstrapFailed` on abort.
+    #[cfg(feature = "std")]
+    pub fn check_entropy(&mut self, samples: &mut [u8]) -> Result<NonIidAssessment, ProtocolError> {
+        match assess_non_iid(samples, 8) {
+            Ok(a) if a.require(BOOTSTRAP_MIN_ENTROPY).is_ok() => Ok(a),
+            _ => {
+                log::warn!("Bootstrap: raw entropy below {} bits/byte, burning samples", BOOTSTRAP_MIN_ENTROPY);
+                burn_slice(samples);
+                self.reset();
+                Err(ProtocolError::BootstrapFailed)
+            }
+        }
+    }
+
+    /// Draws `len` raw bytes from `source` during `KeyDerivation`, gates them with
+    /// `check_entropy` and extracts the master pad from them.
+    ///
+    /// The samples carry only the assessed `per_byte` bits of min-entropy per byte, so they
+    /// are compressed with a Toeplitz hash under `key` to `len * per_byte / 8` bytes, less
+    /// `EXTRACT_MARGIN`. The pad is therefore shorter than `len`; size `len` for the pad
+    /// needed. `key` must be uniform and independent of `source` (it may be public, e.g.
+    /// agreed during `Discovery`) and at least `2 * len` bytes.
+    ///
+    /// `source` must be the raw (unconditioned) source so the assessment is meaningful, and
+    /// `len` at least `non_iid::MIN_SAMPLES`. For a jointly generated pad, run
+    /// `check_entropy` on each member's own contribution before `JointRandom::deal` instead.
+    ///
+    /// # Errors
+    /// * `ProtocolError::InvalidState` outside `BootstrapStage::KeyDerivation`.
+    /// * `ProtocolError::CryptoError` if the source fails or `key` is too short.
+    /// * `ProtocolError::BootstrapFailed` if the samples fail the check (see `check_entropy`)
+    ///   or carry no more than `EXTRACT_MARGIN` bytes of min-entropy.
+    #[cfg(feature = "std")]
+    pub fn seed_pad<R: EntropySource + ?Sized>(
+        &mut self,
+        source: &mut R,
+        len: usize,
+        key: &[u8],
+        id: [u8; 16]
+    ) -> Result<MasterPad, ProtocolError> {
+        if self.current_stage != BootstrapStage::KeyDerivation {
+            return Err(ProtocolError::InvalidState);
+        }
+        let mut material = Zeroizing::new(vec![0u8; len]);
+        source.fill(&mut material).map_err(|_| ProtocolError::CryptoError)?;
+        let assessment = self.check_entropy(&mut material)?;
+
+        let out_len = ((len as f64 * assessment.per_byte() / 8.0) as usize).saturating_sub(EXTRACT_MARGIN);
+        if out_len == 0 {
+            burn_slice(&mut material);
+            self.reset();
+            return Err(ProtocolError::BootstrapFailed);
+        }
+        let mut extractor = ToeplitzExtractor::new(key, out_len);
+        extractor.update(&material);
+        let pad = Zeroizing::new(extractor.finalize().map_err(|_| ProtocolError::CryptoError)?);
+        Ok(MasterPad::from_material(pad, id))
+    }
+
     /// Resets the orchestrator (e.g., on failure or retry).
     pub fn reset(&mut self) {
         self.current_stage = BootstrapStage::Idle;
@@ -149,6 +232,75 @@ mod tests {
         assert_eq!(orch.current_stage(), BootstrapStage::Complete);
     }
     
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_entropy_abort_and_burn() {
+        let mut orch = BootstrapOrchestrator::new();
+        orch.advance().unwrap();
+
+        // A stuck source: every sample equal.
+        let mut samples = vec![0x5Au8; crate::entropy::sp800_90b::non_iid::MIN_SAMPLES];
+        assert_eq!(orch.check_entropy(&mut samples).map(|_| ()), Err(ProtocolError::BootstrapFailed));
+        assert!(samples.iter().all(|&b| b == 0));
+        assert_eq!(orch.current_stage(), BootstrapStage::Idle);
+
+        // Too few samples to assess also aborts.
+        let mut short = vec![0x5Au8; 16];
+        assert_eq!(orch.check_entropy(&mut short).map(|_| ()), Err(ProtocolError::BootstrapFailed));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_seed_pad_gated() {
+        /// LCG whose top `bits` bits of each step go into a byte (0 = stuck).
+        struct Source {
+            state: u64,
+            bits: u32,
+        }
+        impl EntropySource for Source {
+            fn name(&self) -> &'static str { "Raw" }
+            fn fill(&mut self, dest: &mut [u8]) -> Result<(), crate::entropy::EntropyError> {
+                for b in dest.iter_mut() {
+                    self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    *b = 0x5A ^ ((self.state >> 32) >> (32 - self.bits)) as u8;
+                }
+                Ok(())
+            }
+            fn entropy_estimate(&self) -> f64 { self.bits as f64 }
+        }
+        let len = crate::entropy::sp800_90b::non_iid::MIN_SAMPLES;
+        let key = vec![0xC3u8; 2 * len];
+        let mut orch = BootstrapOrchestrator::new();
+        let mut good = Source { state: 3, bits: 8 };
+        assert_eq!(orch.seed_pad(&mut good, len, &key, [7; 16]).map(|_| ()), Err(ProtocolError::InvalidState));
+
+        while orch.current_stage() != BootstrapStage::KeyDerivation {
+            orch.advance().unwrap();
+        }
+        // Even a good source yields less than `len`: the estimate is a lower bound.
+        let pad = orch.seed_pad(&mut good, len, &key, [7; 16]).unwrap();
+        assert!(pad.total_capacity() > (len / 2) as u64);
+        assert!(pad.total_capacity() <= (len - EXTRACT_MARGIN) as u64);
+        assert_eq!(orch.seed_pad(&mut good, len, &key[..len], [7; 16]).map(|_| ()), Err(ProtocolError::CryptoError));
+
+        // Two raw bits per byte assess at about one bit: enough for the gate, but the pad
+        // shrinks to the assessed min-entropy.
+        let mut weak = Source { state: 5, bits: 2 };
+        let mut samples = vec![0u8; len];
+        Source { state: 5, bits: 2 }.fill(&mut samples).unwrap();
+        let per_byte = orch.check_entropy(&mut samples).unwrap().per_byte();
+        assert!(per_byte < 1.5);
+        let pad = orch.seed_pad(&mut weak, len, &key, [7; 16]).unwrap();
+        let expected = (len as f64 * per_byte / 8.0) as usize - EXTRACT_MARGIN;
+        assert_eq!(pad.total_capacity(), expected as u64);
+        assert!(pad.total_capacity() < (len / 4) as u64);
+
+        // A stuck raw source never becomes pad material and aborts the bootstrap.
+        let mut stuck = Source { state: 0, bits: 0 };
+        assert_eq!(orch.seed_pad(&mut stuck, len, &key, [7; 16]).map(|_| ()), Err(ProtocolError::BootstrapFailed));
+        assert_eq!(orch.current_stage(), BootstrapStage::Idle);
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_timeout() {
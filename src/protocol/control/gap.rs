@@ -3,9 +3,12 @@
 //! Manages group policies, member additions, and removals.
 //! Compliant with TOGM Whitepaper Section 7.2 (Access Control).
 
+use crate::core::padding::PaddingPolicy;
+use crate::core::poly_mac::MacMode;
+use crate::mpc::quorum::WeightedPolicy;
 use crate::protocol::ProtocolError;
 use crate::protocol::group_permissions::permissions::PermissionManager;
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec::Vec;
 
 /// Operations supported by GAP.
@@ -13,16 +16,40 @@ use alloc::vec::Vec;
 pub enum GapOperation {
     /// Add a new member (requires Admin/Owner).
     AddMember { user_id: [u8; 32], role: u8 },
-    /// Remove an existing member (requires Admin/Owner).
+    /// Remove an existing member (requires Admin/Owner). Rejected if the remaining weight
+    /// would no longer cover the threshold; lower it with `UpdatePolicy` first.
     RemoveMember { user_id: [u8; 32] },
-    /// Update group policy (e.g., threshold change).
+    /// Update group policy (e.g., threshold change). The threshold is in units of weight.
     UpdatePolicy { new_threshold: u8 },
+    /// Change how many share identifiers a member holds (see `GroupState::share_policy`;
+    /// like membership changes, this requires a reshare).
+    SetWeight { user_id: [u8; 32], weight: u8 },
+    /// Re-deal unused pad lanes; `consumed[i]` is the agreed usage of lane `i` (see
+    /// `GroupState::lane_index`), with one entry per lane ever assigned.
+    RebalanceLanes { epoch: u64, consumed: Vec<u64> },
+    /// Change the group's length-hiding padding policy.
+    SetPadding { policy: PaddingPolicy },
+    /// Change how messages are authenticated (recorded in every envelope). Wegman–Carter is
+    /// refused: the group has no shared hash key to authenticate with.
+    SetMacMode { mode: MacMode },
 }
 
 /// Holds the group's membership and policy state.
 pub struct GroupState {
     pub members: BTreeSet<[u8; 32]>,
     pub threshold: u8,
+    /// Reconstruction weight of members other than the default of one.
+    pub weights: BTreeMap<[u8; 32], u8>,
+    /// Pad lane of every member, fixed when the member joins.
+    pub lanes: BTreeMap<[u8; 32], usize>,
+    /// Number of lanes assigned so far; lanes of removed members are never reassigned.
+    pub lane_count: usize,
+    /// Epoch of the last agreed pad lane rebalance.
+    pub lane_epoch: u64,
+    /// Padding applied to payloads before encryption (pad budget vs. length privacy).
+    pub padding: PaddingPolicy,
+    /// Message authentication mode (pad cost vs. tag length).
+    pub mac_mode: MacMode,
 }
 
 impl GroupState {
@@ -31,7 +58,47 @@ impl GroupState {
         for m in initial_members {
             members.insert(m);
         }
-        Self { members, threshold }
+        // Initial lanes follow sorted member order
+        let lanes: BTreeMap<[u8; 32], usize> = members.iter().enumerate().map(|(i, m)| (*m, i)).collect();
+        let lane_count = lanes.len();
+        Self {
+            members,
+            threshold,
+            weights: BTreeMap::new(),
+            lanes,
+            lane_count,
+            lane_epoch: 0,
+            padding: PaddingPolicy::default(),
+            mac_mode: MacMode::default(),
+        }
+    }
+
+    /// Pad lane index of a member. Stable for as long as the member stays in the group:
+    /// joins take the next unused lane and removals leave their lane unassigned.
+    pub fn lane_index(&self, user_id: &[u8; 32]) -> Option<usize> {
+        self.lanes.get(user_id).copied()
+    }
+
+    /// Reconstruction weight of a member (one unless set by `GapOperation::SetWeight`).
+    pub fn weight(&self, user_id: &[u8; 32]) -> u8 {
+        self.weights.get(user_id).copied().unwrap_or(1)
+    }
+
+    /// Total weight of all members.
+    pub fn total_weight(&self) -> usize {
+        self.members.iter().map(|m| self.weight(m) as usize).sum()
+    }
+
+    /// The weighted access structure for share dealing, with members in sorted order.
+    ///
+    /// Share identifiers follow from each member's sorted position and the weights before
+    /// it, so `AddMember`, `RemoveMember` and `SetWeight` renumber the identifiers of later
+    /// members, and `UpdatePolicy` changes the threshold. After any of them, shares dealt
+    /// under the previous policy must be redistributed with `mpc::reshare` before use; they
+    /// do not match the new policy.
+    pub fn share_policy(&self) -> Result<WeightedPolicy, ProtocolError> {
+        let weights = self.members.iter().map(|m| self.weight(m)).collect();
+        WeightedPolicy::new(weights, self.threshold).map_err(|_| ProtocolError::InvalidPayload)
     }
 }
 
@@ -79,24 +146,77 @@ impl GroupAdminProtocol {
                     // Idempotent or error? Let's say idempotent for now.
                 } else {
                     self.state.members.insert(*user_id);
+                    // New lanes are carved out by the next `RebalanceLanes`
+                    self.state.lanes.insert(*user_id, self.state.lane_count);
+                    self.state.lane_count += 1;
                     // Trigger DKG/PSS would happen here in full implementation
                 }
             }
             GapOperation::RemoveMember { user_id } => {
                 log::info!("GAP: Removing member {:?}", user_id);
+                // Never leave a threshold the remaining weight cannot reach
+                if self.state.members.contains(user_id) {
+                    let total = self.state.total_weight() - self.state.weight(user_id) as usize;
+                    if total < self.state.threshold as usize {
+                        return Err(ProtocolError::InvalidPayload);
+                    }
+                }
                 if !self.state.members.remove(user_id) {
                     // Member not found, maybe warning?
                 }
+                self.state.weights.remove(user_id);
+                self.state.lanes.remove(user_id);
                 // Trigger DKG/PSS would happen here
             }
             GapOperation::UpdatePolicy { new_threshold } => {
                  log::info!("GAP: Updating policy to k={}", new_threshold);
                  // Validate threshold vs member count
-                 if *new_threshold == 0 || *new_threshold as usize > self.state.members.len() {
+                 if *new_threshold == 0 || *new_threshold as usize > self.state.total_weight() {
                      return Err(ProtocolError::InvalidPayload); // Or InvalidPolicy
                  }
                  self.state.threshold = *new_threshold;
             }
+            GapOperation::SetWeight { user_id, weight } => {
+                log::info!("GAP: Setting weight of {:?} to {}", user_id, weight);
+                if !self.state.members.contains(user_id) || *weight == 0 {
+                    return Err(ProtocolError::InvalidPayload);
+                }
+                // The new total must still cover the threshold and fit in GF(256) identifiers
+                let total = self.state.total_weight() - self.state.weight(user_id) as usize + *weight as usize;
+                if total < self.state.threshold as usize || total > 255 {
+                    return Err(ProtocolError::InvalidPayload);
+                }
+                if *weight == 1 {
+                    self.state.weights.remove(user_id);
+                } else {
+                    self.state.weights.insert(*user_id, *weight);
+                }
+                // Shares are re-dealt to the new identifiers via `mpc::reshare`
+            }
+            GapOperation::RebalanceLanes { epoch, consumed } => {
+                log::info!("GAP: Rebalancing pad lanes, epoch {}", epoch);
+                if *epoch != self.state.lane_epoch.wrapping_add(1) || consumed.len() != self.state.lane_count {
+                    return Err(ProtocolError::InvalidPayload);
+                }
+                self.state.lane_epoch = *epoch;
+                // Each member applies it locally via `pad::lifecycle::MasterPad::rebalance_lanes`
+            }
+            GapOperation::SetPadding { policy } => {
+                log::info!("GAP: Setting padding policy {:?}", policy);
+                // Reject unusable parameters up front
+                if policy.padded_len(0).is_err() {
+                    return Err(ProtocolError::InvalidPayload);
+                }
+                self.state.padding = *policy;
+            }
+            GapOperation::SetMacMode { mode } => {
+                log::info!("GAP: Setting MAC mode {:?}", mode);
+                // No member holds a Wegman–Carter hash key, so such a group could not send
+                if mode.validate().is_err() || matches!(mode, MacMode::WegmanCarter { .. }) {
+                    return Err(ProtocolError::InvalidPayload);
+                }
+                self.state.mac_mode = *mode;
+            }
         }
 
         Ok(())
@@ -138,6 +258,12 @@ mod tests {
         let perms = PermissionManager::new(vec![admin_id]);
         
         let op = GapOperation::RemoveMember { user_id: target_id };
+        // The remaining weight 1 cannot cover the threshold 2: nothing changes.
+        assert_eq!(gap.process_request(&op, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+        assert!(gap.state.members.contains(&target_id));
+        assert_eq!(gap.state.threshold, 2);
+
+        gap.process_request(&GapOperation::UpdatePolicy { new_threshold: 1 }, &admin_id, &perms).unwrap();
         gap.process_request(&op, &admin_id, &perms).unwrap();
         
         assert!(!gap.state.members.contains(&target_id));
@@ -157,5 +283,124 @@ mod tests {
         let err = gap.process_request(&op_invalid, &admin_id, &perms);
         assert_eq!(err, Err(ProtocolError::InvalidPayload));
     }
-}
 
+    #[test]
+    fn test_gap_rebalance_lanes() {
+        let admin_id = [1u8; 32];
+        let user_id = [2u8; 32];
+        let mut gap = GroupAdminProtocol::new(admin_id, vec![admin_id, user_id], 2);
+        let perms = PermissionManager::new(vec![admin_id]);
+        assert_eq!(gap.state.lane_index(&user_id), Some(1));
+
+        let op = GapOperation::RebalanceLanes { epoch: 1, consumed: vec![10, 0] };
+        gap.process_request(&op, &admin_id, &perms).unwrap();
+        assert_eq!(gap.state.lane_epoch, 1);
+
+        // Replayed epoch and wrong lane count are rejected.
+        assert_eq!(gap.process_request(&op, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+        let op_bad = GapOperation::RebalanceLanes { epoch: 2, consumed: vec![10] };
+        assert_eq!(gap.process_request(&op_bad, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_gap_lanes_stable() {
+        let admin_id = [5u8; 32];
+        let low_id = [1u8; 32];
+        let high_id = [9u8; 32];
+        let mut gap = GroupAdminProtocol::new(admin_id, vec![admin_id, high_id], 1);
+        let perms = PermissionManager::new(vec![admin_id]);
+        assert_eq!(gap.state.lane_index(&high_id), Some(1));
+
+        // A member sorting before everyone else joins: existing lanes do not move.
+        gap.process_request(&GapOperation::AddMember { user_id: low_id, role: 1 }, &admin_id, &perms).unwrap();
+        assert_eq!(gap.state.lane_index(&admin_id), Some(0));
+        assert_eq!(gap.state.lane_index(&high_id), Some(1));
+        assert_eq!(gap.state.lane_index(&low_id), Some(2));
+
+        // Removal retires the lane without renumbering or reusing it.
+        gap.process_request(&GapOperation::RemoveMember { user_id: admin_id }, &admin_id, &perms).unwrap();
+        assert_eq!(gap.state.lane_index(&admin_id), None);
+        assert_eq!(gap.state.lane_index(&high_id), Some(1));
+        assert_eq!(gap.state.lane_count, 3);
+        let op = GapOperation::RebalanceLanes { epoch: 1, consumed: vec![0, 0, 0] };
+        gap.process_request(&op, &admin_id, &perms).unwrap();
+    }
+
+    #[test]
+    fn test_gap_set_padding() {
+        let admin_id = [1u8; 32];
+        let mut gap = GroupAdminProtocol::new(admin_id, vec![admin_id], 1);
+        let perms = PermissionManager::new(vec![admin_id]);
+        assert_eq!(gap.state.padding, PaddingPolicy::Fixed { block: 4096 });
+
+        let op = GapOperation::SetPadding { policy: PaddingPolicy::Padme };
+        gap.process_request(&op, &admin_id, &perms).unwrap();
+        assert_eq!(gap.state.padding, PaddingPolicy::Padme);
+
+        let op_bad = GapOperation::SetPadding { policy: PaddingPolicy::PowerOfTwo { min: 0 } };
+        assert_eq!(gap.process_request(&op_bad, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_gap_set_mac_mode() {
+        let admin_id = [1u8; 32];
+        let user_id = [2u8; 32];
+        let mut gap = GroupAdminProtocol::new(admin_id, vec![admin_id, user_id], 1);
+        let perms = PermissionManager::new(vec![admin_id]);
+        assert_eq!(gap.state.mac_mode, MacMode::Sip64);
+
+        let op = GapOperation::SetMacMode { mode: MacMode::OneTime { tag_len: 16 } };
+        assert_eq!(gap.process_request(&op, &user_id, &perms), Err(ProtocolError::PermissionDenied));
+        gap.process_request(&op, &admin_id, &perms).unwrap();
+        assert_eq!(gap.state.mac_mode.pad_cost(), 32);
+
+        // Without a shared hash key, Wegman–Carter would leave the group unable to send.
+        let op_wc = GapOperation::SetMacMode { mode: MacMode::WegmanCarter { tag_len: 16 } };
+        assert_eq!(gap.process_request(&op_wc, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+        assert_eq!(gap.state.mac_mode, MacMode::OneTime { tag_len: 16 });
+
+        let op_bad = GapOperation::SetMacMode { mode: MacMode::OneTime { tag_len: 12 } };
+        assert_eq!(gap.process_request(&op_bad, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_gap_set_weight() {
+        let admin_id = [1u8; 32];
+        let server_id = [2u8; 32];
+        let user_id = [3u8; 32];
+        let mut gap = GroupAdminProtocol::new(admin_id, vec![admin_id, server_id, user_id], 2);
+        let perms = PermissionManager::new(vec![admin_id]);
+
+        let op = GapOperation::SetWeight { user_id: server_id, weight: 3 };
+        assert_eq!(gap.process_request(&op, &user_id, &perms), Err(ProtocolError::PermissionDenied));
+        gap.process_request(&op, &admin_id, &perms).unwrap();
+        assert_eq!(gap.state.weight(&server_id), 3);
+        assert_eq!(gap.state.total_weight(), 5);
+
+        // The threshold may now exceed the member count, up to the total weight.
+        gap.process_request(&GapOperation::UpdatePolicy { new_threshold: 4 }, &admin_id, &perms).unwrap();
+        let policy = gap.state.share_policy().unwrap();
+        assert_eq!(policy.weights(), &[1, 3, 1]);
+        assert!(policy.is_qualified(&[0, 1]));
+        assert!(!policy.is_qualified(&[0, 2]));
+
+        // Dropping below the threshold, zero weight and non-members are rejected.
+        let op_low = GapOperation::SetWeight { user_id: server_id, weight: 1 };
+        assert_eq!(gap.process_request(&op_low, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+        let op_zero = GapOperation::SetWeight { user_id, weight: 0 };
+        assert_eq!(gap.process_request(&op_zero, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+        let op_stranger = GapOperation::SetWeight { user_id: [9u8; 32], weight: 2 };
+        assert_eq!(gap.process_request(&op_stranger, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+
+        // Removing the weight-3 member would leave weight 2 below the threshold of 4.
+        let op_remove = GapOperation::RemoveMember { user_id: server_id };
+        assert_eq!(gap.process_request(&op_remove, &admin_id, &perms), Err(ProtocolError::InvalidPayload));
+        assert_eq!(gap.state.weight(&server_id), 3);
+        assert_eq!(gap.state.threshold, 4);
+
+        gap.process_request(&GapOperation::UpdatePolicy { new_threshold: 2 }, &admin_id, &perms).unwrap();
+        gap.process_request(&op_remove, &admin_id, &perms).unwrap();
+        assert!(gap.state.weights.is_empty());
+        assert!(gap.state.share_policy().is_ok());
+    }
+}
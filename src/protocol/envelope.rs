@@ -0,0 +1,412 @@
+//! OTP Wire Envelope.
+//!
+//! Stable, versioned binary encoding for OTP group messages
+//! (`block_id || sender_idx || ciphertext || mac`, Whitepaper Section 7.1).
+//!
+//! # Layout (version 2, integers little-endian)
+//! ```text
+//! magic "TOGM" (4) | version (1) | mac_mode (1) | group_id (16) | sender_idx (2) | offset (8)
+//! | payload_len (4) | ciphertext (payload_len) | tag (mode tag length)
+//! ```
+//! `offset` is the pad (lane) offset of the block `keystream || mac_material` used for the
+//! message; `mac_mode` is `MacMode::to_wire` and fixes the tag length (64 bytes for SIP-64).
+//!
+//! Version 1 envelopes (no `mac_mode` byte, always SIP-64) still parse, but only open for a
+//! group whose mode is SIP-64.
+//!
+//! `seal_padded` / `open_padded` apply the group's `PaddingPolicy` to the plaintext, so the
+//! ciphertext length on the wire is the padded length.
+//!
+//! # Security
+//! - **Header Binding**: `seal`/`open` pass the complete encoded header as MAC metadata, so
+//!   every header field, including the MAC mode, is authenticated.
+//! - **No Downgrade**: Receivers open with the group's `MacMode` (`GroupState::mac_mode`), never
+//!   the mode the sender's header claims; any other mode or version is rejected before
+//!   verification.
+//! - **Strict Parsing**: The input length must match the header exactly; no trailing bytes.
+//! - **Encrypt-then-MAC**: The tag is verified before any decryption.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use crate::core::otp_engine::{encrypt_and_tag_mode, decrypt_and_verify_mode, OtpError};
+use crate::core::padding::PaddingPolicy;
+use crate::core::poly_mac::MacMode;
+use crate::protocol::ProtocolError;
+use zeroize::Zeroize;
+
+/// Envelope magic bytes.
+pub const ENVELOPE_MAGIC: [u8; 4] = *b"TOGM";
+/// Current envelope version.
+pub const ENVELOPE_VERSION: u8 = 2;
+/// Encoded header length in bytes (current version).
+pub const HEADER_LEN: usize = 4 + 1 + 1 + 16 + 2 + 8 + 4;
+/// Encoded header length of legacy version 1 envelopes.
+pub const HEADER_LEN_V1: usize = HEADER_LEN - 1;
+
+/// An owned OTP message envelope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtpEnvelope {
+    /// Wire version (1 for legacy SIP-64 envelopes).
+    pub version: u8,
+    /// Group the message belongs to.
+    pub group_id: [u8; 16],
+    /// Sender's member (lane) index.
+    pub sender_idx: u16,
+    /// Pad offset of the block used for this message.
+    pub offset: u64,
+    /// Authentication mode of `tag`.
+    pub mac_mode: MacMode,
+    /// OTP ciphertext.
+    pub ciphertext: Vec<u8>,
+    /// Tag over `ciphertext` with the header as metadata.
+    pub tag: Vec<u8>,
+}
+
+/// A parsed envelope borrowing from the wire buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OtpEnvelopeRef<'a> {
+    /// Wire version.
+    pub version: u8,
+    /// Group the message belongs to.
+    pub group_id: [u8; 16],
+    /// Sender's member (lane) index.
+    pub sender_idx: u16,
+    /// Pad offset of the block used for this message.
+    pub offset: u64,
+    /// Authentication mode of `tag`.
+    pub mac_mode: MacMode,
+    header: &'a [u8],
+    /// OTP ciphertext.
+    pub ciphertext: &'a [u8],
+    /// Message tag.
+    pub tag: &'a [u8],
+}
+
+fn encode_header(version: u8, mac_mode: MacMode, group_id: &[u8; 16], sender_idx: u16, offset: u64, payload_len: usize) -> Result<Vec<u8>, ProtocolError> {
+    let payload_len: u32 = payload_len.try_into().map_err(|_| ProtocolError::InvalidPayload)?;
+    let mut h = Vec::with_capacity(HEADER_LEN);
+    h.extend_from_slice(&ENVELOPE_MAGIC);
+    h.push(version);
+    match version {
+        1 if mac_mode == MacMode::Sip64 => {}
+        ENVELOPE_VERSION => h.push(mac_mode.to_wire()),
+        _ => return Err(ProtocolError::MalformedMessage),
+    }
+    h.extend_from_slice(group_id);
+    h.extend_from_slice(&sender_idx.to_le_bytes());
+    h.extend_from_slice(&offset.to_le_bytes());
+    h.extend_from_slice(&payload_len.to_le_bytes());
+    Ok(h)
+}
+
+// Rejects envelopes not sealed the way the group authenticates: another MAC mode, or the
+// legacy version unless the group itself uses SIP-64.
+fn check_mode(version: u8, mac_mode: MacMode, expected: MacMode) -> Result<(), ProtocolError> {
+    let version_ok = version == ENVELOPE_VERSION || (version == 1 && expected == MacMode::Sip64);
+    if !version_ok || mac_mode != expected {
+        return Err(ProtocolError::AuthenticationFailed);
+    }
+    Ok(())
+}
+
+fn map_otp_error(e: OtpError) -> ProtocolError {
+    match e {
+        OtpError::TagMismatch => ProtocolError::AuthenticationFailed,
+        OtpError::KeystreamTooShort | OtpError::BlockTooSmall | OtpError::InvalidMacMode | OtpError::LengthMismatch => ProtocolError::CryptoError,
+    }
+}
+
+// Strips padding in place, wiping the decrypted buffer if it is malformed.
+fn unpad_owned(padding: &PaddingPolicy, mut plaintext: Vec<u8>) -> Result<Vec<u8>, ProtocolError> {
+    match padding.unpad(&plaintext) {
+        Ok(inner) => {
+            let len = inner.len();
+            plaintext[len..].zeroize();
+            plaintext.truncate(len);
+            Ok(plaintext)
+        }
+        Err(_) => {
+            plaintext.zeroize();
+            Err(ProtocolError::InvalidPayload)
+        }
+    }
+}
+
+impl OtpEnvelope {
+    /// Encrypts `plaintext` with `block` (`keystream || mac_key`) under SIP-64 and binds the
+    /// header into the tag.
+    pub fn seal(group_id: [u8; 16], sender_idx: u16, offset: u64, plaintext: &[u8], block: &[u8]) -> Result<Self, ProtocolError> {
+        Self::seal_with(group_id, sender_idx, offset, MacMode::Sip64, plaintext, block, &[])
+    }
+
+    /// Like `seal`, authenticating with `mac_mode`; `block` is `keystream || mac_material`
+    /// (see `otp_engine::encrypt_and_tag_mode`) and `hash_key` is the group's Wegman–Carter key.
+    pub fn seal_with(group_id: [u8; 16], sender_idx: u16, offset: u64, mac_mode: MacMode, plaintext: &[u8], block: &[u8], hash_key: &[u8]) -> Result<Self, ProtocolError> {
+        let header = encode_header(ENVELOPE_VERSION, mac_mode, &group_id, sender_idx, offset, plaintext.len())?;
+        let (ciphertext, tag) = encrypt_and_tag_mode(mac_mode, plaintext, &header, block, hash_key).map_err(map_otp_error)?;
+        Ok(Self { version: ENVELOPE_VERSION, group_id, sender_idx, offset, mac_mode, ciphertext, tag })
+    }
+
+    /// Like `seal_with`, padding `plaintext` under `padding` first; `block` must cover the
+    /// padded length (see `pad::lifecycle::MasterPad::get_padded_block`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn seal_padded(group_id: [u8; 16], sender_idx: u16, offset: u64, mac_mode: MacMode, padding: &PaddingPolicy, plaintext: &[u8], block: &[u8], hash_key: &[u8]) -> Result<Self, ProtocolError> {
+        let padded = padding.pad(plaintext).map_err(|_| ProtocolError::InvalidPayload)?;
+        Self::seal_with(group_id, sender_idx, offset, mac_mode, &padded, block, hash_key)
+    }
+
+    /// Verifies the tag and decrypts with `block`, for a group using SIP-64.
+    pub fn open(&self, block: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        self.open_with(MacMode::Sip64, block, &[])
+    }
+
+    /// Like `open`, for a group authenticating with `expected` (and, for Wegman–Carter,
+    /// the group's hash key). Envelopes claiming any other mode are rejected.
+    pub fn open_with(&self, expected: MacMode, block: &[u8], hash_key: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        check_mode(self.version, self.mac_mode, expected)?;
+        let header = self.header()?;
+        decrypt_and_verify_mode(expected, &self.ciphertext, &header, block, hash_key, &self.tag).map_err(map_otp_error)
+    }
+
+    /// Like `open_with`, stripping padding applied under `padding`.
+    pub fn open_padded(&self, expected: MacMode, padding: &PaddingPolicy, block: &[u8], hash_key: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        unpad_owned(padding, self.open_with(expected, block, hash_key)?)
+    }
+
+    /// Serialises the envelope.
+    pub fn encode(&self) -> Result<Vec<u8>, ProtocolError> {
+        let mut out = self.header()?;
+        out.reserve(self.ciphertext.len() + self.tag.len());
+        out.extend_from_slice(&self.ciphertext);
+        out.extend_from_slice(&self.tag);
+        Ok(out)
+    }
+
+    /// Parses and copies an encoded envelope.
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        OtpEnvelopeRef::parse(bytes).map(|r| r.to_owned())
+    }
+
+    fn header(&self) -> Result<Vec<u8>, ProtocolError> {
+        encode_header(self.version, self.mac_mode, &self.group_id, self.sender_idx, self.offset, self.ciphertext.len())
+    }
+}
+
+impl<'a> OtpEnvelopeRef<'a> {
+    /// Zero-copy parse with strict length validation.
+    ///
+    /// # Errors
+    /// * `ProtocolError::MalformedMessage` on wrong magic, unsupported version, unknown MAC
+    ///   mode, or a declared length that overflows `usize`.
+    /// * `ProtocolError::InvalidPayload` if the length does not match the header.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, ProtocolError> {
+        if bytes.len() < HEADER_LEN_V1 {
+            return Err(ProtocolError::InvalidPayload);
+        }
+        if bytes[0..4] != ENVELOPE_MAGIC {
+            return Err(ProtocolError::MalformedMessage);
+        }
+        let version = bytes[4];
+        let (header_len, mac_mode) = match version {
+            1 => (HEADER_LEN_V1, MacMode::Sip64),
+            ENVELOPE_VERSION if bytes.len() >= HEADER_LEN => {
+                (HEADER_LEN, MacMode::from_wire(bytes[5]).ok_or(ProtocolError::MalformedMessage)?)
+            }
+            ENVELOPE_VERSION => return Err(ProtocolError::InvalidPayload),
+            _ => return Err(ProtocolError::MalformedMessage),
+        };
+        let (header, rest) = bytes.split_at(header_len);
+        // Fields after magic, version and (v2) mode byte.
+        let f = &header[header_len - 30..];
+        let group_id: [u8; 16] = f[0..16].try_into().map_err(|_| ProtocolError::MalformedMessage)?;
+        let sender_idx = u16::from_le_bytes(f[16..18].try_into().map_err(|_| ProtocolError::MalformedMessage)?);
+        let offset = u64::from_le_bytes(f[18..26].try_into().map_err(|_| ProtocolError::MalformedMessage)?);
+        let payload_len = u32::from_le_bytes(f[26..30].try_into().map_err(|_| ProtocolError::MalformedMessage)?) as usize;
+
+        // The length comes off the wire: on 32-bit targets it can overflow (v1 and v2 alike).
+        let expected = payload_len.checked_add(mac_mode.tag_len()).ok_or(ProtocolError::MalformedMessage)?;
+        if rest.len() != expected {
+            return Err(ProtocolError::InvalidPayload);
+        }
+        let (ciphertext, tag) = rest.split_at(payload_len);
+
+        Ok(Self { version, group_id, sender_idx, offset, mac_mode, header, ciphertext, tag })
+    }
+
+    /// Verifies the tag (over the received header bytes) and decrypts with `block`, for a
+    /// group using SIP-64.
+    pub fn open(&self, block: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        self.open_with(MacMode::Sip64, block, &[])
+    }
+
+    /// Like `open`, for a group authenticating with `expected` (and, for Wegman–Carter,
+    /// the group's hash key). Envelopes claiming any other mode are rejected.
+    pub fn open_with(&self, expected: MacMode, block: &[u8], hash_key: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        check_mode(self.version, self.mac_mode, expected)?;
+        decrypt_and_verify_mode(expected, self.ciphertext, self.header, block, hash_key, self.tag).map_err(map_otp_error)
+    }
+
+    /// Like `open_with`, stripping padding applied under `padding`.
+    pub fn open_padded(&self, expected: MacMode, padding: &PaddingPolicy, block: &[u8], hash_key: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        unpad_owned(padding, self.open_with(expected, block, hash_key)?)
+    }
+
+    /// Copies into an owned envelope.
+    pub fn to_owned(&self) -> OtpEnvelope {
+        OtpEnvelope {
+            version: self.version,
+            group_id: self.group_id,
+            sender_idx: self.sender_idx,
+            offset: self.offset,
+            mac_mode: self.mac_mode,
+            ciphertext: self.ciphertext.to_vec(),
+            tag: self.tag.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::otp_engine::encrypt_and_tag;
+    use crate::core::sip64::MAC_LEN;
+
+    fn block(payload_len: usize) -> Vec<u8> {
+        (0..payload_len + MAC_LEN).map(|i| (i as u8).wrapping_mul(29).wrapping_add(3)).collect()
+    }
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        let blk = block(11);
+        let env = OtpEnvelope::seal([7u8; 16], 3, 4096, b"hello group", &blk).unwrap();
+        let wire = env.encode().unwrap();
+        assert_eq!(wire.len(), HEADER_LEN + 11 + MAC_LEN);
+        assert_eq!(&wire[..4], b"TOGM");
+
+        let parsed = OtpEnvelopeRef::parse(&wire).unwrap();
+        assert_eq!(parsed.sender_idx, 3);
+        assert_eq!(parsed.offset, 4096);
+        assert_eq!(parsed.open(&blk).unwrap(), b"hello group");
+        assert_eq!(OtpEnvelope::decode(&wire).unwrap(), env);
+        assert_eq!(env.open(&blk).unwrap(), b"hello group");
+    }
+
+    #[test]
+    fn test_envelope_header_is_authenticated() {
+        let blk = block(5);
+        let wire = OtpEnvelope::seal([1u8; 16], 0, 0, b"abcde", &blk).unwrap().encode().unwrap();
+        // Flip one bit in each header field after the version and mode bytes.
+        for idx in [6usize, 22, 24] {
+            let mut forged = wire.clone();
+            forged[idx] ^= 1;
+            let parsed = OtpEnvelopeRef::parse(&forged).unwrap();
+            assert_eq!(parsed.open(&blk), Err(ProtocolError::AuthenticationFailed));
+        }
+    }
+
+    #[test]
+    fn test_envelope_strict_parsing() {
+        let blk = block(5);
+        let wire = OtpEnvelope::seal([1u8; 16], 0, 0, b"abcde", &blk).unwrap().encode().unwrap();
+
+        assert_eq!(OtpEnvelopeRef::parse(&wire[..wire.len() - 1]), Err(ProtocolError::InvalidPayload));
+        let mut longer = wire.clone();
+        longer.push(0);
+        assert_eq!(OtpEnvelopeRef::parse(&longer), Err(ProtocolError::InvalidPayload));
+        let mut bad_magic = wire.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(OtpEnvelopeRef::parse(&bad_magic), Err(ProtocolError::MalformedMessage));
+        let mut bad_version = wire.clone();
+        bad_version[4] = 9;
+        assert_eq!(OtpEnvelopeRef::parse(&bad_version), Err(ProtocolError::MalformedMessage));
+        assert_eq!(OtpEnvelopeRef::parse(&[]), Err(ProtocolError::InvalidPayload));
+
+        // A maximal declared length is rejected without arithmetic overflow.
+        let mut huge = wire.clone();
+        huge[HEADER_LEN - 4..HEADER_LEN].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(OtpEnvelopeRef::parse(&huge).is_err());
+    }
+
+    #[test]
+    fn test_envelope_short_mac_modes() {
+        let hk = [0x5Cu8; 16];
+        for mode in [MacMode::OneTime { tag_len: 16 }, MacMode::WegmanCarter { tag_len: 16 }] {
+            // Block only needs the keystream and `pad_cost` bytes, not a 64-byte key.
+            let blk = &block(3)[..3 + mode.pad_cost()];
+            let wire = OtpEnvelope::seal_with([2u8; 16], 1, 77, mode, b"yes", blk, &hk).unwrap().encode().unwrap();
+            assert_eq!(wire.len(), HEADER_LEN + 3 + 16);
+            let parsed = OtpEnvelopeRef::parse(&wire).unwrap();
+            assert_eq!(parsed.mac_mode, mode);
+            assert_eq!(parsed.open_with(mode, blk, &hk).unwrap(), b"yes");
+
+            // Swapping or downgrading the recorded mode never opens.
+            let mut forged = wire.clone();
+            forged[5] = MacMode::OneTime { tag_len: 16 }.to_wire() ^ MacMode::WegmanCarter { tag_len: 16 }.to_wire() ^ wire[5];
+            let swapped = OtpEnvelopeRef::parse(&forged).unwrap();
+            assert_eq!(swapped.open_with(mode, &block(3), &hk), Err(ProtocolError::AuthenticationFailed));
+            assert!(swapped.open_with(swapped.mac_mode, &block(3), &hk).is_err());
+            forged[5] = MacMode::Sip64.to_wire();
+            assert_eq!(OtpEnvelopeRef::parse(&forged), Err(ProtocolError::InvalidPayload));
+        }
+    }
+
+    #[test]
+    fn test_envelope_rejects_unexpected_mode() {
+        // A correctly tagged SIP-64 envelope does not open for a group using Wegman–Carter.
+        let blk = block(5);
+        let hk = [0x5Cu8; 16];
+        let group = MacMode::WegmanCarter { tag_len: 16 };
+        let env = OtpEnvelope::seal([1u8; 16], 0, 0, b"abcde", &blk).unwrap();
+        assert_eq!(env.open_with(group, &blk, &hk), Err(ProtocolError::AuthenticationFailed));
+        let wire = env.encode().unwrap();
+        let parsed = OtpEnvelopeRef::parse(&wire).unwrap();
+        assert_eq!(parsed.open_with(group, &blk, &hk), Err(ProtocolError::AuthenticationFailed));
+        assert_eq!(parsed.open_with(MacMode::Sip64, &blk, &[]).unwrap(), b"abcde");
+    }
+
+    #[test]
+    fn test_envelope_padded() {
+        let padding = PaddingPolicy::Fixed { block: 32 };
+        let mode = MacMode::OneTime { tag_len: 16 };
+        let blk = &block(32)[..32 + mode.pad_cost()];
+        let yes = OtpEnvelope::seal_padded([3u8; 16], 0, 0, mode, &padding, b"yes", blk, &[]).unwrap();
+        let no = OtpEnvelope::seal_padded([3u8; 16], 0, 0, mode, &padding, b"no", blk, &[]).unwrap();
+        // Both answers look the same on the wire.
+        assert_eq!(yes.encode().unwrap().len(), no.encode().unwrap().len());
+        assert_eq!(yes.ciphertext.len(), 32);
+
+        let wire = yes.encode().unwrap();
+        let parsed = OtpEnvelopeRef::parse(&wire).unwrap();
+        assert_eq!(parsed.open_padded(mode, &padding, blk, &[]).unwrap(), b"yes");
+        assert_eq!(yes.open_padded(mode, &padding, blk, &[]).unwrap(), b"yes");
+        // A receiver expecting another bucket size rejects the payload.
+        assert_eq!(parsed.open_padded(mode, &PaddingPolicy::Fixed { block: 16 }, blk, &[]), Err(ProtocolError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_envelope_accepts_v1() {
+        let blk = block(4);
+        let mut wire = Vec::new();
+        wire.extend_from_slice(b"TOGM");
+        wire.push(1);
+        wire.extend_from_slice(&[9u8; 16]);
+        wire.extend_from_slice(&5u16.to_le_bytes());
+        wire.extend_from_slice(&64u64.to_le_bytes());
+        wire.extend_from_slice(&4u32.to_le_bytes());
+        assert_eq!(wire.len(), HEADER_LEN_V1);
+        let (ct, tag) = encrypt_and_tag(b"v1v1", &wire, &blk).unwrap();
+        wire.extend_from_slice(&ct);
+        wire.extend_from_slice(&tag);
+
+        let parsed = OtpEnvelopeRef::parse(&wire).unwrap();
+        assert_eq!((parsed.version, parsed.mac_mode, parsed.sender_idx), (1, MacMode::Sip64, 5));
+        assert_eq!(parsed.open(&blk).unwrap(), b"v1v1");
+        let owned = OtpEnvelope::decode(&wire).unwrap();
+        assert_eq!(owned.open(&blk).unwrap(), b"v1v1");
+        assert_eq!(owned.encode().unwrap(), wire);
+        // Legacy envelopes never open for a group that moved off SIP-64.
+        let group = MacMode::OneTime { tag_len: 16 };
+        assert_eq!(parsed.open_with(group, &blk, &[]), Err(ProtocolError::AuthenticationFailed));
+    }
+}
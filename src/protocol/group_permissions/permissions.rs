@@ -23,7 +23,11 @@ impl PermissionManager {
         match op {
             GapOperation::AddMember { .. } 
             | GapOperation::RemoveMember { .. } 
-            | GapOperation::UpdatePolicy { .. } => {
+            | GapOperation::UpdatePolicy { .. }
+            | GapOperation::SetWeight { .. }
+            | GapOperation::RebalanceLanes { .. }
+            | GapOperation::SetPadding { .. }
+            | GapOperation::SetMacMode { .. } => {
                 self.is_admin(user_id)
             }
         }
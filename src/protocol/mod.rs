@@ -14,6 +14,7 @@ pub mod control;
 pub mod group_permissions;
 pub mod multi_device;
 pub mod messaging;
+pub mod envelope;
 pub mod state_machine;
 
 /// Errors related to protocol execution.
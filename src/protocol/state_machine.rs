@@ -8,13 +8,16 @@
 //! - **Bootstrapping**: Participating in group formation or joining (Section 6).
 //! - **Active**: Normal operation, sending/receiving messages.
 //! - **ConsensusPending**: Waiting for critical consensus (DBAP) (Section 8).
-//! - **Recovery**: Restoring from backup or fetching history.
+//! - **Recovery**: Restoring from backup or fetching history, or repairing a lost share
+//!   (`mpc::repair`).
 //! - **Lockdown**: Security violation detected (Iron Laws triggered), keys zeroized.
 //!
 //! # Whitepaper Compliance
 //! - Section 8: System States and Transitions.
 
 use super::ProtocolError;
+use crate::mpc::repair::Enrollment;
+use crate::mpc::share::Share;
 use alloc::vec::Vec;
 use alloc::boxed::Box;
 
@@ -161,6 +164,21 @@ impl StateMachine {
         }
     }
 
+    /// Completes a share repair and transitions Recovery -> Active.
+    ///
+    /// The replacement device rejoins with the identifier `enrollment` was opened for.
+    /// If any helper's partial is missing the node stays in Recovery and `enrollment` keeps
+    /// collecting partials; call again once they have arrived.
+    pub fn complete_repair(&mut self, enrollment: &Enrollment) -> Result<Share, ProtocolError> {
+        if self.current_state != State::Recovery {
+            return Err(ProtocolError::InvalidState);
+        }
+        let share = enrollment.finish().map_err(|_| ProtocolError::CryptoError)?;
+        let trans = self.begin_transition(State::Active);
+        trans.commit();
+        Ok(share)
+    }
+
     /// Triggers a security lockdown.
     ///
     /// This transition is allowed from ANY state.
@@ -261,6 +279,49 @@ mod tests {
         assert_eq!(sm.state(), State::Offline);
     }
     
+    #[test]
+    fn test_complete_repair() {
+        use crate::mpc::quorum::split_secret;
+        use crate::mpc::repair::{repair_contribute, RepairRound};
+        use crate::mpc::test_util::{MockEntropy, pairwise};
+
+        let mut rng = MockEntropy { state: 41 };
+        let shares = split_secret(b"node", 2, 3, &mut rng).unwrap();
+        let helpers = [1u16, 3];
+        let mut keys = pairwise(&[1, 3, 2], &mut rng);
+        let mut sm = StateMachine::new();
+        let mut enrollment = Enrollment::new(2, &helpers, 2, 4).unwrap();
+
+        // Only a recovering node can complete a repair.
+        assert_eq!(sm.complete_repair(&enrollment).map(|_| ()), Err(ProtocolError::InvalidState));
+
+        let masks: Vec<Vec<Share>> = [0, 2]
+            .iter()
+            .enumerate()
+            .map(|(i, &h)| repair_contribute(&shares[h], &helpers, 2, 2, &mut keys[i], &mut rng).unwrap())
+            .collect();
+        let partials: Vec<Share> = (0..2)
+            .map(|j| {
+                let mut round = RepairRound::new(helpers[j], &helpers, 2, 2, 4).unwrap();
+                for (i, from) in masks.iter().enumerate() {
+                    round.receive(helpers[i], &from[j], &keys[j]).unwrap();
+                }
+                round.finish(&mut keys[j]).unwrap()
+            })
+            .collect();
+
+        sm.start_recovery().unwrap();
+        // Helper 3's partial has not arrived: the node stays in Recovery and can retry.
+        enrollment.receive(1, &partials[0], &keys[2]).unwrap();
+        assert_eq!(sm.complete_repair(&enrollment).map(|_| ()), Err(ProtocolError::CryptoError));
+        assert_eq!(sm.state(), State::Recovery);
+
+        enrollment.receive(3, &partials[1], &keys[2]).unwrap();
+        let share = sm.complete_repair(&enrollment).unwrap();
+        assert_eq!(share.value, shares[1].value);
+        assert_eq!(sm.state(), State::Active);
+    }
+
     #[test]
     fn test_transaction_rollback() {
         let mut sm = StateMachine::new();